@@ -0,0 +1,203 @@
+//! Derive macros for CDLint, which generate the code lowering the spanned JSON of a Custom
+//! Difficulty into the structs describing it.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, LitStr};
+
+/// Derives `FromSpannedJson` and `FromSpannedMembers` for a struct which is lowered from a JSON
+/// object, with one member per field. Every field is lowered through its own `FromSpannedJson`
+/// impl. Unknown and duplicate members are recorded in the `LoweringContext` rather than reported
+/// right away.
+///
+/// Struct attributes:
+///
+/// - `#[cd(rename_all = "PascalCase")]`: member names are the field names in `PascalCase`, instead
+///   of the field names as-is.
+/// - `#[cd(ignore = "Member")]`: a member which is accepted but not lowered, e.g. because the game
+///   no longer reads it. Can be repeated.
+///
+/// Field attributes:
+///
+/// - `#[cd(rename = "Member")]`: the name of the member, instead of the one derived from the field
+///   name.
+/// - `#[cd(required)]`: the member has to be present. Absent members are otherwise lowered to the
+///   field's `Default`.
+#[proc_macro_derive(FromSpannedJson, attributes(cd))]
+pub fn derive_from_spanned_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Member {
+    field: syn::Ident,
+    name: String,
+    required: bool,
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`FromSpannedJson` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`FromSpannedJson` can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut pascal_case = false;
+    let mut ignored = Vec::<LitStr>::new();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("cd")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                let case: LitStr = meta.value()?.parse()?;
+                if case.value() != "PascalCase" {
+                    return Err(meta.error("only `rename_all = \"PascalCase\"` is supported"));
+                }
+                pascal_case = true;
+            } else if meta.path.is_ident("ignore") {
+                ignored.push(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unknown `cd` attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let mut members = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.clone().unwrap();
+        let mut name = if pascal_case {
+            to_pascal_case(&ident.to_string())
+        } else {
+            ident.to_string()
+        };
+        let mut required = false;
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("cd")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("required") {
+                    required = true;
+                } else {
+                    return Err(meta.error("unknown `cd` attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        members.push(Member {
+            field: ident,
+            name,
+            required,
+        });
+    }
+
+    // Every type parameter is lowered, and defaulted if its member is absent.
+    let type_params = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause.predicates.push(parse_quote! {
+            #param: crate::handlers::FromSpannedJson + ::std::default::Default
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+
+    let names = members
+        .iter()
+        .map(|member| &member.name)
+        .collect::<Vec<_>>();
+    let locals = members
+        .iter()
+        .map(|member| format_ident!("__{}", member.field))
+        .collect::<Vec<_>>();
+    let inits = members.iter().zip(&locals).map(|(member, local)| {
+        let field = &member.field;
+        let name = &member.name;
+        if member.required {
+            quote! {
+                #field: match #local {
+                    ::std::option::Option::Some(val) => val,
+                    ::std::option::Option::None => return ::std::result::Result::Err(
+                        cx.missing_member(span, #name),
+                    ),
+                }
+            }
+        } else {
+            quote! { #field: #local.unwrap_or_default() }
+        }
+    });
+    let ignored_arm = (!ignored.is_empty()).then(|| quote! { #(#ignored)|* => {} });
+
+    Ok(quote! {
+        impl #impl_generics crate::handlers::FromSpannedJson for #ident #ty_generics #where_clause {
+            const EXPECTED: &'static str = "object";
+
+            fn from_spanned_json(
+                cx: &mut crate::handlers::LoweringContext<'_, '_>,
+                val: &crate::spanned::Spanned<crate::parser::Json>,
+            ) -> ::anyhow::Result<Self> {
+                let members = cx.expect_object(val)?;
+                <Self as crate::handlers::FromSpannedMembers>::from_members(cx, val.span, members)
+            }
+        }
+
+        impl #impl_generics crate::handlers::FromSpannedMembers for #ident #ty_generics #where_clause {
+            const MEMBERS: &'static [&'static str] = &[#(#names),*];
+
+            fn from_members(
+                cx: &mut crate::handlers::LoweringContext<'_, '_>,
+                span: ::chumsky::span::SimpleSpan,
+                members: &[(
+                    crate::spanned::Spanned<::std::string::String>,
+                    crate::spanned::Spanned<crate::parser::Json>,
+                )],
+            ) -> ::anyhow::Result<Self> {
+                #(let mut #locals = ::std::option::Option::None;)*
+                for (name, val) in cx.unique_members(members) {
+                    match name.val.as_str() {
+                        #(#names => {
+                            #locals = ::std::option::Option::Some(
+                                crate::handlers::FromSpannedJson::from_spanned_json(cx, val)
+                                    .map_err(|e| e.context(::std::format!(
+                                        "trying to lower member \"{}\"",
+                                        #names,
+                                    )))?,
+                            );
+                        })*
+                        #ignored_arm
+                        _ => cx.unknown_member(name, Self::MEMBERS),
+                    }
+                }
+                ::std::result::Result::Ok(Self {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
+
+/// `snake_case` to `PascalCase`, e.g. `extra_large_enemy_damage_resistance_b` to
+/// `ExtraLargeEnemyDamageResistanceB`.
+fn to_pascal_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,8 @@
+//! Feeds arbitrary bytes to the parser and the handlers. Seed it with the Custom Difficulties in
+//! `tests` so that it starts out with inputs which get past the parser.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| cdlint_fuzz::check_lower(bytes));
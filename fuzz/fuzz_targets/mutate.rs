@@ -0,0 +1,8 @@
+//! Feeds structured mutations of valid Custom Difficulties to the parser and the handlers.
+
+#![no_main]
+
+use cdlint_fuzz::mutation::MutatedCd;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|cd: MutatedCd| cdlint_fuzz::check_lower(cd.to_json().as_bytes()));
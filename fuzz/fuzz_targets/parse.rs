@@ -0,0 +1,7 @@
+//! Feeds arbitrary bytes to the parser.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| cdlint_fuzz::check_parse(bytes));
@@ -0,0 +1,62 @@
+//! What the fuzz targets check: that CDLint doesn't panic or overflow its stack on any input, and
+//! that whenever it fails to parse or lower a Custom Difficulty, it reports why with an error
+//! diagnostic, rather than only failing with an error message.
+
+use std::sync::OnceLock;
+
+use cdlint::config::Config;
+use cdlint::diagnostic::ReportKind;
+use cdlint::driver::{self, Input, Lowering};
+use cdlint::lint::LintSelection;
+use cdlint::{parser, Diagnostics};
+use confique::Config as _;
+
+pub mod mutation;
+
+/// The path diagnostics are reported against.
+const PATH: &str = "fuzz.json";
+
+/// The default config, as used when there is no `config.toml`.
+fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        Config::builder()
+            .load()
+            .expect("the default config should load")
+    })
+}
+
+/// Parses the input, checking that the parser reports why if it can't parse anything.
+pub fn check_parse(bytes: &[u8]) {
+    let input = Input::from_bytes(PATH.to_string(), bytes);
+    if parser::empty_input_report(&input.path, &input.src).is_some() {
+        return;
+    }
+    let parsed = parser::parse(config(), &input.path, &input.src);
+    if parsed.json.is_none() {
+        assert_reported(&parsed.errors, "nothing could be parsed");
+    }
+}
+
+/// Parses and lowers the input, checking that every failure is reported.
+pub fn check_lower(bytes: &[u8]) {
+    let input = Input::from_bytes(PATH.to_string(), bytes);
+    match driver::lower(config(), &LintSelection::default(), &input) {
+        Lowering::Lowered(_) => {}
+        Lowering::NothingToLower(diagnostics) => {
+            assert_reported(&diagnostics, "nothing could be lowered")
+        }
+        Lowering::Failed(diagnostics, e) => {
+            assert_reported(&diagnostics, &format!("lowering failed with `{e:#}`"))
+        }
+    }
+}
+
+fn assert_reported(diagnostics: &Diagnostics<'_>, failure: &str) {
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.kind == ReportKind::Error),
+        "{failure}, but no error was reported"
+    );
+}
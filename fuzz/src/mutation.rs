@@ -0,0 +1,296 @@
+//! Structured mutations of valid Custom Difficulties. Arbitrary bytes rarely get past the parser,
+//! so these make the fuzzer spend its time in the handlers instead: values of the wrong kind,
+//! removed, duplicated and misspelled members, unknown Enemy Descriptors and so on.
+
+use std::sync::OnceLock;
+
+use arbitrary::Arbitrary;
+
+/// The Custom Difficulties which are mutated.
+const SEEDS: &[&str] = &[
+    include_str!("../../tests/ND.json"),
+    include_str!("../../tests/EnemyExpansion.a2.json"),
+    include_str!("../../tests/test.cd.json"),
+    include_str!("../../tests/ui/clean.json"),
+    include_str!("../../tests/ui/enemy_descriptors.json"),
+    include_str!("../../tests/ui/enemy_pools.json"),
+    include_str!("../../tests/ui/pawn_stats.json"),
+    include_str!("../../tests/ui/seasonal_events.json"),
+    include_str!("../../tests/ui/suppressions.json"),
+    include_str!("../../tests/ui/values.json"),
+];
+
+/// A seed with mutations applied in order.
+#[derive(Debug, Arbitrary)]
+pub struct MutatedCd {
+    seed: usize,
+    mutations: Vec<Mutation>,
+}
+
+/// A change to the value at an index, counting the values of the seed in pre-order, starting
+/// from the top-level value's first member.
+#[derive(Debug, Arbitrary)]
+enum Mutation {
+    Replace(usize, Value),
+    /// Removes the value, or the member whose value it is.
+    Remove(usize),
+    /// Defines the member whose value it is again, or repeats the array element.
+    Duplicate(usize),
+    /// Renames the member whose value it is.
+    Rename(usize, Name),
+    /// Adds a member to the value if it is an object, or an element if it is an array.
+    Insert(usize, Name, Value),
+}
+
+#[derive(Debug, Arbitrary)]
+enum Value {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Str(Name),
+    Array(Vec<Value>),
+    Object(Vec<(Name, Value)>),
+}
+
+/// A member name or string, usually one which appears in the seeds, e.g. the name of a member, an
+/// Enemy Descriptor or a seasonal event.
+#[derive(Debug, Arbitrary)]
+enum Name {
+    Known(usize),
+    Other(String),
+}
+
+/// A JSON value. Unlike [`serde_json::Value`], an object can have the same member more than once,
+/// and a number can be anything the parser recovers from, e.g. `NaN`.
+#[derive(Debug, Clone)]
+enum Node {
+    Null,
+    Bool(bool),
+    Num(String),
+    Str(String),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl MutatedCd {
+    pub fn to_json(&self) -> String {
+        let mut cd = seeds()[self.seed % SEEDS.len()].clone();
+        for mutation in &self.mutations {
+            mutation.apply(&mut cd);
+        }
+        let mut json = String::new();
+        cd.write(&mut json);
+        json
+    }
+}
+
+impl Mutation {
+    fn apply(&self, cd: &mut Node) {
+        let values = cd.count() - 1;
+        if values == 0 {
+            // Only `Insert` can add a value to an empty Custom Difficulty.
+            if let Mutation::Insert(_, name, val) = self {
+                cd.insert(name, val);
+            }
+            return;
+        }
+        let (Mutation::Replace(index, ..)
+        | Mutation::Remove(index)
+        | Mutation::Duplicate(index)
+        | Mutation::Rename(index, ..)
+        | Mutation::Insert(index, ..)) = self;
+        let mut index = index % values;
+        cd.with_parent(&mut index, &mut |parent, i| match (self, parent) {
+            (Mutation::Replace(_, val), Node::Array(elements)) => elements[i] = val.to_node(),
+            (Mutation::Replace(_, val), Node::Object(members)) => members[i].1 = val.to_node(),
+            (Mutation::Remove(_), Node::Array(elements)) => {
+                elements.remove(i);
+            }
+            (Mutation::Remove(_), Node::Object(members)) => {
+                members.remove(i);
+            }
+            (Mutation::Duplicate(_), Node::Array(elements)) => {
+                elements.insert(i + 1, elements[i].clone())
+            }
+            (Mutation::Duplicate(_), Node::Object(members)) => {
+                members.insert(i + 1, members[i].clone())
+            }
+            (Mutation::Rename(_, name), Node::Object(members)) => members[i].0 = name.to_string(),
+            (Mutation::Insert(_, name, val), Node::Array(elements)) => {
+                elements[i].insert(name, val)
+            }
+            (Mutation::Insert(_, name, val), Node::Object(members)) => {
+                members[i].1.insert(name, val)
+            }
+            _ => {}
+        });
+    }
+}
+
+impl Value {
+    fn to_node(&self) -> Node {
+        match self {
+            Value::Null => Node::Null,
+            Value::Bool(b) => Node::Bool(*b),
+            Value::Integer(n) => Node::Num(n.to_string()),
+            Value::Float(n) if n.is_nan() => Node::Num("NaN".to_string()),
+            Value::Float(n) if n.is_infinite() => {
+                Node::Num(if *n > 0.0 { "Infinity" } else { "-Infinity" }.to_string())
+            }
+            Value::Float(n) => Node::Num(format!("{n:?}")),
+            Value::Str(s) => Node::Str(s.to_string()),
+            Value::Array(elements) => Node::Array(elements.iter().map(Value::to_node).collect()),
+            Value::Object(members) => Node::Object(
+                members
+                    .iter()
+                    .map(|(name, val)| (name.to_string(), val.to_node()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Name::Known(i) => f.write_str(&known_names()[i % known_names().len()]),
+            Name::Other(name) => f.write_str(name),
+        }
+    }
+}
+
+impl Node {
+    fn from_json(val: &serde_json::Value) -> Self {
+        match val {
+            serde_json::Value::Null => Node::Null,
+            serde_json::Value::Bool(b) => Node::Bool(*b),
+            serde_json::Value::Number(n) => Node::Num(n.to_string()),
+            serde_json::Value::String(s) => Node::Str(s.clone()),
+            serde_json::Value::Array(elements) => {
+                Node::Array(elements.iter().map(Node::from_json).collect())
+            }
+            serde_json::Value::Object(members) => Node::Object(
+                members
+                    .iter()
+                    .map(|(name, val)| (name.clone(), Node::from_json(val)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The number of values, including this one.
+    fn count(&self) -> usize {
+        1 + match self {
+            Node::Array(elements) => elements.iter().map(Node::count).sum(),
+            Node::Object(members) => members.iter().map(|(_, val)| val.count()).sum(),
+            _ => 0,
+        }
+    }
+
+    /// Calls `f` with the array or object containing the value at `index`, counting in pre-order
+    /// after this value, and the value's index in it. Returns whether the value was found.
+    fn with_parent(&mut self, index: &mut usize, f: &mut dyn FnMut(&mut Node, usize)) -> bool {
+        let len = match self {
+            Node::Array(elements) => elements.len(),
+            Node::Object(members) => members.len(),
+            _ => return false,
+        };
+        for i in 0..len {
+            if *index == 0 {
+                f(self, i);
+                return true;
+            }
+            *index -= 1;
+            let child = match self {
+                Node::Array(elements) => &mut elements[i],
+                Node::Object(members) => &mut members[i].1,
+                _ => unreachable!(),
+            };
+            if child.with_parent(index, f) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn insert(&mut self, name: &Name, val: &Value) {
+        match self {
+            Node::Array(elements) => elements.push(val.to_node()),
+            Node::Object(members) => members.push((name.to_string(), val.to_node())),
+            _ => {}
+        }
+    }
+
+    fn write(&self, json: &mut String) {
+        match self {
+            Node::Null => json.push_str("null"),
+            Node::Bool(b) => json.push_str(if *b { "true" } else { "false" }),
+            Node::Num(n) => json.push_str(n),
+            Node::Str(s) => json.push_str(&serde_json::Value::from(s.as_str()).to_string()),
+            Node::Array(elements) => {
+                json.push('[');
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    element.write(json);
+                }
+                json.push(']');
+            }
+            Node::Object(members) => {
+                json.push('{');
+                for (i, (name, val)) in members.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    json.push_str(&serde_json::Value::from(name.as_str()).to_string());
+                    json.push(':');
+                    val.write(json);
+                }
+                json.push('}');
+            }
+        }
+    }
+}
+
+fn seeds() -> &'static [Node] {
+    static SEED_NODES: OnceLock<Vec<Node>> = OnceLock::new();
+    SEED_NODES.get_or_init(|| {
+        SEEDS
+            .iter()
+            .map(|seed| {
+                let json = serde_json::from_str(seed).expect("seeds should be valid JSON");
+                Node::from_json(&json)
+            })
+            .collect()
+    })
+}
+
+/// The member names and strings which appear in the seeds.
+fn known_names() -> &'static [String] {
+    fn collect(node: &Node, names: &mut Vec<String>) {
+        match node {
+            Node::Str(s) => names.push(s.clone()),
+            Node::Array(elements) => elements.iter().for_each(|element| collect(element, names)),
+            Node::Object(members) => {
+                for (name, val) in members {
+                    names.push(name.clone());
+                    collect(val, names);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    static NAMES: OnceLock<Vec<String>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        let mut names = Vec::new();
+        for seed in seeds() {
+            collect(seed, &mut names);
+        }
+        names.sort();
+        names.dedup();
+        names
+    })
+}
@@ -0,0 +1,57 @@
+//! Mod packages, as distributed on mod.io, are zip archives which may contain several Custom
+//! Difficulty files.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::Context;
+use tracing::*;
+
+use crate::driver::Input;
+
+/// Whether the path is a zip archive rather than a Custom Difficulty file, judging by its
+/// extension.
+pub fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+/// The JSON files inside the archive, in the order they are stored in. Each one's path is shown as
+/// `archive.zip!/path/inside.json` in diagnostics.
+pub fn read_json_entries(archive_path: &Path) -> anyhow::Result<Vec<Input>> {
+    debug!(?archive_path);
+    let file = File::open(archive_path)
+        .with_context(|| format!("failed to read file `{}`", archive_path.display()))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .with_context(|| format!("`{}` is not a valid zip archive", archive_path.display()))?;
+
+    let mut inputs = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .with_context(|| format!("failed to read entry {i} of `{}`", archive_path.display()))?;
+        let is_json = Path::new(entry.name())
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        if !entry.is_file() || !is_json {
+            trace!(entry = entry.name(), "skipping entry");
+            continue;
+        }
+
+        let path = format!("{}!/{}", archive_path.display(), entry.name());
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("failed to read `{path}`"))?;
+        inputs.push(Input::from_bytes(path, &bytes));
+    }
+
+    if inputs.is_empty() {
+        anyhow::bail!(
+            "`{}` does not contain any JSON files",
+            archive_path.display()
+        );
+    }
+    Ok(inputs)
+}
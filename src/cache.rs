@@ -0,0 +1,352 @@
+//! Caching what linting each file reported, so that files which didn't change since they were last
+//! linted with the same config aren't linted again, e.g. when linting an archive after changing one
+//! file in it. Results are kept in [`CACHE_DIR`] next to the executable, one file per result, named
+//! after a hash of the file's content and of everything else which affects what linting reports.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tracing::*;
+
+use crate::config::Config;
+use crate::diagnostic::{Color, Label, Note, Report, ReportKind, Suggestion};
+use crate::driver::Input;
+use crate::game_data::GameData;
+use crate::lint::{self, LintSelection};
+use crate::{plugins, Diagnostics};
+
+/// The directory next to the executable which lint results are cached in.
+pub const CACHE_DIR: &str = "cache";
+
+/// How many results are kept. The least recently written ones are removed beyond that.
+const MAX_ENTRIES: usize = 256;
+
+/// What linting a file reported, with suppressed diagnostics already left out.
+pub struct LintResult<'d> {
+    pub diagnostics: Diagnostics<'d>,
+    /// The descriptions of the hazard levels estimated for the Custom Difficulty.
+    pub estimates: Vec<String>,
+}
+
+/// The lint results cached for one config and lint selection, see [`Cache::open`].
+pub struct Cache {
+    dir: PathBuf,
+    /// A hash of everything besides the file itself which affects what linting reports.
+    fingerprint: u64,
+    /// Whether a result was written, in which case old results are pruned when the cache is
+    /// dropped.
+    written: AtomicBool,
+}
+
+impl Cache {
+    /// The cache of results of linting with the config and the lint selection, unless results
+    /// can't be cached because linting does more than report diagnostics: plugins can depend on
+    /// anything, and the cyclic reference graph is written while linting.
+    pub fn open(dir: PathBuf, config: &Config, selection: &LintSelection) -> Option<Self> {
+        if plugins::names().next().is_some() {
+            debug!("not caching lint results because plugins are loaded");
+            return None;
+        }
+        if config.generate_cyclic_reference_graph {
+            debug!("not caching lint results because the cyclic reference graph is generated");
+            return None;
+        }
+
+        let color = concolor::get(concolor::Stream::Stdout).color();
+        let fingerprint = StableHasher::new()
+            .write(env!("CARGO_PKG_VERSION").as_bytes())
+            .write(format!("{config:?}").as_bytes())
+            .write(format!("{selection:?}").as_bytes())
+            .write(format!("{:?}", GameData::for_version(&config.game_version)).as_bytes())
+            // Messages are colored when diagnostics are built rather than when they are printed.
+            .write(&[color as u8])
+            .finish();
+        Some(Self {
+            dir,
+            fingerprint,
+            written: AtomicBool::new(false),
+        })
+    }
+
+    /// What linting the file reported the last time it was linted with the same config, if it
+    /// was.
+    pub fn get<'d>(&self, input: &'d Input) -> Option<LintResult<'d>> {
+        let content = content_hash(input);
+        let path = self.entry_path(content);
+        let entry = std::fs::read(&path).ok()?;
+        let entry = match serde_json::from_slice::<Entry>(&entry) {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!(?path, "ignoring unreadable cached lint results: {e}");
+                return None;
+            }
+        };
+        if entry.fingerprint != self.fingerprint || entry.content != content {
+            return None;
+        }
+
+        let lints = lint::lint_names();
+        let diagnostics = entry
+            .diagnostics
+            .into_iter()
+            .map(|report| report.into_report(&input.path, &lints))
+            .collect::<Option<Vec<_>>>()?;
+        debug!(path = input.path, "using cached lint results");
+        Some(LintResult {
+            diagnostics,
+            estimates: entry.estimates,
+        })
+    }
+
+    /// Caches what linting the file reported. Failing to do so only means that the file is linted
+    /// again next time.
+    pub fn insert(&self, input: &Input, result: &LintResult<'_>) {
+        let Some(diagnostics) = result
+            .diagnostics
+            .iter()
+            .map(CachedReport::new)
+            .collect::<Option<Vec<_>>>()
+        else {
+            debug!(path = input.path, "lint results can't be cached");
+            return;
+        };
+        let content = content_hash(input);
+        let entry = Entry {
+            fingerprint: self.fingerprint,
+            content,
+            diagnostics,
+            estimates: result.estimates.clone(),
+        };
+        if let Err(e) = self.write(&self.entry_path(content), &entry) {
+            warn!(
+                "failed to cache lint results in `{}`, pass `--no-cache` to not cache them: {:#}",
+                self.dir.display(),
+                e
+            );
+        }
+    }
+
+    fn entry_path(&self, content: u64) -> PathBuf {
+        let name = StableHasher::new()
+            .write(&self.fingerprint.to_le_bytes())
+            .write(&content.to_le_bytes())
+            .finish();
+        self.dir.join(format!("{name:016x}.json"))
+    }
+
+    fn write(&self, path: &Path, entry: &Entry) -> anyhow::Result<()> {
+        static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+        std::fs::create_dir_all(&self.dir)?;
+        // The entry is written to a temporary file first, so that another run reading it at the
+        // same time never sees half of it.
+        let temp_path = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&temp_path, serde_json::to_vec(entry)?)?;
+        std::fs::rename(&temp_path, path)?;
+        self.written.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Removes the least recently written results beyond [`MAX_ENTRIES`].
+    fn prune(&self) -> std::io::Result<()> {
+        let mut entries = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let path = entry.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some((entry.metadata().ok()?.modified().ok()?, path))
+            })
+            .collect::<Vec<_>>();
+        if entries.len() <= MAX_ENTRIES {
+            return Ok(());
+        }
+        entries.sort();
+        for (_, path) in &entries[..entries.len() - MAX_ENTRIES] {
+            // Another run may have removed it already.
+            if let Err(e) = std::fs::remove_file(path) {
+                debug!(?path, "failed to remove cached lint results: {e}");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Cache {
+    fn drop(&mut self) {
+        if !self.written.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Err(e) = self.prune() {
+            warn!(
+                "failed to remove old lint results from `{}`: {}",
+                self.dir.display(),
+                e
+            );
+        }
+    }
+}
+
+/// A hash of the file as it is linted.
+fn content_hash(input: &Input) -> u64 {
+    StableHasher::new()
+        .write(input.src.as_bytes())
+        .write(format!("{:?}", input.encoding_issue).as_bytes())
+        .finish()
+}
+
+/// 64-bit FNV-1a. Unlike [`std::collections::hash_map::DefaultHasher`], it hashes the same bytes
+/// to the same value in every build, so that results cached by one build of CDLint are found by
+/// another build of the same version rather than piling up unused.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Hashes the bytes after their length, so that different fields can't run into each other.
+    fn write(&mut self, bytes: &[u8]) -> &mut Self {
+        for byte in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        self
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The cached results of linting a file.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// The [`Cache::fingerprint`] and the [`content_hash`], which the entry's file name is a hash
+    /// of, in case two entries end up with the same file name.
+    fingerprint: u64,
+    content: u64,
+    diagnostics: Vec<CachedReport>,
+    estimates: Vec<String>,
+}
+
+/// A [`Report`] without the path of the file it is about.
+#[derive(Serialize, Deserialize)]
+struct CachedReport {
+    kind: CachedKind,
+    offset: usize,
+    message: Option<String>,
+    labels: Vec<CachedLabel>,
+    help: Option<String>,
+    notes: Vec<Note>,
+    suggestions: Vec<Suggestion>,
+    lint: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum CachedKind {
+    Error,
+    Warning,
+    Advice,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedLabel {
+    span: Range<usize>,
+    color: Option<CachedColor>,
+    message: Option<String>,
+}
+
+/// The colors labels are drawn in.
+#[derive(Serialize, Deserialize)]
+enum CachedColor {
+    Red,
+    Yellow,
+    Green,
+    Cyan,
+    Blue,
+}
+
+impl CachedReport {
+    /// The report as it is cached, unless it uses a report kind or label color which isn't.
+    fn new(report: &Report<'_>) -> Option<Self> {
+        let kind = match report.kind {
+            ReportKind::Error => CachedKind::Error,
+            ReportKind::Warning => CachedKind::Warning,
+            ReportKind::Advice => CachedKind::Advice,
+            ReportKind::Custom(..) => return None,
+        };
+        let labels = report
+            .labels
+            .iter()
+            .map(|label| {
+                let color = match label.color {
+                    None => None,
+                    Some(Color::Red) => Some(CachedColor::Red),
+                    Some(Color::Yellow) => Some(CachedColor::Yellow),
+                    Some(Color::Green) => Some(CachedColor::Green),
+                    Some(Color::Cyan) => Some(CachedColor::Cyan),
+                    Some(Color::Blue) => Some(CachedColor::Blue),
+                    Some(_) => return None,
+                };
+                Some(CachedLabel {
+                    span: label.span.1.clone(),
+                    color,
+                    message: label.message.clone(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            kind,
+            offset: report.offset,
+            message: report.message.clone(),
+            labels,
+            help: report.help.clone(),
+            notes: report.notes.clone(),
+            suggestions: report.suggestions.clone(),
+            lint: report.lint.map(str::to_string),
+        })
+    }
+
+    /// The report about the file at `path`, unless it was reported by a lint which no longer
+    /// exists.
+    fn into_report<'d>(self, path: &'d String, lints: &[&'static str]) -> Option<Report<'d>> {
+        let kind = match self.kind {
+            CachedKind::Error => ReportKind::Error,
+            CachedKind::Warning => ReportKind::Warning,
+            CachedKind::Advice => ReportKind::Advice,
+        };
+        let lint = match self.lint {
+            Some(name) => Some(*lints.iter().find(|lint| **lint == name)?),
+            None => None,
+        };
+        let labels = self.labels.into_iter().map(|label| Label {
+            span: (path, label.span),
+            color: label.color.map(|color| match color {
+                CachedColor::Red => Color::Red,
+                CachedColor::Yellow => Color::Yellow,
+                CachedColor::Green => Color::Green,
+                CachedColor::Cyan => Color::Cyan,
+                CachedColor::Blue => Color::Blue,
+            }),
+            message: label.message,
+        });
+
+        let mut report = Report::build(kind, path, self.offset)
+            .with_labels(labels)
+            .finish();
+        report.message = self.message;
+        report.help = self.help;
+        report.notes = self.notes;
+        report.suggestions = self.suggestions;
+        report.lint = lint;
+        Some(report)
+    }
+}
@@ -0,0 +1,104 @@
+//! `cdlint data`, which manages the vanilla game data lints check against.
+
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::config::Config;
+use crate::game_data::{self, GameData};
+
+#[derive(Debug, clap::Args)]
+pub struct DataArgs {
+    #[command(subcommand)]
+    command: DataCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum DataCommand {
+    /// List the game versions which can be selected with `game_version` or `--game-version`.
+    List,
+    /// Download the latest curated game data pack from CDLint's releases, so that a new game
+    /// version can be linted against before the next CDLint release.
+    Update(UpdateArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct UpdateArgs {
+    /// Where to download the game data pack from.
+    #[arg(long, default_value = LATEST_PACK_URL)]
+    url: String,
+}
+
+/// The game data pack attached to the latest release.
+const LATEST_PACK_URL: &str = concat!(
+    env!("CARGO_PKG_REPOSITORY"),
+    "/releases/latest/download/game-data.json"
+);
+
+/// Game data packs are small, anything much larger is not one.
+const MAX_PACK_SIZE: u64 = 1024 * 1024;
+
+pub fn run(config: &Config, game_data_dir: &Path, args: DataArgs) -> anyhow::Result<()> {
+    match args.command {
+        DataCommand::List => {
+            list(config);
+            Ok(())
+        }
+        DataCommand::Update(args) => update(config, game_data_dir, &args.url),
+    }
+}
+
+fn list(config: &Config) {
+    let selected = config.game_data();
+    for game_data in game_data::available() {
+        let source = if game_data.is_downloaded() {
+            "downloaded"
+        } else {
+            "built in"
+        };
+        let marker = if std::ptr::eq(game_data, selected) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{marker} {} ({source})", game_data.version);
+    }
+}
+
+fn update(config: &Config, game_data_dir: &Path, url: &str) -> anyhow::Result<()> {
+    println!("downloading `{url}`");
+    let response = ureq::get(url)
+        .call()
+        .with_context(|| format!("failed to download `{url}`"))?;
+    let mut json = String::new();
+    response
+        .into_reader()
+        .take(MAX_PACK_SIZE)
+        .read_to_string(&mut json)
+        .with_context(|| format!("failed to download `{url}`"))?;
+
+    // Only keep data packs which can be loaded, so a bad download doesn't replace a good one.
+    let game_data = GameData::from_json(&json)?;
+    std::fs::create_dir_all(game_data_dir)
+        .with_context(|| format!("failed to create `{}`", game_data_dir.display()))?;
+    let path = game_data_dir.join(format!("{}.json", game_data.version));
+    std::fs::write(&path, &json)
+        .with_context(|| format!("failed to write `{}`", path.display()))?;
+    println!(
+        "saved game data for `{}` to `{}`",
+        game_data.version,
+        path.display()
+    );
+
+    if config.game_version != game_data::LATEST && config.game_version != game_data.version {
+        println!(
+            "note: `game_version` in the config is `{}`, set it to `{}` or `{}` to lint against \
+             the downloaded game data",
+            config.game_version,
+            game_data.version,
+            game_data::LATEST
+        );
+    }
+    Ok(())
+}
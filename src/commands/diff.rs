@@ -0,0 +1,171 @@
+//! `cdlint diff`, which compares two Custom Difficulties after resolving them.
+
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::commands::resolve::resolve_file;
+use crate::config::Config;
+use crate::diagnostic::{Color, Fmt};
+
+#[derive(Debug, clap::Args)]
+pub struct DiffArgs {
+    /// The path to the old Custom Difficulty JSON file.
+    old: PathBuf,
+
+    /// The path to the new Custom Difficulty JSON file.
+    new: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A difference between the two Custom Difficulties.
+#[derive(Debug)]
+struct Change {
+    kind: ChangeKind,
+    /// Where the value is, e.g. `EnemyDescriptors.ED_Spider_Grunt.Scale` or
+    /// `MaxActiveEnemies[3]`.
+    path: String,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+pub fn run(config: &Config, args: DiffArgs) -> anyhow::Result<()> {
+    let (old, _) = resolve_file(config, &args.old)?;
+    let (new, _) = resolve_file(config, &args.new)?;
+
+    let mut changes = Vec::new();
+    diff_values(String::new(), Some(&old), Some(&new), &mut changes);
+
+    if changes.is_empty() {
+        println!("no differences");
+        return Ok(());
+    }
+    print_table(&changes);
+    Ok(())
+}
+
+/// Compares the values at `path`, which are absent if the member is only in one of the Custom
+/// Difficulties.
+///
+/// Arrays of strings (pool entries and seasonal events) are compared as sets, other arrays element
+/// by element.
+fn diff_values(path: String, old: Option<&Value>, new: Option<&Value>, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let keys = old
+                .keys()
+                .chain(new.keys().filter(|key| !old.contains_key(*key)));
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(path, old.get(key), new.get(key), changes);
+            }
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new)))
+            if all_strings(old) && all_strings(new) =>
+        {
+            for removed in old.iter().filter(|val| !new.contains(val)) {
+                changes.push(Change {
+                    kind: ChangeKind::Removed,
+                    path: path.clone(),
+                    old: Some(removed.to_string()),
+                    new: None,
+                });
+            }
+            for added in new.iter().filter(|val| !old.contains(val)) {
+                changes.push(Change {
+                    kind: ChangeKind::Added,
+                    path: path.clone(),
+                    old: None,
+                    new: Some(added.to_string()),
+                });
+            }
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new))) => {
+            for i in 0..old.len().max(new.len()) {
+                diff_values(format!("{path}[{i}]"), old.get(i), new.get(i), changes);
+            }
+        }
+        (Some(old), Some(new)) if old != new => changes.push(Change {
+            kind: ChangeKind::Changed,
+            path,
+            old: Some(old.to_string()),
+            new: Some(new.to_string()),
+        }),
+        (Some(old), None) => changes.push(Change {
+            kind: ChangeKind::Removed,
+            path,
+            old: Some(old.to_string()),
+            new: None,
+        }),
+        (None, Some(new)) => changes.push(Change {
+            kind: ChangeKind::Added,
+            path,
+            old: None,
+            new: Some(new.to_string()),
+        }),
+        _ => {}
+    }
+}
+
+fn all_strings(vals: &[Value]) -> bool {
+    vals.iter().all(Value::is_string)
+}
+
+/// Values longer than this are cut short in the table, e.g. whole Enemy Descriptors.
+const MAX_VALUE_WIDTH: usize = 40;
+
+/// Prints one change per row, with the member, old and new value columns aligned.
+fn print_table(changes: &[Change]) {
+    let shorten = |val: &Option<String>| {
+        let val = val.as_deref().unwrap_or_default();
+        if val.chars().count() > MAX_VALUE_WIDTH {
+            let short = val.chars().take(MAX_VALUE_WIDTH - 1).collect::<String>();
+            format!("{short}…")
+        } else {
+            val.to_string()
+        }
+    };
+
+    const HEADER: [&str; 3] = ["Member", "Old", "New"];
+    let path_width = changes
+        .iter()
+        .map(|change| change.path.len())
+        .chain([HEADER[0].len()])
+        .max()
+        .unwrap_or_default();
+    let old_width = changes
+        .iter()
+        .map(|change| shorten(&change.old).chars().count())
+        .chain([HEADER[1].len()])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "  {:path_width$}  {:old_width$}  {}",
+        HEADER[0], HEADER[1], HEADER[2]
+    );
+    for change in changes {
+        let marker = match change.kind {
+            ChangeKind::Added => "+".fg(Color::Green),
+            ChangeKind::Removed => "-".fg(Color::Red),
+            ChangeKind::Changed => "~".fg(Color::Yellow),
+        };
+        let row = format!(
+            "{marker} {:path_width$}  {:old_width$}  {}",
+            change.path,
+            shorten(&change.old),
+            shorten(&change.new),
+        );
+        println!("{}", row.trim_end());
+    }
+}
@@ -0,0 +1,167 @@
+//! `cdlint init`, which writes a starter Custom Difficulty.
+
+use std::fmt::Write as _;
+use std::io::{BufRead, IsTerminal, Write as _};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context};
+
+use crate::hazards::{vanilla_hazard, Hazard};
+
+#[derive(Debug, clap::Args)]
+pub struct InitArgs {
+    /// Where to write the Custom Difficulty.
+    #[arg(default_value = "custom_difficulty.json")]
+    output: PathBuf,
+
+    /// The name shown in the game's difficulty selection.
+    #[arg(long)]
+    name: Option<String>,
+
+    /// The vanilla hazard level the values are taken from.
+    #[arg(long, value_name = "1-5", value_parser = clap::value_parser!(u8).range(1..=5))]
+    hazard: Option<u8>,
+
+    /// Include an example custom Enemy Descriptor.
+    #[arg(long)]
+    enemy_descriptors: bool,
+
+    /// Don't ask for anything which is not given, using the defaults instead. This is implied
+    /// when stdin is not a terminal.
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Overwrite the output file if it exists.
+    #[arg(long)]
+    force: bool,
+}
+
+const DEFAULT_NAME: &str = "My Custom Difficulty";
+const DEFAULT_HAZARD: usize = 5;
+
+pub fn run(args: InitArgs) -> anyhow::Result<()> {
+    if args.output.exists() && !args.force {
+        bail!(
+            "`{}` already exists, pass `--force` to overwrite it",
+            args.output.display()
+        );
+    }
+
+    let interactive = !args.yes && std::io::stdin().is_terminal();
+    let name = match args.name {
+        Some(name) => name,
+        None if interactive => prompt(&format!("Name [{DEFAULT_NAME}]"))?
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| DEFAULT_NAME.to_string()),
+        None => DEFAULT_NAME.to_string(),
+    };
+    let hazard = match args.hazard {
+        Some(level) => usize::from(level),
+        None if interactive => loop {
+            let answer = prompt(&format!("Base hazard, 1 to 5 [{DEFAULT_HAZARD}]"))?;
+            match answer.as_deref() {
+                None | Some("") => break DEFAULT_HAZARD,
+                Some(answer) => match answer.parse() {
+                    Ok(level @ 1..=5) => break level,
+                    _ => eprintln!("expected a hazard level from 1 to 5"),
+                },
+            }
+        },
+        None => DEFAULT_HAZARD,
+    };
+    let enemy_descriptors = args.enemy_descriptors
+        || (interactive
+            && prompt("Include an example Enemy Descriptor? [y/N]")?
+                .is_some_and(|answer| answer.eq_ignore_ascii_case("y")));
+
+    let hazard = vanilla_hazard(hazard).expect("hazard level is checked to be from 1 to 5");
+    let template = template(&name, hazard, enemy_descriptors);
+    std::fs::write(&args.output, template)
+        .with_context(|| format!("failed to write `{}`", args.output.display()))?;
+    println!("wrote `{}`", args.output.display());
+    Ok(())
+}
+
+/// Asks on stdout and reads the trimmed answer from stdin, which is `None` at end of input.
+fn prompt(question: &str) -> anyhow::Result<Option<String>> {
+    print!("{question}: ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer)? == 0 {
+        println!();
+        return Ok(None);
+    }
+    Ok(Some(answer.trim().to_string()))
+}
+
+/// The Custom Difficulty with the values of the vanilla hazard. Optional members are commented out
+/// and placed before the last member, so they can be uncommented without fixing up commas.
+fn template(name: &str, hazard: &Hazard, enemy_descriptors: bool) -> String {
+    let array = |vals: &[f64]| {
+        let vals = vals.iter().map(f64::to_string).collect::<Vec<_>>();
+        format!("[{}]", vals.join(", "))
+    };
+    let name = serde_json::to_string(name).expect("strings can be serialized");
+
+    let mut out = String::new();
+    // Writing to a `String` can't fail.
+    let mut line = |line: &str| {
+        let _ = writeln!(out, "{line}");
+    };
+    line("{");
+    line(&format!(
+        "  // Created by `cdlint init` from the values of Hazard {}. Comments are not allowed in",
+        hazard.level
+    ));
+    line("  // JSON: uncomment the members you want and remove the remaining comments before");
+    line("  // loading this Custom Difficulty in the game.");
+    line(&format!("  \"Name\": {name},"));
+    line(&format!(
+        "  \"Description\": \"Based on Hazard {}.\",",
+        hazard.level
+    ));
+    line(&format!("  \"HazardBonus\": {},", hazard.hazard_bonus));
+    line("  // Arrays give the values for 1, 2, 3 and 4 players.");
+    line(&format!(
+        "  \"EnemyCountModifier\": {},",
+        array(&hazard.enemy_count_modifier)
+    ));
+    line(&format!(
+        "  \"EnemyDamageModifier\": {},",
+        array(&hazard.enemy_damage_modifier)
+    ));
+    line(&format!(
+        "  \"EnemyDamageResistance\": {},",
+        array(&hazard.enemy_damage_resistance)
+    ));
+    line(&format!(
+        "  // \"SmallEnemyDamageResistance\": {},",
+        array(&hazard.enemy_damage_resistance)
+    ));
+    line("  // \"StartingNitra\": 0,");
+    line("  // \"MaxActiveSwarmers\": 60,");
+    line("  // \"MaxActiveCritters\": 40,");
+    if enemy_descriptors {
+        line("  // Custom Enemy Descriptors copy the fields of their \"Base\" and only spawn once they");
+        line("  // are added to a pool.");
+        line("  \"EnemyDescriptors\": {");
+        line("    \"ED_Spider_Grunt_Big\": {");
+        line("      \"Base\": \"ED_Spider_Grunt\",");
+        line("      // \"Elite\": false,");
+        line("      // \"PawnStats\": { \"PST_MovementSpeed\": 1.2 },");
+        line("      \"Scale\": 1.5");
+        line("    }");
+        line("  },");
+        line("  \"CommonEnemies\": { \"add\": [\"ED_Spider_Grunt_Big\"] },");
+    } else {
+        line("  // \"CommonEnemies\": { \"clear\": false, \"add\": [], \"remove\": [] },");
+    }
+    line("  // \"SpecialEnemies\": { \"clear\": false, \"add\": [], \"remove\": [] },");
+    line(&format!(
+        "  \"MaxActiveEnemies\": {},",
+        hazard.max_active_enemies
+    ));
+    line(&format!("  \"ResupplyCost\": {}", hazard.resupply_cost));
+    line("}");
+    out
+}
@@ -0,0 +1,166 @@
+//! `cdlint merge`, which layers Custom Difficulties over a base Custom Difficulty.
+//!
+//! Each overlay is merged into the result of the previous ones, so later files take precedence:
+//!
+//! - Members are replaced by the overlay's, except for `EnemyDescriptors` and the enemy pools.
+//! - Enemy Descriptors are merged field by field, and their `PawnStats` pawn stat by pawn stat.
+//! - Pools are merged so that the merged pool ends up with the Enemy Descriptors it would have if
+//!   the base's `clear`, `add` and `remove` were applied first and the overlay's after them.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use indexmap::IndexSet;
+use serde_json::{Map, Value};
+
+use crate::config::Config;
+use crate::diagnostic::ReportKind;
+use crate::driver::{self, Input};
+use crate::lint::LintSelection;
+use crate::summary::Summary;
+
+#[derive(Debug, clap::Args)]
+pub struct MergeArgs {
+    /// The path to the base Custom Difficulty JSON file.
+    base: PathBuf,
+
+    /// The paths to the Custom Difficulty JSON files layered over the base, in order. Later files
+    /// take precedence.
+    #[arg(required = true)]
+    overlays: Vec<PathBuf>,
+
+    /// Where to write the merged Custom Difficulty.
+    #[arg(short, long, value_name = "PATH")]
+    output: PathBuf,
+}
+
+const POOLS: [&str; 5] = [
+    "EnemyPool",
+    "CommonEnemies",
+    "DisruptiveEnemies",
+    "SpecialEnemies",
+    "StationaryEnemies",
+];
+
+pub fn run(config: &Config, args: MergeArgs) -> anyhow::Result<()> {
+    let mut merged = read_members(config, &args.base)?;
+    for overlay in &args.overlays {
+        merge(&mut merged, read_members(config, overlay)?);
+    }
+    let merged = serde_json::to_string_pretty(&Value::Object(merged))? + "\n";
+
+    // Lint the merged Custom Difficulty as if it was already written.
+    let input = Input::new(args.output.display().to_string(), merged);
+    let selection = LintSelection::default();
+    let mut loaded = driver::load(config, &selection, &input, false)?;
+    driver::lint(config, &selection, &input, &mut loaded)?;
+    input.print(&loaded.diagnostics)?;
+    let summary = Summary::new(&loaded.diagnostics);
+    summary.print(false);
+
+    std::fs::write(&args.output, &input.src)
+        .with_context(|| format!("failed to write `{}`", args.output.display()))?;
+
+    if loaded
+        .diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.kind == ReportKind::Error)
+    {
+        bail!("the merged Custom Difficulty has errors");
+    }
+    Ok(())
+}
+
+/// The top-level members of the Custom Difficulty as written, which must not have errors.
+fn read_members(config: &Config, path: &Path) -> anyhow::Result<Map<String, Value>> {
+    let input = Input::read(path)?;
+    let loaded = driver::load_without_errors(config, &input)?;
+    let mut members = Map::new();
+    for (name, val) in &loaded.top_level_members {
+        members
+            .entry(name.val.clone())
+            .or_insert_with(|| val.val.to_value());
+    }
+    Ok(members)
+}
+
+fn merge(base: &mut Map<String, Value>, overlay: Map<String, Value>) {
+    for (name, val) in overlay {
+        match base.get_mut(&name) {
+            Some(base_val) if name == "EnemyDescriptors" => merge_enemy_descriptors(base_val, val),
+            Some(base_val) if POOLS.contains(&name.as_str()) => merge_pool(base_val, val),
+            _ => {
+                base.insert(name, val);
+            }
+        }
+    }
+}
+
+fn merge_enemy_descriptors(base: &mut Value, overlay: Value) {
+    let (Value::Object(base), Value::Object(overlay)) = (base, overlay) else {
+        return;
+    };
+    for (ed_name, ed) in overlay {
+        let Some(Value::Object(base_ed)) = base.get_mut(&ed_name) else {
+            base.insert(ed_name, ed);
+            continue;
+        };
+        let Value::Object(ed) = ed else {
+            continue;
+        };
+        for (field, val) in ed {
+            match (base_ed.get_mut(&field), val) {
+                (Some(Value::Object(base_stats)), Value::Object(stats)) if field == "PawnStats" => {
+                    base_stats.extend(stats);
+                }
+                (_, val) => {
+                    base_ed.insert(field, val);
+                }
+            }
+        }
+    }
+}
+
+/// With the base's Enemy Descriptors added `A` and removed `R`, followed by the overlay's `A'`
+/// and `R'`, the merged pool adds `(A - R - R') + A'` and removes `(R - A') + R'`. If the
+/// overlay clears the pool, the base's changes don't matter.
+fn merge_pool(base: &mut Value, overlay: Value) {
+    let (Value::Object(base), Value::Object(overlay)) = (base, overlay) else {
+        return;
+    };
+    if overlay.get("clear") == Some(&Value::Bool(true)) {
+        *base = overlay;
+        return;
+    }
+
+    let names = |pool: &Map<String, Value>, key: &str| {
+        pool.get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect::<IndexSet<_>>()
+    };
+    let (add, remove) = (names(base, "add"), names(base, "remove"));
+    let (overlay_add, overlay_remove) = (names(&overlay, "add"), names(&overlay, "remove"));
+
+    let merged_add = add
+        .iter()
+        .filter(|name| !remove.contains(*name) && !overlay_remove.contains(*name))
+        .chain(&overlay_add)
+        .cloned()
+        .collect::<IndexSet<_>>();
+    let merged_remove = remove
+        .iter()
+        .filter(|name| !overlay_add.contains(*name))
+        .chain(&overlay_remove)
+        .cloned()
+        .collect::<IndexSet<_>>();
+
+    for (key, names) in [("add", merged_add), ("remove", merged_remove)] {
+        if !names.is_empty() || base.contains_key(key) {
+            base.insert(key.to_string(), names.into_iter().collect());
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Subcommands other than linting.
+
+pub mod data;
+pub mod diff;
+pub mod init;
+pub mod merge;
+pub mod pools;
+pub mod resolve;
+pub mod stats;
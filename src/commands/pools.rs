@@ -0,0 +1,285 @@
+//! `cdlint pools`, which simulates how the game builds the enemy pool at the start of a mission.
+//!
+//! The simulation follows the order the game fills the enemy pool in:
+//!
+//! 1. The Enemy Descriptors added to `EnemyPool` are in the pool from the start.
+//! 2. Common enemies are added in random order until the pool has `MinPoolSize` Enemy Descriptors.
+//! 3. As many disruptive enemies as rolled from `DisruptiveEnemyPoolCount` are added.
+//! 4. Special enemies are added in random order until the pool has `MinPoolSize` Enemy
+//!    Descriptors.
+//! 5. The Enemy Descriptors removed from `EnemyPool` are removed.
+//!
+//! Pools which are not cleared also contain their vanilla Enemy Descriptors, which depend on the
+//! biome, so only the Enemy Descriptors the Custom Difficulty adds to them are simulated.
+
+use std::path::PathBuf;
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::diagnostic::{Color, Fmt};
+use crate::driver::{self, Input};
+use crate::spanned::Spanned;
+use crate::symbol::Symbol;
+
+#[derive(Debug, clap::Args)]
+pub struct PoolsArgs {
+    /// The path to a Custom Difficulty JSON file.
+    input: PathBuf,
+
+    /// How many missions to simulate.
+    #[arg(long, value_name = "N", default_value_t = 10_000)]
+    runs: usize,
+
+    /// The seed of the random rolls, so that simulations can be repeated.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+/// Used when the Custom Difficulty does not specify `MinPoolSize`. The game's value is not known.
+const ASSUMED_MIN_POOL_SIZE: usize = 5;
+/// Used when the Custom Difficulty does not specify `DisruptiveEnemyPoolCount`. The game's value is
+/// not known.
+const ASSUMED_DISRUPTIVE_ENEMY_POOL_COUNT: (usize, usize) = (1, 2);
+
+/// Enemy Descriptors which make it into fewer of the simulated pools than this are flagged.
+const RARE_THRESHOLD: f64 = 0.05;
+/// How many of the most frequent pools are shown.
+const REPRESENTATIVE_POOLS: usize = 3;
+
+pub fn run(config: &Config, args: PoolsArgs) -> anyhow::Result<()> {
+    let input = Input::read(&args.input)?;
+    let loaded = driver::load_without_errors(config, &input)?;
+    let cd = &loaded.cd;
+    let pools = Pools::new(cd);
+
+    let (min_pool_size, min_pool_size_assumed) = if cd.min_pool_size.is_specified() {
+        (cd.min_pool_size.val, false)
+    } else {
+        (ASSUMED_MIN_POOL_SIZE, true)
+    };
+    let (disruptive_count, disruptive_count_assumed) =
+        if cd.disruptive_enemy_pool_count.is_specified() {
+            let range = &cd.disruptive_enemy_pool_count;
+            ((range.min.val, range.max.val.max(range.min.val)), false)
+        } else {
+            (ASSUMED_DISRUPTIVE_ENEMY_POOL_COUNT, true)
+        };
+
+    let runs = args.runs.max(1);
+    let mut rng = SplitMix64(args.seed);
+    let mut frequencies = IndexMap::<Vec<&str>, usize>::new();
+    let mut appearances = IndexMap::<&str, usize>::new();
+    for name in pools.all() {
+        appearances.insert(name, 0);
+    }
+    for _ in 0..runs {
+        let disruptive = rng.range(disruptive_count.0, disruptive_count.1);
+        let pool = pools.roll(&mut rng, min_pool_size, disruptive);
+        for name in &pool {
+            *appearances.entry(name).or_default() += 1;
+        }
+        let mut pool = pool.into_iter().collect::<Vec<_>>();
+        pool.sort_unstable();
+        *frequencies.entry(pool).or_default() += 1;
+    }
+
+    let assumed = |assumed: bool| if assumed { " (assumed)" } else { "" };
+    println!(
+        "Simulated {runs} missions with MinPoolSize {min_pool_size}{} and \
+         DisruptiveEnemyPoolCount {}-{}{}",
+        assumed(min_pool_size_assumed),
+        disruptive_count.0,
+        disruptive_count.1,
+        assumed(disruptive_count_assumed),
+    );
+    for (name, pool) in pools.uncleared(cd) {
+        let simulated = match pool.len() {
+            0 => "none of its Enemy Descriptors are simulated".to_string(),
+            added => format!("only the {added} it adds are simulated"),
+        };
+        println!(
+            "note: {name} is not cleared, so its vanilla Enemy Descriptors are unknown and \
+             {simulated}"
+        );
+    }
+
+    println!();
+    println!("Representative pools");
+    let mut frequencies = frequencies.into_iter().collect::<Vec<_>>();
+    frequencies.sort_by(|(_, a), (_, b)| b.cmp(a));
+    for (pool, count) in frequencies.iter().take(REPRESENTATIVE_POOLS) {
+        let pool = if pool.is_empty() {
+            "(empty)".to_string()
+        } else {
+            pool.join(", ")
+        };
+        println!("  {:>5.1}%  {pool}", percent(*count, runs));
+    }
+
+    println!();
+    println!("Chance to be in the pool");
+    let width = appearances
+        .keys()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or_default();
+    for (name, count) in &appearances {
+        let chance = *count as f64 / runs as f64;
+        let row = format!(
+            "  {name:width$}  {:>5.1}%  {}",
+            percent(*count, runs),
+            pools.source(name)
+        );
+        if chance < RARE_THRESHOLD {
+            println!("{row}  {}", "almost never in the pool".fg(Color::Yellow));
+        } else {
+            println!("{row}");
+        }
+    }
+
+    Ok(())
+}
+
+fn percent(count: usize, runs: usize) -> f64 {
+    count as f64 * 100.0 / runs as f64
+}
+
+/// The Enemy Descriptors each pool contributes, as far as the Custom Difficulty specifies them.
+struct Pools<'a> {
+    enemy_pool_add: Vec<&'a str>,
+    enemy_pool_remove: Vec<&'a str>,
+    common: Vec<&'a str>,
+    disruptive: Vec<&'a str>,
+    special: Vec<&'a str>,
+}
+
+impl<'a> Pools<'a> {
+    fn new(cd: &'a CustomDifficulty) -> Self {
+        Self {
+            enemy_pool_add: names(&cd.enemy_pool.add),
+            enemy_pool_remove: names(&cd.enemy_pool.remove),
+            common: known_entries(&cd.common_enemies),
+            disruptive: known_entries(&cd.disruptive_enemies),
+            special: known_entries(&cd.special_enemies),
+        }
+    }
+
+    fn all(&self) -> IndexSet<&'a str> {
+        self.enemy_pool_add
+            .iter()
+            .chain(&self.common)
+            .chain(&self.disruptive)
+            .chain(&self.special)
+            .copied()
+            .collect()
+    }
+
+    /// The pools which still contain their vanilla Enemy Descriptors, with the Enemy Descriptors
+    /// which are simulated for them.
+    fn uncleared<'p>(
+        &'p self,
+        cd: &CustomDifficulty,
+    ) -> impl Iterator<Item = (&'static str, &'p [&'a str])> + 'p {
+        [
+            ("CommonEnemies", cd.common_enemies.clear.val, &self.common),
+            (
+                "DisruptiveEnemies",
+                cd.disruptive_enemies.clear.val,
+                &self.disruptive,
+            ),
+            (
+                "SpecialEnemies",
+                cd.special_enemies.clear.val,
+                &self.special,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, clear, _)| !clear)
+        .map(|(name, _, pool)| (name, pool.as_slice()))
+    }
+
+    /// Which pools the Enemy Descriptor comes from.
+    fn source(&self, name: &str) -> String {
+        [
+            ("EnemyPool", &self.enemy_pool_add),
+            ("CommonEnemies", &self.common),
+            ("DisruptiveEnemies", &self.disruptive),
+            ("SpecialEnemies", &self.special),
+        ]
+        .into_iter()
+        .filter(|(_, pool)| pool.contains(&name))
+        .map(|(pool, _)| pool)
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    fn roll(
+        &self,
+        rng: &mut SplitMix64,
+        min_pool_size: usize,
+        disruptive: usize,
+    ) -> IndexSet<&'a str> {
+        let mut pool = self.enemy_pool_add.iter().copied().collect::<IndexSet<_>>();
+        let mut add_shuffled = |pool: &mut IndexSet<&'a str>, from: &[&'a str], up_to: usize| {
+            let mut from = from.to_vec();
+            rng.shuffle(&mut from);
+            for name in from {
+                if pool.len() >= up_to {
+                    break;
+                }
+                pool.insert(name);
+            }
+        };
+        add_shuffled(&mut pool, &self.common, min_pool_size);
+        let disruptive_up_to = pool.len() + disruptive;
+        add_shuffled(&mut pool, &self.disruptive, disruptive_up_to);
+        add_shuffled(&mut pool, &self.special, min_pool_size);
+        for name in &self.enemy_pool_remove {
+            pool.shift_remove(name);
+        }
+        pool
+    }
+}
+
+fn names(names: &Spanned<Vec<Spanned<Symbol>>>) -> Vec<&str> {
+    names.iter().map(|name| name.as_str()).collect()
+}
+
+/// The Enemy Descriptors in the pool after `clear`, `add` and `remove`, except for the vanilla
+/// Enemy Descriptors of a pool which is not cleared.
+fn known_entries(pool: &Spanned<EnemyPool>) -> Vec<&str> {
+    let remove = names(&pool.remove);
+    names(&pool.add)
+        .into_iter()
+        .filter(|name| !remove.contains(name))
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// A small, seedable pseudo-random number generator, which is plenty for the simulation.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `min` to `max`, inclusive.
+    fn range(&mut self, min: usize, max: usize) -> usize {
+        min + (self.next() % (max - min + 1) as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, vals: &mut [T]) {
+        for i in (1..vals.len()).rev() {
+            let j = self.range(0, i);
+            vals.swap(i, j);
+        }
+    }
+}
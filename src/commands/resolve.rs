@@ -0,0 +1,422 @@
+//! `cdlint resolve`, which prints a Custom Difficulty the way the game loads it.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde_json::{json, Map, Value};
+use tracing::*;
+
+use crate::config::Config;
+use crate::custom_difficulty::{ArrayOrSingleItem, CustomDifficulty, EnemyPool, WeightedRange};
+use crate::driver::{self, Input};
+use crate::hazards::PLAYER_COUNTS;
+use crate::resolve::ResolvedEnemyDescriptor;
+use crate::spanned::Spanned;
+
+#[derive(Debug, clap::Args)]
+pub struct ResolveArgs {
+    /// The path to a Custom Difficulty JSON file.
+    input: PathBuf,
+
+    /// Write the resolved Custom Difficulty to this file instead of printing it.
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+/// The values the game uses for top-level members which are left out, as far as they are known
+/// (see `cd.schema.json`). Values per player count are given for each player count.
+const KNOWN_DEFAULTS: [(&str, &str); 23] = [
+    ("Name", r#""""#),
+    ("Description", r#""""#),
+    ("MaxActiveCritters", "[40, 40, 40, 40]"),
+    ("MaxActiveSwarmers", "[60, 60, 60, 60]"),
+    ("MaxActiveEnemies", "[60, 60, 60, 60]"),
+    ("ResupplyCost", "[80.0, 80.0, 80.0, 80.0]"),
+    ("StartingNitra", "[0, 0, 0, 0]"),
+    ("ExtraLargeEnemyDamageResistance", "[0.75, 0.8, 1.2, 1.7]"),
+    ("ExtraLargeEnemyDamageResistanceB", "[0.7, 0.7, 1.0, 1.3]"),
+    ("ExtraLargeEnemyDamageResistanceC", "[0.6, 0.7, 1.0, 1.6]"),
+    ("ExtraLargeEnemyDamageResistanceD", "[1.3, 1.3, 1.5, 1.65]"),
+    ("EnemyDamageResistance", "[0.75, 0.8, 1.2, 1.7]"),
+    ("SmallEnemyDamageResistance", "[0.75, 0.8, 1.2, 1.7]"),
+    ("EnemyDamageModifier", "[2.8, 3.0, 3.2, 3.4]"),
+    ("EnemyCountModifier", "[0.85, 0.85, 1.25, 1.5]"),
+    (
+        "SeasonalEvents",
+        r#"["SE_ProspectorDrone", "SE_ProspectorDataDeposit", "SE_RivalBomb"]"#,
+    ),
+    (
+        "EscortMule",
+        r#"{
+            "FriendlyFireModifier": 0.1,
+            "NeutralDamageModifier": 0.1,
+            "BigHitDamageModifier": 0.5,
+            "BigHitDamageReductionThreshold": 6.0
+        }"#,
+    ),
+    // A left out Enemy Descriptor map or pool changes nothing, which is what these mean.
+    ("EnemyDescriptors", "{}"),
+    ("EnemyPool", r#"{"clear": false, "add": [], "remove": []}"#),
+    (
+        "CommonEnemies",
+        r#"{"clear": false, "add": [], "remove": []}"#,
+    ),
+    (
+        "DisruptiveEnemies",
+        r#"{"clear": false, "add": [], "remove": []}"#,
+    ),
+    (
+        "SpecialEnemies",
+        r#"{"clear": false, "add": [], "remove": []}"#,
+    ),
+    (
+        "StationaryEnemies",
+        r#"{"clear": false, "add": [], "remove": []}"#,
+    ),
+];
+
+pub fn run(config: &Config, args: ResolveArgs) -> anyhow::Result<()> {
+    let (resolved, unknown) = resolve_file(config, &args.input)?;
+    if !unknown.is_empty() {
+        warn!(
+            "the game's default values of {} are unknown, so they are left out",
+            unknown.join(", ")
+        );
+    }
+
+    let resolved = serde_json::to_string_pretty(&resolved)?;
+    match args.output {
+        Some(output) => std::fs::write(&output, resolved + "\n")
+            .with_context(|| format!("failed to write `{}`", output.display()))?,
+        None => println!("{resolved}"),
+    }
+
+    Ok(())
+}
+
+/// Reads and resolves the Custom Difficulty, see [`resolved_json`]. Fails after printing the errors
+/// if there are any, since the game would likely not load it as resolved.
+pub fn resolve_file(config: &Config, input: &Path) -> anyhow::Result<(Value, Vec<&'static str>)> {
+    let input = Input::read(input)?;
+    let loaded = driver::load_without_errors(config, &input)?;
+    Ok(resolved_json(&loaded.cd))
+}
+
+/// The Custom Difficulty with every member the game reads, in the order of the Custom Difficulty
+/// struct. Values per player count are given for each player count, and Enemy Descriptors are
+/// flattened through their `Base` chains.
+///
+/// Also returns the members which are left out because their default values are unknown.
+pub fn resolved_json(cd: &CustomDifficulty) -> (Value, Vec<&'static str>) {
+    let mut members = Members::default();
+
+    members.push("Name", specified(&cd.name, |name| json!(name)));
+    members.push(
+        "Description",
+        specified(&cd.description, |description| json!(description)),
+    );
+    members.push(
+        "MaxActiveCritters",
+        per_player_count(&cd.max_active_critters),
+    );
+    members.push(
+        "MaxActiveSwarmers",
+        per_player_count(&cd.max_active_swarmers),
+    );
+    members.push("MaxActiveEnemies", per_player_count(&cd.max_active_enemies));
+    members.push("ResupplyCost", per_player_count(&cd.resupply_cost));
+    members.push("StartingNitra", per_player_count(&cd.starting_nitra));
+    members.push(
+        "ExtraLargeEnemyDamageResistance",
+        per_player_count(&cd.extra_large_enemy_damage_resistance),
+    );
+    members.push(
+        "ExtraLargeEnemyDamageResistanceB",
+        per_player_count(&cd.extra_large_enemy_damage_resistance_b),
+    );
+    members.push(
+        "ExtraLargeEnemyDamageResistanceC",
+        per_player_count(&cd.extra_large_enemy_damage_resistance_c),
+    );
+    members.push(
+        "ExtraLargeEnemyDamageResistanceD",
+        per_player_count(&cd.extra_large_enemy_damage_resistance_d),
+    );
+    members.push(
+        "EnemyDamageResistance",
+        per_player_count(&cd.enemy_damage_resistance),
+    );
+    members.push(
+        "SmallEnemyDamageResistance",
+        per_player_count(&cd.small_enemy_damage_resistance),
+    );
+    members.push(
+        "EnemyDamageModifier",
+        per_player_count(&cd.enemy_damage_modifier),
+    );
+    members.push(
+        "EnemyCountModifier",
+        per_player_count(&cd.enemy_count_modifier),
+    );
+    members.push(
+        "EncounterDifficulty",
+        specified(&cd.encounter_difficulty, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "StationaryDifficulty",
+        specified(&cd.stationary_difficulty, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "EnemyWaveInterval",
+        specified(&cd.enemy_wave_interval, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "EnemyNormalWaveInterval",
+        specified(&cd.enemy_normal_wave_interval, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "EnemyNormalWaveDifficulty",
+        specified(&cd.enemy_normal_wave_difficulty, |bins| {
+            weighted_ranges(bins)
+        }),
+    );
+    members.push(
+        "EnemyDiversity",
+        specified(&cd.enemy_diversity, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "StationaryEnemyDiversity",
+        specified(&cd.stationary_enemy_diversity, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "VeteranNormal",
+        specified(&cd.veteran_normal, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "VeteranLarge",
+        specified(&cd.veteran_large, |bins| weighted_ranges(bins)),
+    );
+    members.push(
+        "DisruptiveEnemyPoolCount",
+        specified(
+            &cd.disruptive_enemy_pool_count,
+            |range| json!({ "min": range.min.val, "max": range.max.val }),
+        ),
+    );
+    members.push("MinPoolSize", specified(&cd.min_pool_size, |v| json!(v)));
+    members.push(
+        "MaxActiveElites",
+        specified(&cd.max_active_elites, |v| json!(v)),
+    );
+    members.push(
+        "EnvironmentalDamageModifier",
+        specified(&cd.environmental_damage_modifier, |v| json!(v)),
+    );
+    members.push(
+        "PointExtractionScalar",
+        specified(&cd.point_extraction_scalar, |v| json!(v)),
+    );
+    members.push("HazardBonus", specified(&cd.hazard_bonus, |v| json!(v)));
+    members.push(
+        "FriendlyFireModifier",
+        specified(&cd.friendly_fire_modifier, |v| json!(v)),
+    );
+    members.push(
+        "WaveStartDelayScale",
+        specified(&cd.wave_start_delay_scale, |v| json!(v)),
+    );
+    members.push("SpeedModifier", specified(&cd.speed_modifier, |v| json!(v)));
+    members.push(
+        "AttackCooldownModifier",
+        specified(&cd.attack_cooldown_modifier, |v| json!(v)),
+    );
+    members.push(
+        "ProjectileSpeedModifier",
+        specified(&cd.projectile_speed_modifier, |v| json!(v)),
+    );
+    members.push(
+        "HealthRegenerationMax",
+        specified(&cd.health_regeneration_max, |v| json!(v)),
+    );
+    members.push(
+        "ReviveHealthRatio",
+        specified(&cd.revive_health_ratio, |v| json!(v)),
+    );
+    members.push("EliteCooldown", specified(&cd.elite_cooldown, |v| json!(v)));
+    members.push(
+        "EnemyDescriptors",
+        specified(&cd.enemy_descriptors, |_| enemy_descriptors(cd)),
+    );
+    members.push("EnemyPool", specified(&cd.enemy_pool, enemy_pool));
+    members.push("CommonEnemies", specified(&cd.common_enemies, enemy_pool));
+    members.push(
+        "DisruptiveEnemies",
+        specified(&cd.disruptive_enemies, enemy_pool),
+    );
+    members.push("SpecialEnemies", specified(&cd.special_enemies, enemy_pool));
+    members.push(
+        "StationaryEnemies",
+        specified(&cd.stationary_enemies, enemy_pool),
+    );
+    members.push(
+        "SeasonalEvents",
+        specified(&cd.seasonal_events, |events| {
+            json!(events.iter().map(|event| &event.val).collect::<Vec<_>>())
+        }),
+    );
+    members.push(
+        "EscortMule",
+        specified(&cd.escort_mule, |mule| {
+            json!({
+                "FriendlyFireModifier": mule.friendly_fire_modifier.val,
+                "NeutralDamageModifier": mule.neutral_damage_modifier.val,
+                "BigHitDamageModifier": mule.big_hit_damage_modifier.val,
+                "BigHitDamageReductionThreshold": mule.big_hit_damage_reduction_threshold.val,
+            })
+        }),
+    );
+
+    (Value::Object(members.json), members.unknown)
+}
+
+/// The top-level members of the resolved Custom Difficulty.
+#[derive(Default)]
+struct Members {
+    json: Map<String, Value>,
+    unknown: Vec<&'static str>,
+}
+
+impl Members {
+    /// Adds the member with its specified value, or with its default value if it is not specified.
+    fn push(&mut self, name: &'static str, val: Option<Value>) {
+        let default = || {
+            let (_, default) = KNOWN_DEFAULTS.iter().find(|(member, _)| *member == name)?;
+            Some(serde_json::from_str(default).expect("known defaults are valid JSON"))
+        };
+        match val.or_else(default) {
+            Some(val) => {
+                self.json.insert(name.to_string(), val);
+            }
+            None => self.unknown.push(name),
+        }
+    }
+}
+
+fn specified<T>(val: &Spanned<T>, to_json: impl FnOnce(&T) -> Value) -> Option<Value> {
+    val.is_specified().then(|| to_json(&val.val))
+}
+
+/// The value for each player count. An array with fewer values than player counts uses its last
+/// value for the remaining player counts.
+fn per_player_count<T: Copy + Into<Value>>(val: &Spanned<ArrayOrSingleItem<T>>) -> Option<Value> {
+    if !val.is_specified() {
+        return None;
+    }
+    let values = match &val.val {
+        ArrayOrSingleItem::Array(values) => values.as_slice(),
+        ArrayOrSingleItem::SingleItem(value) => std::slice::from_ref(value),
+    };
+    let last = *values.last()?;
+    Some(
+        (0..PLAYER_COUNTS)
+            .map(|i| values.get(i).copied().unwrap_or(last).into())
+            .collect(),
+    )
+}
+
+fn weighted_ranges<T: Copy + Into<Value>>(bins: &[Spanned<WeightedRange<T>>]) -> Value {
+    bins.iter()
+        .map(|bin| {
+            json!({
+                "weight": bin.weight.val,
+                "range": {
+                    "min": bin.range.min.val.into(),
+                    "max": bin.range.max.val.into(),
+                },
+            })
+        })
+        .collect()
+}
+
+fn enemy_pool(pool: &EnemyPool) -> Value {
+    json!({
+        "clear": pool.clear.val,
+        "add": pool.add.iter().map(|name| &name.val).collect::<Vec<_>>(),
+        "remove": pool.remove.iter().map(|name| &name.val).collect::<Vec<_>>(),
+    })
+}
+
+/// Enemy Descriptors with a cyclic `Base` chain can't be resolved, and are left out.
+fn enemy_descriptors(cd: &CustomDifficulty) -> Value {
+    let mut eds = Map::new();
+    for name in cd.enemy_descriptors.keys() {
+        match cd.resolve_enemy_descriptor(name) {
+            Some(ed) => {
+                eds.insert(name.val.to_string(), enemy_descriptor(&ed));
+            }
+            None => warn!(
+                "Enemy Descriptor `{}` has a cyclic `Base` chain, so it is left out",
+                name.val
+            ),
+        }
+    }
+    Value::Object(eds)
+}
+
+/// Fields which are only known from the vanilla Enemy Descriptor it inherits from are left out, with
+/// `Base` naming that Enemy Descriptor.
+fn enemy_descriptor(ed: &ResolvedEnemyDescriptor<'_>) -> Value {
+    let mut json = Map::new();
+    let mut field = |name: &str, val: Option<Value>| {
+        if let Some(val) = val {
+            json.insert(name.to_string(), val);
+        }
+    };
+
+    field("Base", ed.inherits_from.map(|base| json!(base)));
+    field("SpawnSpread", ed.spawn_spread.map(|v| json!(v.val.val)));
+    field(
+        "IdealSpawnSize",
+        ed.ideal_spawn_size.map(|v| json!(v.val.val)),
+    );
+    field(
+        "CanBeUsedForConstantPressure",
+        ed.can_be_used_for_constant_pressure
+            .map(|v| json!(v.val.val)),
+    );
+    field(
+        "CanBeUsedInEncounters",
+        ed.can_be_used_in_encounters.map(|v| json!(v.val.val)),
+    );
+    field(
+        "DifficultyRating",
+        ed.difficulty_rating.map(|v| json!(v.val.val)),
+    );
+    field(
+        "MinSpawnCount",
+        ed.min_spawn_count.map(|v| json!(v.val.val)),
+    );
+    field(
+        "MaxSpawnCount",
+        ed.max_spawn_count.map(|v| json!(v.val.val)),
+    );
+    field("Rarity", ed.rarity.map(|v| json!(v.val.val)));
+    field(
+        "SpawnAmountModifier",
+        ed.spawn_amount_modifier.map(|v| json!(v.val.val)),
+    );
+    field("Elite", ed.elite.map(|v| json!(v.val.val)));
+    field("Scale", ed.scale.map(|v| json!(v.val.val)));
+    field("TimeDilation", ed.time_dilation.map(|v| json!(v.val.val)));
+    field(
+        "PawnStats",
+        (!ed.pawn_stats.is_empty()).then(|| {
+            ed.pawn_stats
+                .iter()
+                .map(|(stat, v)| (stat.to_string(), json!(v.val.val)))
+                .collect()
+        }),
+    );
+
+    Value::Object(json)
+}
@@ -0,0 +1,179 @@
+//! `cdlint stats`, which summarizes what a Custom Difficulty changes.
+
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::commands::resolve::resolved_json;
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::driver::{self, Input};
+use crate::handlers::FromSpannedMembers;
+use crate::hazards::PLAYER_COUNTS;
+use crate::spanned::Spanned;
+
+#[derive(Debug, clap::Args)]
+pub struct StatsArgs {
+    /// The path to a Custom Difficulty JSON file.
+    input: PathBuf,
+}
+
+/// Lines listing members are wrapped at this width.
+const MAX_LINE_WIDTH: usize = 80;
+
+pub fn run(config: &Config, args: StatsArgs) -> anyhow::Result<()> {
+    let input = Input::read(&args.input)?;
+    let loaded = driver::load_without_errors(config, &input)?;
+    let cd = &loaded.cd;
+    let (resolved, _) = resolved_json(cd);
+
+    if cd.name.is_specified() && !cd.name.is_empty() {
+        println!("{} ({})", cd.name.val, input.path);
+    } else {
+        println!("{}", input.path);
+    }
+
+    println!();
+    println!("Per player count");
+    let label_width = "Resupplies at mission start".len();
+    let header = (1..=PLAYER_COUNTS)
+        .map(|players| format!("{:>8}", format!("{players}P")))
+        .collect::<String>();
+    println!("  {:label_width$}{header}", "");
+    let row = |label: &str, vals: Vec<String>| {
+        let vals = vals
+            .iter()
+            .map(|val| format!("{val:>8}"))
+            .collect::<String>();
+        println!("  {label:label_width$}{vals}");
+    };
+    let member = |name: &str| per_player_count(&resolved, name);
+    let format = |vals: &[Option<f64>]| {
+        vals.iter()
+            .map(|val| val.map_or_else(|| "?".to_string(), |val| val.to_string()))
+            .collect::<Vec<_>>()
+    };
+    row("Max active enemies", format(&member("MaxActiveEnemies")));
+    row("Max active swarmers", format(&member("MaxActiveSwarmers")));
+    row("Max active critters", format(&member("MaxActiveCritters")));
+    row(
+        "Enemy count modifier",
+        format(&member("EnemyCountModifier")),
+    );
+    row(
+        "Enemy damage modifier",
+        format(&member("EnemyDamageModifier")),
+    );
+    row("Resupply cost", format(&member("ResupplyCost")));
+    row("Starting nitra", format(&member("StartingNitra")));
+    let resupplies = member("ResupplyCost")
+        .into_iter()
+        .zip(member("StartingNitra"))
+        .map(|vals| match vals {
+            (Some(cost), Some(nitra)) if cost > 0.0 => (nitra / cost).floor().to_string(),
+            (Some(_), Some(_)) => "any".to_string(),
+            _ => "?".to_string(),
+        })
+        .collect();
+    row("Resupplies at mission start", resupplies);
+
+    println!();
+    println!("Pools");
+    let pools = [
+        ("EnemyPool", &cd.enemy_pool),
+        ("CommonEnemies", &cd.common_enemies),
+        ("DisruptiveEnemies", &cd.disruptive_enemies),
+        ("SpecialEnemies", &cd.special_enemies),
+        ("StationaryEnemies", &cd.stationary_enemies),
+    ];
+    let pool_width = pools.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, pool) in pools {
+        println!("  {name:pool_width$}  {}", describe_pool(pool));
+    }
+
+    println!();
+    println!("Enemy Descriptors");
+    let (modified, new): (Vec<_>, Vec<_>) = cd.enemy_descriptors.keys().partition(|name| {
+        config.game_data().is_vanilla_enemy_descriptor(&name.val)
+            || config.is_extra_enemy_descriptor(&name.val)
+    });
+    println!("  {} defined", cd.enemy_descriptors.len());
+    println!("  {} new", new.len());
+    println!(
+        "  {} modifying vanilla or modded Enemy Descriptors",
+        modified.len()
+    );
+
+    println!();
+    println!("Left at the game's values");
+    let untouched = <CustomDifficulty as FromSpannedMembers>::MEMBERS
+        .iter()
+        .filter(|member| {
+            !loaded
+                .top_level_members
+                .iter()
+                .any(|(name, _)| name.val == **member)
+        })
+        .copied()
+        .collect::<Vec<_>>();
+    if untouched.is_empty() {
+        println!("  none");
+    } else {
+        for line in wrap(&untouched, MAX_LINE_WIDTH - 2) {
+            println!("  {line}");
+        }
+    }
+
+    Ok(())
+}
+
+/// The value of the resolved member for each player count, if known.
+fn per_player_count(resolved: &Value, name: &str) -> [Option<f64>; PLAYER_COUNTS] {
+    let vals = resolved.get(name).and_then(Value::as_array);
+    std::array::from_fn(|i| vals.and_then(|vals| vals.get(i)).and_then(Value::as_f64))
+}
+
+fn describe_pool(pool: &Spanned<EnemyPool>) -> String {
+    if !pool.is_specified() {
+        return "unchanged".to_string();
+    }
+    let mut changes = Vec::new();
+    if pool.clear.val {
+        changes.push("cleared".to_string());
+    }
+    changes.push(format!("{} added", pool.add.len()));
+    changes.push(format!("{} removed", pool.remove.len()));
+    let mut description = changes.join(", ");
+    if let Some(effective) = pool.effective_pool() {
+        description.push_str(&format!(
+            ", leaving {} Enemy Descriptor{}",
+            effective.len(),
+            if effective.len() == 1 { "" } else { "s" }
+        ));
+    }
+    description
+}
+
+/// Joins the words with commas into lines no longer than `width`, unless a word is longer.
+fn wrap(words: &[&str], width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for (i, word) in words.iter().enumerate() {
+        let word = if i + 1 < words.len() {
+            format!("{word},")
+        } else {
+            word.to_string()
+        };
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use confique::Config as DeriveConfig;
 
+use crate::game_data::{self, GameData};
+
 #[derive(Debug, DeriveConfig)]
 pub struct Config {
     /// Add your custom enemy descriptors e.g. `ED_EnemyName` to this list, so that lints such as
@@ -7,10 +11,85 @@ pub struct Config {
     #[config(default = [])]
     pub extra_enemy_descriptors: Vec<String>,
 
-    /// Would you like `cyclic_enemy_descriptor_references` lint to generate a graphviz graph of
-    /// the "based-on" relationships between Enemy Descriptors? Note that if this option is
-    /// enabled, the graphviz `dot` command line must be installed:
-    /// <https://graphviz.org/download/>.
+    /// Enemy Descriptor names which are handled by companion mods as another Enemy Descriptor, e.g.
+    /// `ED_Grunt = "ED_Spider_Grunt"`. References to an alias are checked as references to the
+    /// Enemy Descriptor it stands for. Aliases are not resolved transitively.
+    #[config(default = {})]
+    pub aliases: BTreeMap<String, String>,
+
+    /// Would you like `cyclic_enemy_descriptor_references` lint to generate a graph of the
+    /// "based-on" relationships between Enemy Descriptors? It is written next to the executable
+    /// both as an SVG, which can be opened in a browser, and as a graphviz `.dot` file, which can
+    /// be rendered with graphviz: <https://graphviz.org/download/>.
     #[config(default = false)]
     pub generate_cyclic_reference_graph: bool,
+
+    /// The maximum number of `Base` links allowed between an Enemy Descriptor and the Enemy
+    /// Descriptor its `Base` chain ends at, before the `deep-enemy-descriptor-base-chains` lint
+    /// warns about it.
+    #[config(default = 3)]
+    pub max_enemy_descriptor_base_depth: usize,
+
+    /// The maximum number of characters in the custom difficulty name before the `long-cd-name`
+    /// lint warns that it may get truncated in the in-game UI.
+    #[config(default = 32)]
+    pub max_cd_name_length: usize,
+
+    /// The rich text tags e.g. `Red` (as in `<Red>text</>`) which may be used in the custom
+    /// difficulty name and description. If empty, the `rich-text-markup` lint only checks that
+    /// tags are balanced, and does not check tag names.
+    #[config(default = [])]
+    pub rich_text_tags: Vec<String>,
+
+    /// The `[min, max]` range of Enemy Descriptor `Scale` values outside of which the
+    /// `extreme-enemy-descriptor-values` lint warns.
+    #[config(default = [0.1, 10.0])]
+    pub sane_scale_range: [f64; 2],
+
+    /// The `[min, max]` range of Enemy Descriptor `TimeDilation` values outside of which the
+    /// `extreme-enemy-descriptor-values` lint warns.
+    #[config(default = [0.1, 10.0])]
+    pub sane_time_dilation_range: [f64; 2],
+
+    /// Lint groups which are allow-by-default but should be run, e.g. `cdlint::nursery` for
+    /// experimental lints with a higher chance of false positives.
+    #[config(default = [])]
+    pub enabled_lint_groups: Vec<String>,
+
+    /// Should the `json-comments` lint report `//` and `/* */` comments as errors? If `false`,
+    /// they are reported as warnings instead.
+    #[config(default = true)]
+    pub deny_json_comments: bool,
+
+    /// The maximum nesting depth of arrays and objects. More deeply nested JSON is rejected
+    /// before parsing, so that it can't overflow the stack.
+    #[config(default = 128)]
+    pub max_json_nesting_depth: usize,
+
+    /// Which game version's vanilla data (Enemy Descriptors, seasonal events, pawn stats, etc.)
+    /// lints check against, e.g. `season05`, or `latest` for the newest one, including data
+    /// downloaded by `cdlint data update`. Can be overridden with `--game-version`.
+    #[config(default = "latest")]
+    pub game_version: String,
+}
+
+impl Config {
+    /// The Enemy Descriptor the name stands for, per `aliases`.
+    pub fn resolve_alias<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases.get(name).map_or(name, String::as_str)
+    }
+
+    /// Whether the Enemy Descriptor is listed in `extra_enemy_descriptors`.
+    pub fn is_extra_enemy_descriptor(&self, name: &str) -> bool {
+        self.extra_enemy_descriptors
+            .iter()
+            .any(|extra| extra == name)
+    }
+
+    /// The vanilla game data of the selected game version. Unknown game versions are rejected on
+    /// startup, but fall back to the latest game version here.
+    pub fn game_data(&self) -> &'static GameData {
+        GameData::for_version(&self.game_version)
+            .unwrap_or(game_data::BUILT_IN[game_data::BUILT_IN.len() - 1])
+    }
 }
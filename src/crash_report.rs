@@ -0,0 +1,80 @@
+//! Reporting panics as bugs in CDLint. Besides the panic message, the user is told which file was
+//! being checked and which lint was running, and asked to report the bug with a minimized Custom
+//! Difficulty which still crashes CDLint.
+
+use std::cell::RefCell;
+
+const NEW_ISSUE_URL: &str = "https://github.com/jieyouxu/CDLint/issues/new";
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::default();
+}
+
+/// What CDLint is doing, as far as it matters for a crash report.
+#[derive(Debug, Default, Clone)]
+struct Context {
+    /// The path of the Custom Difficulty being checked, as shown in diagnostics.
+    file: Option<String>,
+    lint: Option<&'static str>,
+}
+
+/// Restores what CDLint was doing before when dropped, see [`enter_file`] and [`enter_lint`].
+#[must_use]
+pub struct ContextGuard {
+    previous: Context,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.previous);
+        CONTEXT.with_borrow_mut(|cx| *cx = previous);
+    }
+}
+
+fn enter(f: impl FnOnce(&mut Context)) -> ContextGuard {
+    CONTEXT.with_borrow_mut(|cx| {
+        let previous = cx.clone();
+        f(cx);
+        ContextGuard { previous }
+    })
+}
+
+/// Records that the Custom Difficulty at `path` is being checked, until the guard is dropped.
+pub fn enter_file(path: &str) -> ContextGuard {
+    enter(|cx| {
+        cx.file = Some(path.to_string());
+        cx.lint = None;
+    })
+}
+
+/// Records that the lint is running, until the guard is dropped.
+pub fn enter_lint(lint: &'static str) -> ContextGuard {
+    enter(|cx| cx.lint = Some(lint))
+}
+
+/// Prints the crash report after the panic message and backtrace.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+
+        // The context can't be borrowed if the panic happened while it was being changed.
+        let cx = CONTEXT
+            .try_with(|cx| cx.try_borrow().map(|cx| cx.clone()).unwrap_or_default())
+            .unwrap_or_default();
+        let doing = match (&cx.file, cx.lint) {
+            (Some(file), Some(lint)) => format!(" while running the `{lint}` lint on `{file}`"),
+            (Some(file), None) => format!(" while checking `{file}`"),
+            (None, _) => String::new(),
+        };
+        eprintln!();
+        eprintln!("error: internal error: CDLint crashed{doing}");
+        eprintln!("note: this is a bug in CDLint, please report it at {NEW_ISSUE_URL}");
+        eprintln!(
+            "note: include the output above, the CDLint version ({}) and the smallest Custom \
+             Difficulty which still crashes CDLint, e.g. with the members which aren't needed to \
+             crash it removed",
+            env!("CARGO_PKG_VERSION")
+        );
+    }));
+}
@@ -1,8 +1,9 @@
-use chumsky::span::SimpleSpan;
-use indexmap::IndexMap;
+use cdlint_derive::FromSpannedJson;
+use indexmap::{IndexMap, IndexSet};
 use serde::Deserialize;
 
 use crate::spanned::Spanned;
+use crate::symbol::Symbol;
 
 #[derive(Debug, PartialEq, Deserialize)]
 #[serde(untagged)]
@@ -17,9 +18,11 @@ impl<T: Default> Default for ArrayOrSingleItem<T> {
     }
 }
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize, FromSpannedJson)]
 pub struct Range<T> {
+    #[cd(required)]
     pub min: Spanned<T>,
+    #[cd(required)]
     pub max: Spanned<T>,
 }
 
@@ -32,24 +35,54 @@ impl<T: Default> Default for Range<T> {
     }
 }
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize, FromSpannedJson)]
 pub struct WeightedRange<T> {
+    #[cd(required)]
     pub weight: Spanned<f64>,
+    #[cd(required)]
     pub range: Spanned<Range<T>>,
 }
 
-#[derive(Debug, PartialEq, Deserialize, Default)]
+/// An array of weighted bins with each bin reduced to its weight, spanned like the bin, so that
+/// arrays whose ranges differ in type can be handled together.
+pub type WeightedBinWeights<'a> = Spanned<Vec<Spanned<&'a Spanned<f64>>>>;
+
+#[derive(Debug, PartialEq, Deserialize, Default, FromSpannedJson)]
 pub struct EnemyPool {
     #[serde(default)]
     pub clear: Spanned<bool>,
     #[serde(default)]
-    pub add: Spanned<Vec<Spanned<String>>>,
+    pub add: Spanned<Vec<Spanned<Symbol>>>,
     #[serde(default)]
-    pub remove: Spanned<Vec<Spanned<String>>>,
+    pub remove: Spanned<Vec<Spanned<Symbol>>>,
 }
 
-#[derive(Debug, PartialEq, Deserialize, Default)]
+impl EnemyPool {
+    /// The Enemy Descriptors left in the pool after `clear`, `add` and `remove` are applied (in
+    /// that order). Returns `None` if the pool is not cleared, because then the pool still contains
+    /// its vanilla Enemy Descriptors which we don't know about.
+    pub fn effective_pool(&self) -> Option<IndexSet<&str>> {
+        if !self.clear.val {
+            return None;
+        }
+
+        let mut pool = self
+            .add
+            .val
+            .iter()
+            .map(|name| name.as_str())
+            .collect::<IndexSet<_>>();
+        for name in &self.remove.val {
+            pool.shift_remove(name.as_str());
+        }
+
+        Some(pool)
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize, Default, FromSpannedJson)]
 #[serde(rename_all = "PascalCase")]
+#[cd(rename_all = "PascalCase")]
 pub struct EscortMule {
     /// The damage taken from players.
     pub friendly_fire_modifier: Spanned<f64>,
@@ -65,12 +98,13 @@ pub struct EscortMule {
 #[derive(Debug, PartialEq, Default, Deserialize)]
 pub struct PawnStats(pub IndexMap<Spanned<String>, Spanned<f64>>);
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize, FromSpannedJson)]
 #[serde(rename_all = "PascalCase")]
+#[cd(rename_all = "PascalCase", ignore = "UseSpawnRarityModifiers")]
 pub struct EnemyDescriptor {
     /// The EnemyDescriptor to copy values from. Required if defining a new EnemyDescriptor.
     #[serde(default)]
-    pub base: Spanned<String>,
+    pub base: Spanned<Symbol>,
     /// The maximum distance enemies can spawn from the center of spawn point in centimeters.
     #[serde(default)]
     pub spawn_spread: Spanned<f64>,
@@ -108,17 +142,9 @@ pub struct EnemyDescriptor {
     pub pawn_stats: Spanned<PawnStats>,
 }
 
-impl<T: Default> Default for Spanned<T> {
-    fn default() -> Self {
-        Self {
-            span: SimpleSpan::new(usize::MAX, usize::MAX),
-            val: Default::default(),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Deserialize, Default)]
+#[derive(Debug, PartialEq, Deserialize, Default, FromSpannedJson)]
 #[serde(rename_all = "PascalCase")]
+#[cd(rename_all = "PascalCase")]
 pub struct CustomDifficulty {
     /// The difficulty name
     #[serde(default)]
@@ -257,7 +283,7 @@ pub struct CustomDifficulty {
     /// be used to define new `EnemyDescriptor`s that can be added to pools or modify (or completely
     /// replace) existing `EnemyDescriptor`s.
     #[serde(default)]
-    pub enemy_descriptors: Spanned<IndexMap<Spanned<String>, Spanned<EnemyDescriptor>>>,
+    pub enemy_descriptors: Spanned<IndexMap<Spanned<Symbol>, Spanned<EnemyDescriptor>>>,
     /// The enemy pool which is what the game pulls `EnemyDescriptor`s from when attempting to spawn
     /// enemies. This pool is built by pulling enemies from the `CommonEnemies`,
     /// `DisruptiveEnemies`, and `SpecialEnemies` pools upon mission start. It is recommended to not
@@ -285,3 +311,69 @@ pub struct CustomDifficulty {
     #[serde(default)]
     pub escort_mule: Spanned<EscortMule>,
 }
+
+impl CustomDifficulty {
+    /// The definition of the given Enemy Descriptor in this Custom Difficulty, if any.
+    pub fn enemy_descriptor(&self, name: &str) -> Option<&Spanned<EnemyDescriptor>> {
+        self.enemy_descriptors
+            .val
+            .iter()
+            .find(|(ed_name, _)| ed_name.val == name)
+            .map(|(_, ed)| ed)
+    }
+
+    /// The Enemy Descriptors visited when following `Base` from the given Enemy Descriptor,
+    /// starting with itself. The chain ends at an Enemy Descriptor without a `Base`, based on
+    /// itself, or not (re)defined in this Custom Difficulty. Returns `None` if the chain is cyclic.
+    pub fn base_chain<'a>(&'a self, name: &'a str) -> Option<Vec<&'a str>> {
+        let mut chain = vec![name];
+        let mut current = name;
+
+        while let Some(ed) = self.enemy_descriptor(current) {
+            if !ed.val.base.is_specified() || ed.val.base.val == current {
+                break;
+            }
+
+            current = &ed.val.base.val;
+            if chain.contains(&current) {
+                return None;
+            }
+            chain.push(current);
+        }
+
+        Some(chain)
+    }
+
+    /// The members which are arrays of weighted bins, by name.
+    pub fn weighted_bin_weights(&self) -> [(&'static str, WeightedBinWeights<'_>); 9] {
+        fn weights<T>(bins: &Spanned<Vec<Spanned<WeightedRange<T>>>>) -> WeightedBinWeights<'_> {
+            let weights = bins
+                .val
+                .iter()
+                .map(|bin| Spanned::new(&bin.val.weight, bin.span))
+                .collect();
+            Spanned::new(weights, bins.span)
+        }
+
+        [
+            ("EncounterDifficulty", weights(&self.encounter_difficulty)),
+            ("StationaryDifficulty", weights(&self.stationary_difficulty)),
+            ("EnemyWaveInterval", weights(&self.enemy_wave_interval)),
+            (
+                "EnemyNormalWaveInterval",
+                weights(&self.enemy_normal_wave_interval),
+            ),
+            (
+                "EnemyNormalWaveDifficulty",
+                weights(&self.enemy_normal_wave_difficulty),
+            ),
+            ("EnemyDiversity", weights(&self.enemy_diversity)),
+            (
+                "StationaryEnemyDiversity",
+                weights(&self.stationary_enemy_diversity),
+            ),
+            ("VeteranNormal", weights(&self.veteran_normal)),
+            ("VeteranLarge", weights(&self.veteran_large)),
+        ]
+    }
+}
@@ -0,0 +1,556 @@
+//! Diagnostics reported by handlers and lints.
+//!
+//! [`Report`] mirrors the subset of the `ariadne` report API used in this crate, but keeps the
+//! diagnostic's contents (kind, spans, messages and the lint which produced it) accessible so that
+//! diagnostics can be filtered before they are rendered with `ariadne`.
+
+use std::borrow::Cow;
+use std::io;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+pub use ariadne::{Color, Fmt, ReportKind, Source};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::spanned::{is_synthetic, synthetic_sp};
+
+/// How diagnostics are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// Rich reports with source snippets and labels.
+    #[default]
+    Human,
+    /// One line per diagnostic: `path:line:col: level[lint-name]: message`, followed by a
+    /// `path:line:col: note: message` line for each note about another span.
+    Short,
+}
+
+/// What linting outputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// The diagnostics as given by `--error-format`, followed by a summary.
+    #[default]
+    Text,
+    /// A standalone HTML page with the diagnostics, the Custom Difficulty's source and its Enemy
+    /// Descriptor graph.
+    Html,
+}
+
+/// Whether diagnostics are colored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color if printing to a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Overrides whether `ariadne` (and [`Fmt::fg`]) colors its output for the rest of the run.
+    pub fn apply(self) {
+        concolor::set(match self {
+            ColorChoice::Auto => concolor::ColorChoice::Auto,
+            ColorChoice::Always => concolor::ColorChoice::Always,
+            ColorChoice::Never => concolor::ColorChoice::Never,
+        });
+    }
+}
+
+/// How diagnostics are printed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmitOptions {
+    pub error_format: ErrorFormat,
+    /// Only use ASCII characters for the report drawings.
+    pub ascii: bool,
+    /// Collapse the diagnostics of lints which fire many times, see [`collapse_repeated`].
+    pub collapse: bool,
+}
+
+static EMIT_OPTIONS: OnceLock<EmitOptions> = OnceLock::new();
+
+/// Sets how diagnostics are printed for the rest of the run. Handlers print some diagnostics
+/// immediately, so this is global rather than passed around.
+pub fn set_emit_options(options: EmitOptions) {
+    EMIT_OPTIONS
+        .set(options)
+        .expect("emit options should only be set once");
+}
+
+fn emit_options() -> EmitOptions {
+    EMIT_OPTIONS.get().copied().unwrap_or_default()
+}
+
+/// How many times a lint has to fire in one file for its diagnostics to be collapsed.
+const COLLAPSE_THRESHOLD: usize = 10;
+/// How many occurrences a collapsed diagnostic points at.
+const COLLAPSED_OCCURRENCES: usize = 4;
+
+/// Collapses the diagnostics of each lint which fires at least [`COLLAPSE_THRESHOLD`] times into its
+/// first diagnostic, which also points at the next few occurrences and notes how many more there
+/// are. Only human-readable diagnostics are collapsed, and only unless `--no-collapse` is given.
+pub fn collapse_repeated<'r, 'a>(diagnostics: Vec<&'r Report<'a>>) -> Vec<Cow<'r, Report<'a>>> {
+    let options = emit_options();
+    if !options.collapse || options.error_format != ErrorFormat::Human {
+        return diagnostics.into_iter().map(Cow::Borrowed).collect();
+    }
+
+    let mut occurrences = IndexMap::<_, Vec<_>>::new();
+    for diagnostic in &diagnostics {
+        if let Some(lint) = diagnostic.lint {
+            occurrences.entry(lint).or_default().push(*diagnostic);
+        }
+    }
+
+    let mut collapsed = Vec::new();
+    for diagnostic in diagnostics {
+        let Some(occurrences) = diagnostic
+            .lint
+            .and_then(|lint| occurrences.get(lint))
+            .filter(|occurrences| occurrences.len() >= COLLAPSE_THRESHOLD)
+        else {
+            collapsed.push(Cow::Borrowed(diagnostic));
+            continue;
+        };
+        if !std::ptr::eq(occurrences[0], diagnostic) {
+            continue;
+        }
+
+        let mut report = diagnostic.clone();
+        let color = report.labels.first().and_then(|label| label.color);
+        for occurrence in &occurrences[1..COLLAPSED_OCCURRENCES] {
+            let span = occurrence.primary_span();
+            if is_synthetic(&span) || span.is_empty() {
+                continue;
+            }
+            let mut label = Label::new((occurrence.path, span));
+            label.color = color;
+            if let Some(message) = &occurrence.message {
+                label = label.with_message(message);
+            }
+            report.labels.push(label);
+        }
+        let more = occurrences.len() - COLLAPSED_OCCURRENCES;
+        report.notes.push(Note {
+            span: None,
+            message: format!(
+                "and {more} more occurrences of `{}`, pass `--no-collapse` to show them all",
+                diagnostic.lint.unwrap_or_default()
+            ),
+        });
+        collapsed.push(Cow::Owned(report));
+    }
+    collapsed
+}
+
+type Span<'a> = (&'a String, Range<usize>);
+
+#[derive(Debug, Clone)]
+pub struct Label<'a> {
+    pub span: Span<'a>,
+    pub color: Option<Color>,
+    pub message: Option<String>,
+}
+
+impl<'a> Label<'a> {
+    pub fn new(span: Span<'a>) -> Self {
+        Self {
+            span,
+            color: None,
+            message: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_message<M: ToString>(mut self, message: M) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    fn to_ariadne(&self) -> ariadne::Label<Span<'a>> {
+        let mut label = ariadne::Label::new(self.span.clone());
+        if let Some(color) = self.color {
+            label = label.with_color(color);
+        }
+        if let Some(message) = &self.message {
+            label = label.with_message(message);
+        }
+        label
+    }
+}
+
+/// How confident a [`Suggestion`] is that its replacement is correct, so that fixes can be applied
+/// automatically only when they are safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Applicability {
+    /// The replacement is definitely what the author meant, and can be applied without review.
+    MachineApplicable,
+    /// The replacement is probably what the author meant, e.g. the closest known name to a
+    /// misspelled one, but should be reviewed before it is applied.
+    MaybeIncorrect,
+    /// The replacement contains placeholders such as `<value>` which have to be filled in.
+    HasPlaceholders,
+}
+
+/// How a [`Suggestion`] changes the source, which decides how it is worded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuggestionKind {
+    Replace,
+    /// Inserts the replacement at the span, which is empty.
+    Insert,
+    /// Removes the source at the span, which is described as `what`, e.g. "the comment". The
+    /// replacement is empty.
+    Remove {
+        what: String,
+    },
+}
+
+/// A fix for a diagnostic: replacing the source at `span` with `replacement`. Insertions have an
+/// empty span and removals an empty replacement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub span: Range<usize>,
+    pub replacement: String,
+    pub applicability: Applicability,
+    pub kind: SuggestionKind,
+}
+
+impl Suggestion {
+    pub fn new<R: ToString>(
+        span: Range<usize>,
+        replacement: R,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            span,
+            replacement: replacement.to_string(),
+            applicability,
+            kind: SuggestionKind::Replace,
+        }
+    }
+
+    /// Inserts `text` at `offset`.
+    pub fn insert<T: ToString>(offset: usize, text: T, applicability: Applicability) -> Self {
+        Self {
+            span: offset..offset,
+            replacement: text.to_string(),
+            applicability,
+            kind: SuggestionKind::Insert,
+        }
+    }
+
+    /// Removes the source at `span`, which the help message calls `what`.
+    pub fn remove<W: ToString>(span: Range<usize>, what: W, applicability: Applicability) -> Self {
+        Self {
+            span,
+            replacement: String::new(),
+            applicability,
+            kind: SuggestionKind::Remove {
+                what: what.to_string(),
+            },
+        }
+    }
+
+    /// The help message the suggestion is shown as.
+    pub fn message(&self) -> String {
+        let message = match &self.kind {
+            SuggestionKind::Replace => format!("replace with `{}`", self.replacement),
+            SuggestionKind::Insert => format!("insert `{}`", self.replacement),
+            SuggestionKind::Remove { what } => format!("remove {what}"),
+        };
+        match self.applicability {
+            Applicability::HasPlaceholders => format!("{message} and fill in the placeholders"),
+            Applicability::MachineApplicable | Applicability::MaybeIncorrect => message,
+        }
+    }
+}
+
+/// Additional information about a diagnostic, e.g. "the pool is cleared here", which may point at
+/// a span other than the ones the diagnostic is about.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    pub span: Option<Range<usize>>,
+    pub message: String,
+}
+
+impl Note {
+    /// The span the note is about, unless it is about the diagnostic as a whole or a synthetic span.
+    pub fn file_span(&self) -> Option<Range<usize>> {
+        self.span.clone().filter(|span| !is_synthetic(span))
+    }
+}
+
+/// `s` as a JSON string literal, for suggestions which replace a string including its quotes.
+pub fn string_literal(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
+#[derive(Debug, Clone)]
+pub struct Report<'a> {
+    pub kind: ReportKind<'static>,
+    pub path: &'a String,
+    pub offset: usize,
+    pub message: Option<String>,
+    pub labels: Vec<Label<'a>>,
+    pub help: Option<String>,
+    /// Notes in the order they were added.
+    pub notes: Vec<Note>,
+    /// Fixes for the diagnostic, which are shown after the help.
+    pub suggestions: Vec<Suggestion>,
+    /// The name of the lint which produced this diagnostic, if it was produced by a lint.
+    pub lint: Option<&'static str>,
+}
+
+impl<'a> Report<'a> {
+    pub fn build(kind: ReportKind<'static>, path: &'a String, offset: usize) -> ReportBuilder<'a> {
+        ReportBuilder {
+            report: Report {
+                kind,
+                path,
+                offset,
+                message: None,
+                labels: Vec::new(),
+                help: None,
+                notes: Vec::new(),
+                suggestions: Vec::new(),
+                lint: None,
+            },
+        }
+    }
+
+    /// The span this diagnostic is primarily about: the span of its first label, or its offset if
+    /// it has no labels. Synthetic spans are skipped, and a diagnostic only about synthetic spans is
+    /// about the start of the file.
+    pub fn primary_span(&self) -> Range<usize> {
+        self.labels
+            .iter()
+            .map(|label| label.span.1.clone())
+            .find(|span| !is_synthetic(span))
+            .unwrap_or_else(|| {
+                let offset = self.file_offset();
+                offset..offset
+            })
+    }
+
+    /// The offset the diagnostic is reported at, or the start of the file if it was built from a
+    /// synthetic span.
+    fn file_offset(&self) -> usize {
+        if self.offset == synthetic_sp().start {
+            0
+        } else {
+            self.offset
+        }
+    }
+
+    pub fn print<C: ariadne::Cache<&'a String>>(&self, cache: C) -> io::Result<()> {
+        let options = emit_options();
+        match options.error_format {
+            ErrorFormat::Human => self.to_ariadne(&options).print(cache),
+            ErrorFormat::Short => {
+                println!("{}", self.to_short(cache)?);
+                Ok(())
+            }
+        }
+    }
+
+    /// Formats the diagnostic as `path:line:col: level[lint-name]: message`, followed by a line
+    /// for each note about a span.
+    fn to_short<C: ariadne::Cache<&'a String>>(&self, mut cache: C) -> io::Result<String> {
+        let source = cache
+            .fetch(&self.path)
+            .map_err(|e| io::Error::other(format!("{e:?}")))?;
+        // Line and column are 0-based, and default to the start of the file.
+        let location = |offset: usize| {
+            // Columns count characters, like in the human format.
+            let (line, col) = source
+                .get_byte_line(offset)
+                .map_or((0, 0), |(text, line, col)| {
+                    let text = source.get_line_text(text).unwrap_or_default();
+                    (line, text[..col.min(text.len())].chars().count())
+                });
+            format!("{}:{}:{}", self.path, line + 1, col + 1)
+        };
+
+        let level = match self.kind {
+            ReportKind::Error => "error",
+            ReportKind::Warning => "warning",
+            ReportKind::Advice => "advice",
+            ReportKind::Custom(name, _) => name,
+        };
+        let lint = self
+            .lint
+            .map(|lint| format!("[{lint}]"))
+            .unwrap_or_default();
+        let message = strip_ansi(self.message.as_deref().unwrap_or_default());
+
+        let mut short = format!(
+            "{}: {level}{lint}: {message}",
+            location(self.primary_span().start)
+        );
+        for note in &self.notes {
+            if let Some(span) = note.file_span() {
+                short.push_str(&format!(
+                    "\n{}: note: {}",
+                    location(span.start),
+                    strip_ansi(&note.message)
+                ));
+            }
+        }
+        Ok(short)
+    }
+
+    fn to_ariadne(&self, options: &EmitOptions) -> ariadne::Report<'static, Span<'a>> {
+        let char_set = if options.ascii {
+            ariadne::CharSet::Ascii
+        } else {
+            ariadne::CharSet::Unicode
+        };
+        // Labels at synthetic spans have nothing to point at, so their messages become notes about
+        // the whole file instead.
+        let (synthetic, labels): (Vec<_>, Vec<_>) = self
+            .labels
+            .iter()
+            .partition(|label| is_synthetic(&label.span.1));
+        // Notes about a span are shown as labels there, the others below the snippet.
+        let (span_notes, notes): (Vec<_>, Vec<_>) = self
+            .notes
+            .iter()
+            .partition(|note| note.file_span().is_some());
+        let notes = synthetic
+            .iter()
+            .filter_map(|label| label.message.as_ref())
+            .chain(notes.iter().map(|note| &note.message))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        let offset = self.file_offset();
+        let has_labels = !labels.is_empty() || !span_notes.is_empty();
+        let mut report = ariadne::Report::build(self.kind, (self.path, offset..offset))
+            // Spans are byte offsets, as the lexer produces them.
+            .with_config(
+                ariadne::Config::default()
+                    .with_char_set(char_set)
+                    .with_index_type(ariadne::IndexType::Byte),
+            )
+            .with_labels(labels.into_iter().map(Label::to_ariadne))
+            .with_labels(span_notes.iter().filter_map(|note| {
+                Some(
+                    ariadne::Label::new((self.path, note.file_span()?)).with_message(&note.message),
+                )
+            }));
+        if !has_labels && (!notes.is_empty() || self.help.is_some() || !self.suggestions.is_empty())
+        {
+            // `ariadne` only renders help and notes for reports with labels.
+            report.add_label(ariadne::Label::new((self.path, offset..offset)));
+        }
+        if let Some(message) = &self.message {
+            report.set_message(message);
+        }
+        for help in self.help.iter() {
+            report.add_help(help);
+        }
+        for suggestion in &self.suggestions {
+            report.add_help(suggestion.message());
+        }
+        for note in notes {
+            report.add_note(note);
+        }
+        report.finish()
+    }
+}
+
+pub struct ReportBuilder<'a> {
+    report: Report<'a>,
+}
+
+impl<'a> ReportBuilder<'a> {
+    pub fn with_message<M: ToString>(mut self, message: M) -> Self {
+        self.report.message = Some(message.to_string());
+        self
+    }
+
+    pub fn with_label(mut self, label: Label<'a>) -> Self {
+        self.add_label(label);
+        self
+    }
+
+    pub fn with_labels<L: IntoIterator<Item = Label<'a>>>(mut self, labels: L) -> Self {
+        self.report.labels.extend(labels);
+        self
+    }
+
+    pub fn with_help<N: ToString>(mut self, help: N) -> Self {
+        self.set_help(help);
+        self
+    }
+
+    pub fn with_note<N: ToString>(mut self, note: N) -> Self {
+        self.add_note(note);
+        self
+    }
+
+    pub fn with_span_note<N: ToString>(mut self, span: Range<usize>, note: N) -> Self {
+        self.add_span_note(span, note);
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.add_suggestion(suggestion);
+        self
+    }
+
+    pub fn add_label(&mut self, label: Label<'a>) {
+        self.report.labels.push(label);
+    }
+
+    pub fn set_help<N: ToString>(&mut self, help: N) {
+        self.report.help = Some(help.to_string());
+    }
+
+    pub fn add_note<N: ToString>(&mut self, note: N) {
+        self.report.notes.push(Note {
+            span: None,
+            message: note.to_string(),
+        });
+    }
+
+    /// Adds a note about another span than the diagnostic's labels, e.g. where a pool is cleared.
+    pub fn add_span_note<N: ToString>(&mut self, span: Range<usize>, note: N) {
+        self.report.notes.push(Note {
+            span: Some(span),
+            message: note.to_string(),
+        });
+    }
+
+    pub fn add_suggestion(&mut self, suggestion: Suggestion) {
+        self.report.suggestions.push(suggestion);
+    }
+
+    pub fn finish(self) -> Report<'a> {
+        self.report
+    }
+}
+
+/// Removes ANSI escape sequences (as produced by [`Fmt::fg`]) from the text.
+pub(crate) fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the escape sequence up to and including its final byte, e.g. `m` in `\x1b[34m`.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
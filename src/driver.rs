@@ -0,0 +1,370 @@
+//! Reading, lowering and linting a Custom Difficulty file, shared by the subcommands.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use tracing::*;
+
+use crate::commands::resolve;
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{self, ReportKind, Source};
+use crate::encoding::EncodingIssue;
+use crate::lint::{LintGroup, LintSelection};
+use crate::parser::Json;
+use crate::plugins::{self, PluginInput};
+use crate::spanned::Spanned;
+use crate::summary::Summary;
+use crate::suppression::Suppressions;
+use crate::symbol::Interner;
+use crate::time_passes::{time, Pass};
+use crate::{crash_report, early_lints, encoding, handlers, late_lints, lint, parser, suppression};
+use crate::{tag_lint, Diagnostics};
+
+/// The name the cyclic reference graph is written under next to the executable, without the
+/// extension.
+const REFERENCE_GRAPH_NAME: &str = "cyclic_enemy_descriptor_references";
+
+/// A Custom Difficulty file, decoded to UTF-8.
+pub struct Input {
+    /// The path as shown in diagnostics.
+    pub path: String,
+    pub src: String,
+    pub(crate) encoding_issue: Option<EncodingIssue>,
+    /// The name the cyclic reference graph of the file is written under, without the extension.
+    pub(crate) reference_graph_name: String,
+}
+
+impl Input {
+    pub fn read(input: &Path) -> anyhow::Result<Self> {
+        debug!(?input);
+        match std::fs::read(input) {
+            Ok(bytes) => Ok(Self::from_bytes(input.display().to_string(), &bytes)),
+            Err(e) => {
+                error!(path = ?input, "failed to read input");
+                Err(e).with_context(|| format!("failed to read file `{}`", input.display()))
+            }
+        }
+    }
+
+    /// The JSON files in the directory and its subdirectories, sorted by path.
+    pub fn read_dir(dir: &Path) -> anyhow::Result<Vec<Self>> {
+        fn collect(dir: &Path, paths: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+            let entries = std::fs::read_dir(dir)
+                .with_context(|| format!("failed to read directory `{}`", dir.display()))?;
+            for entry in entries {
+                let path = entry
+                    .with_context(|| format!("failed to read directory `{}`", dir.display()))?
+                    .path();
+                if path.is_dir() {
+                    collect(&path, paths)?;
+                } else if path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+                {
+                    paths.push(path);
+                }
+            }
+            Ok(())
+        }
+
+        debug!(?dir);
+        let mut paths = Vec::new();
+        collect(dir, &mut paths)?;
+        if paths.is_empty() {
+            bail!("`{}` does not contain any JSON files", dir.display());
+        }
+        paths.sort();
+        paths.iter().map(|path| Self::read(path)).collect()
+    }
+
+    /// A Custom Difficulty which is not read from its own file, e.g. one inside an archive. `path`
+    /// is shown in diagnostics in place of a file path.
+    pub fn from_bytes(path: String, bytes: &[u8]) -> Self {
+        let (src, encoding_issue) = encoding::decode(bytes);
+        Self {
+            path,
+            src,
+            encoding_issue,
+            reference_graph_name: REFERENCE_GRAPH_NAME.to_string(),
+        }
+    }
+
+    /// A Custom Difficulty which is not read from a file, e.g. one made by a subcommand. `path`
+    /// is shown in diagnostics in place of a file path.
+    pub fn new(path: String, src: String) -> Self {
+        Self {
+            path,
+            src,
+            encoding_issue: None,
+            reference_graph_name: REFERENCE_GRAPH_NAME.to_string(),
+        }
+    }
+
+    /// Names the cyclic reference graph of the file after its path, so that the graphs of files
+    /// linted together, e.g. the files in an archive, don't overwrite each other.
+    pub fn name_reference_graph_after_path(&mut self) {
+        let path = self
+            .path
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        self.reference_graph_name = format!("{REFERENCE_GRAPH_NAME}.{path}");
+    }
+
+    /// Prints the diagnostics against this file, collapsing lints which fire many times.
+    pub fn print<'d>(
+        &'d self,
+        diagnostics: impl IntoIterator<Item = &'d crate::DiagnosticReport<'d>>,
+    ) -> anyhow::Result<()> {
+        let diagnostics = diagnostic::collapse_repeated(diagnostics.into_iter().collect());
+        for diagnostic in diagnostics {
+            diagnostic.print((&self.path, Source::from(&self.src)))?;
+        }
+        Ok(())
+    }
+}
+
+/// A Custom Difficulty which was parsed and lowered.
+pub struct Loaded<'d> {
+    pub cd: CustomDifficulty,
+    /// The interner of the names in `cd`, which lints intern the names they compare them with in.
+    pub interner: Interner,
+    /// The top-level members as written, without `__cdlint` members.
+    pub top_level_members: Vec<(Spanned<String>, Spanned<Json>)>,
+    pub suppressions: Suppressions,
+    /// Diagnostics about decoding and parsing the file, and about the suppressions.
+    pub diagnostics: Diagnostics<'d>,
+}
+
+impl Loaded<'_> {
+    /// Whether any diagnostic is an error, i.e. the game would likely not load the file as
+    /// written.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.kind == ReportKind::Error)
+    }
+}
+
+/// What came of parsing and lowering a Custom Difficulty, see [`lower`].
+pub enum Lowering<'d> {
+    Lowered(Box<Loaded<'d>>),
+    /// Nothing can be lowered because the file is empty or not an object. The diagnostics say why.
+    NothingToLower(Diagnostics<'d>),
+    /// Lowering failed. The diagnostics say why, together with any syntax errors which might be
+    /// the reason for it.
+    Failed(Diagnostics<'d>, anyhow::Error),
+}
+
+/// Parses and lowers the Custom Difficulty. Fails if nothing can be lowered because the file is
+/// empty or not an object, after printing why.
+pub fn load<'d>(
+    config: &Config,
+    selection: &LintSelection,
+    input: &'d Input,
+    show_stats: bool,
+) -> anyhow::Result<Loaded<'d>> {
+    match lower(config, selection, input) {
+        Lowering::Lowered(loaded) => Ok(*loaded),
+        Lowering::NothingToLower(diagnostics) => {
+            input.print(&diagnostics)?;
+            Summary::new(&diagnostics).print(show_stats);
+            bail!("`{}` has nothing to lint", input.path);
+        }
+        Lowering::Failed(diagnostics, e) => {
+            input.print(&diagnostics)?;
+            Err(e)
+        }
+    }
+}
+
+/// Parses and lowers the Custom Difficulty like [`load`], but leaves printing the diagnostics to
+/// the caller. Every failure comes with a diagnostic saying why.
+pub fn lower<'d>(config: &Config, selection: &LintSelection, input: &'d Input) -> Lowering<'d> {
+    let _file = crash_report::enter_file(&input.path);
+    let path = &input.path;
+    let json_string = &input.src;
+
+    if let Some(report) = parser::empty_input_report(path, json_string) {
+        return Lowering::NothingToLower(vec![report]);
+    }
+
+    let parsed = time(Pass::Parsing, || parser::parse(config, path, json_string));
+
+    // The parser recovers from syntax errors, so they are reported together with the diagnostics
+    // from linting whatever could be parsed.
+    let mut diagnostics = input
+        .encoding_issue
+        .map(|issue| issue.report(path))
+        .into_iter()
+        .chain(parsed.errors)
+        .chain(
+            parsed
+                .issues
+                .iter()
+                .filter(|issue| {
+                    issue
+                        .lint()
+                        .is_none_or(|lint| selection.is_enabled(lint, LintGroup::Default, config))
+                })
+                .map(|issue| {
+                    let mut report = issue.report(config, path, json_string);
+                    report.lint = issue.lint();
+                    report
+                }),
+        )
+        .collect::<Vec<_>>();
+
+    let Some(mut custom_difficulty_json) = parsed.json else {
+        return Lowering::Failed(
+            diagnostics,
+            anyhow!("failed to parse Custom Difficulty JSON"),
+        );
+    };
+    custom_difficulty_json.val.remove_invalid();
+
+    let (span, mut top_level_members) = match custom_difficulty_json {
+        Spanned {
+            val: Json::Object(Spanned { val, .. }),
+            span,
+        } => (span, val),
+        value => {
+            // Nothing can be linted without an object, but this is a problem with the input rather
+            // than with CDLint, so report it like any other diagnostic.
+            diagnostics.push(handlers::unexpected_top_level_kind(path, &value));
+            return Lowering::NothingToLower(diagnostics);
+        }
+    };
+
+    let mut custom_difficulty = CustomDifficulty::default();
+    let mut interner = Interner::default();
+
+    let (suppressions, lowered) = time(Pass::Lowering, || {
+        let suppressions =
+            suppression::strip_suppressions(&mut top_level_members, path, &mut diagnostics);
+        suppressions.check_lint_names(path, &mut diagnostics);
+
+        let lowered = handlers::handle_top_level_members(
+            &mut diagnostics,
+            path,
+            &mut interner,
+            &mut custom_difficulty,
+            span,
+            &top_level_members,
+        );
+        (suppressions, lowered)
+    });
+    if let Err(e) = lowered {
+        return Lowering::Failed(
+            diagnostics,
+            e.context("trying to process top level members"),
+        );
+    }
+
+    Lowering::Lowered(Box::new(Loaded {
+        cd: custom_difficulty,
+        interner,
+        top_level_members,
+        suppressions,
+        diagnostics,
+    }))
+}
+
+/// Like [`load`], but without running lints, and failing after printing the errors if there are
+/// any. For subcommands which need a Custom Difficulty the game would load.
+pub fn load_without_errors<'d>(config: &Config, input: &'d Input) -> anyhow::Result<Loaded<'d>> {
+    // Lints don't run, so none are selected.
+    let loaded = load(config, &LintSelection::default(), input, false)?;
+    if loaded.has_errors() {
+        input.print(
+            loaded
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.kind == ReportKind::Error),
+        )?;
+        bail!("`{}` has errors", input.path);
+    }
+    Ok(loaded)
+}
+
+/// Runs the lints enabled by the config and the lint selection, adding their diagnostics to
+/// `loaded.diagnostics`.
+pub fn lint<'d>(
+    config: &Config,
+    selection: &LintSelection,
+    input: &'d Input,
+    loaded: &mut Loaded<'d>,
+) -> anyhow::Result<()> {
+    let _file = crash_report::enter_file(&input.path);
+    let path = &input.path;
+    let diagnostics = &mut loaded.diagnostics;
+
+    // There are two kinds of lints:
+    // 1. Early-pass lints: these lints are performed on the spanned CD JSON.
+    // 2. Late-pass lints: these lints are performed on the built CD struct.
+
+    early_lints::run_early_lints(
+        config,
+        selection,
+        &loaded.top_level_members,
+        path,
+        diagnostics,
+    );
+
+    for lint in late_lints::LATE_LINTS
+        .iter()
+        .filter(|lint| lint.is_enabled(config, selection))
+    {
+        debug!(lint = lint.name, "running late lint");
+        let _lint = crash_report::enter_lint(lint.name);
+        let start = diagnostics.len();
+        time(Pass::Lint(lint.name), || {
+            (lint.check)(config, &loaded.cd, path, diagnostics)
+        });
+        tag_lint(&mut diagnostics[start..], lint.name);
+    }
+
+    if selection.is_enabled(
+        lint::CYCLIC_ENEMY_DESCRIPTOR_REFERENCES,
+        LintGroup::Default,
+        config,
+    ) {
+        let _lint = crash_report::enter_lint(lint::CYCLIC_ENEMY_DESCRIPTOR_REFERENCES);
+        let start = diagnostics.len();
+        time(Pass::Lint(lint::CYCLIC_ENEMY_DESCRIPTOR_REFERENCES), || {
+            late_lints::lint_cyclic_enemy_descriptor_references(
+                config,
+                &loaded.cd,
+                &mut loaded.interner,
+                path,
+                &input.reference_graph_name,
+                diagnostics,
+            )
+        })?;
+        tag_lint(
+            &mut diagnostics[start..],
+            lint::CYCLIC_ENEMY_DESCRIPTOR_REFERENCES,
+        );
+    }
+
+    plugins::run_plugins(
+        config,
+        selection,
+        || PluginInput {
+            cd: resolve::resolved_json(&loaded.cd).0,
+            source_map: plugins::source_map(&loaded.top_level_members),
+        },
+        path,
+        &input.src,
+        diagnostics,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::diagnostic::{Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion};
+use crate::early_lints::{EarlyContext, EarlyLintPass};
+use crate::spanned::Spanned;
+
+/// We parse numbers as `f64`, but the game consumes them as `f32`. Numbers which overflow to
+/// infinity when narrowed to `f32` are errors, and numbers which can't be represented exactly
+/// enough to round-trip through `f32` get silently rounded.
+pub struct F32Precision;
+
+impl EarlyLintPass for F32Precision {
+    fn check_num(&self, cx: &mut EarlyContext<'_, '_>, n: &Spanned<f64>) {
+        let path = cx.path;
+        let narrowed = n.val as f32;
+
+        if narrowed.is_infinite() {
+            cx.emit(
+                Report::build(ReportKind::Error, path, n.span.start)
+                    .with_message("number overflows to infinity")
+                    .with_label(
+                        Label::new((path, n.span.into_range()))
+                            .with_color(Color::Red)
+                            .with_message(format!(
+                                "this is outside the range of a 32-bit float, which is ±{}",
+                                format!("{:e}", f32::MAX).fg(Color::Blue)
+                            )),
+                    )
+                    .finish(),
+            );
+            return;
+        }
+
+        // Compare the shortest representation of the narrowed number instead of the narrowed number
+        // itself, otherwise every number that isn't exactly representable in binary (e.g. 0.1) would
+        // be reported.
+        let round_tripped = narrowed.to_string().parse::<f64>().unwrap();
+        if round_tripped == n.val {
+            return;
+        }
+
+        cx.emit(
+            Report::build(ReportKind::Warning, path, n.span.start)
+                .with_message("number loses precision as a 32-bit float")
+                .with_label(
+                    Label::new((path, n.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message(format!(
+                            "the game reads this as {}",
+                            narrowed.fg(Color::Blue)
+                        )),
+                )
+                .with_suggestion(Suggestion::new(
+                    n.span.into_range(),
+                    narrowed,
+                    Applicability::MachineApplicable,
+                ))
+                .finish(),
+        );
+    }
+}
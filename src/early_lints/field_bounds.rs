@@ -0,0 +1,90 @@
+use std::fmt;
+
+/// One end of the valid range of a numeric field.
+#[derive(Debug, Clone, Copy)]
+pub enum Bound {
+    Unbounded,
+    Inclusive(f64),
+    Exclusive(f64),
+}
+
+/// The valid range of a numeric Custom Difficulty field.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldBounds {
+    pub field: &'static str,
+    pub min: Bound,
+    pub max: Bound,
+}
+
+impl FieldBounds {
+    pub fn contains(&self, val: f64) -> bool {
+        let above_min = match self.min {
+            Bound::Unbounded => true,
+            Bound::Inclusive(min) => val >= min,
+            Bound::Exclusive(min) => val > min,
+        };
+        let below_max = match self.max {
+            Bound::Unbounded => true,
+            Bound::Inclusive(max) => val <= max,
+            Bound::Exclusive(max) => val < max,
+        };
+        above_min && below_max
+    }
+}
+
+impl fmt::Display for FieldBounds {
+    /// Formats the bounds in interval notation, e.g. `(0, +inf)` or `[0, 1]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.min {
+            Bound::Unbounded => write!(f, "(-inf")?,
+            Bound::Inclusive(min) => write!(f, "[{min}")?,
+            Bound::Exclusive(min) => write!(f, "({min}")?,
+        }
+        match self.max {
+            Bound::Unbounded => write!(f, ", +inf)"),
+            Bound::Inclusive(max) => write!(f, ", {max}]"),
+            Bound::Exclusive(max) => write!(f, ", {max})"),
+        }
+    }
+}
+
+const fn positive(field: &'static str) -> FieldBounds {
+    FieldBounds {
+        field,
+        min: Bound::Exclusive(0.0),
+        max: Bound::Unbounded,
+    }
+}
+
+/// Top-level fields which take a number or an array of numbers (one per player count).
+pub const TOP_LEVEL_FIELD_BOUNDS: [FieldBounds; 13] = [
+    positive("ResupplyCost"),
+    // Damage resistances divide the damage taken, so a resistance of 0 makes no sense.
+    positive("ExtraLargeEnemyDamageResistance"),
+    positive("ExtraLargeEnemyDamageResistanceB"),
+    positive("ExtraLargeEnemyDamageResistanceC"),
+    positive("ExtraLargeEnemyDamageResistanceD"),
+    positive("EnemyDamageResistance"),
+    positive("SmallEnemyDamageResistance"),
+    positive("SpeedModifier"),
+    positive("AttackCooldownModifier"),
+    positive("ProjectileSpeedModifier"),
+    positive("MinPoolSize"),
+    FieldBounds {
+        field: "HealthRegenerationMax",
+        min: Bound::Inclusive(0.0),
+        max: Bound::Inclusive(1.0),
+    },
+    FieldBounds {
+        field: "ReviveHealthRatio",
+        min: Bound::Exclusive(0.0),
+        max: Bound::Inclusive(1.0),
+    },
+];
+
+/// Fields of an Enemy Descriptor definition.
+pub const ENEMY_DESCRIPTOR_FIELD_BOUNDS: [FieldBounds; 3] = [
+    positive("Scale"),
+    positive("DifficultyRating"),
+    positive("TimeDilation"),
+];
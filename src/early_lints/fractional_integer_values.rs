@@ -0,0 +1,87 @@
+use crate::diagnostic::{Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion};
+use crate::early_lints::{EarlyContext, EarlyLintPass};
+use crate::early_lints::{ENEMY_DESCRIPTOR_INTEGER_FIELDS, TOP_LEVEL_INTEGER_FIELDS};
+use crate::parser::Json;
+use crate::spanned::Spanned;
+
+/// Fields like `MaxActiveEnemies` or `MinSpawnCount` are integers in game, so a value like `60.5`
+/// is silently truncated towards zero to `60`, which is probably not what the user expected.
+pub struct FractionalIntegerValues;
+
+impl EarlyLintPass for FractionalIntegerValues {
+    fn check_top_level_member(
+        &self,
+        cx: &mut EarlyContext<'_, '_>,
+        name: &Spanned<String>,
+        val: &Spanned<Json>,
+    ) {
+        if TOP_LEVEL_INTEGER_FIELDS.contains(&name.as_str()) {
+            check_value(cx, name, val);
+        }
+    }
+
+    fn check_enemy_descriptor_member(
+        &self,
+        cx: &mut EarlyContext<'_, '_>,
+        _ed_name: &Spanned<String>,
+        name: &Spanned<String>,
+        val: &Spanned<Json>,
+    ) {
+        if ENEMY_DESCRIPTOR_INTEGER_FIELDS.contains(&name.as_str()) {
+            check_value(cx, name, val);
+        }
+    }
+}
+
+/// Checks every number in the value, including numbers nested in arrays and objects (e.g. the
+/// `min` and `max` of a range), except for weighted bin `weight`s which are not integers.
+fn check_value(cx: &mut EarlyContext<'_, '_>, field: &str, member_val: &Spanned<Json>) {
+    match &member_val.val {
+        Json::Num(n) => {
+            if n.fract() == 0.0 || !n.is_finite() {
+                return;
+            }
+
+            let path = cx.path;
+            cx.emit(
+                Report::build(ReportKind::Warning, path, n.span.start)
+                    .with_message(format!(
+                        "fractional value {} for integer field \"{}\"",
+                        n.val.fg(Color::Blue),
+                        field.fg(Color::Blue)
+                    ))
+                    .with_label(
+                        Label::new((path, n.span.into_range()))
+                            .with_color(Color::Yellow)
+                            .with_message(format!(
+                                "this is truncated to {}",
+                                n.val.trunc().fg(Color::Blue)
+                            )),
+                    )
+                    .with_note(format!(
+                        "\"{}\" is an integer in game",
+                        field.fg(Color::Blue)
+                    ))
+                    .with_suggestion(Suggestion::new(
+                        n.span.into_range(),
+                        n.val.round(),
+                        Applicability::MaybeIncorrect,
+                    ))
+                    .finish(),
+            );
+        }
+        Json::Array(a) => {
+            for elem in &a.val {
+                check_value(cx, field, elem);
+            }
+        }
+        Json::Object(o) => {
+            for (name, val) in &o.val {
+                if name.val != "weight" {
+                    check_value(cx, field, val);
+                }
+            }
+        }
+        _ => {}
+    }
+}
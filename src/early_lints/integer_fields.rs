@@ -0,0 +1,23 @@
+/// Top-level fields whose numbers are integers in game. For weighted range fields, only the range
+/// `min` and `max` are integers; the bin `weight` is not.
+pub const TOP_LEVEL_INTEGER_FIELDS: [&str; 15] = [
+    "MaxActiveCritters",
+    "MaxActiveSwarmers",
+    "MaxActiveEnemies",
+    "StartingNitra",
+    "EncounterDifficulty",
+    "StationaryDifficulty",
+    "EnemyWaveInterval",
+    "EnemyNormalWaveInterval",
+    "EnemyNormalWaveDifficulty",
+    "EnemyDiversity",
+    "StationaryEnemyDiversity",
+    "DisruptiveEnemyPoolCount",
+    "MinPoolSize",
+    "MaxActiveElites",
+    "EliteCooldown",
+];
+
+/// Fields of an Enemy Descriptor definition whose numbers are integers in game.
+pub const ENEMY_DESCRIPTOR_INTEGER_FIELDS: [&str; 3] =
+    ["IdealSpawnSize", "MinSpawnCount", "MaxSpawnCount"];
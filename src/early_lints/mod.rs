@@ -0,0 +1,189 @@
+use tracing::*;
+
+use crate::config::Config;
+use crate::crash_report;
+use crate::diagnostic::Report;
+use crate::lint::{Lint, LintGroup, LintSelection};
+use crate::parser::Json;
+use crate::spanned::Spanned;
+use crate::time_passes::{time, Pass};
+use crate::Diagnostics;
+
+// Data
+mod field_bounds;
+mod integer_fields;
+
+pub(crate) use field_bounds::{FieldBounds, ENEMY_DESCRIPTOR_FIELD_BOUNDS, TOP_LEVEL_FIELD_BOUNDS};
+pub(crate) use integer_fields::{ENEMY_DESCRIPTOR_INTEGER_FIELDS, TOP_LEVEL_INTEGER_FIELDS};
+
+// Early lints
+mod f32_precision;
+mod fractional_integer_values;
+mod non_functional_members;
+mod out_of_bounds_values;
+mod redundant_self_base;
+
+pub(crate) use f32_precision::*;
+pub(crate) use fractional_integer_values::*;
+pub(crate) use non_functional_members::*;
+pub(crate) use out_of_bounds_values::*;
+pub(crate) use redundant_self_base::*;
+
+/// A lint which checks the spanned JSON of a Custom Difficulty, i.e. what was written rather than
+/// the [`CustomDifficulty`][crate::custom_difficulty::CustomDifficulty] built from it.
+///
+/// [`run_early_lints`] walks the JSON and calls the hooks of each enabled early lint on the way;
+/// hooks which a lint doesn't implement do nothing.
+pub(crate) trait EarlyLintPass {
+    /// Called for each top-level member, in the order they appear in the file.
+    fn check_top_level_member(
+        &self,
+        _cx: &mut EarlyContext<'_, '_>,
+        _name: &Spanned<String>,
+        _val: &Spanned<Json>,
+    ) {
+    }
+
+    /// Called for each Enemy Descriptor definition in `EnemyDescriptors`, before its members.
+    fn check_enemy_descriptor(
+        &self,
+        _cx: &mut EarlyContext<'_, '_>,
+        _ed_name: &Spanned<String>,
+        _members: &[(Spanned<String>, Spanned<Json>)],
+    ) {
+    }
+
+    /// Called for each member of each Enemy Descriptor definition in `EnemyDescriptors`.
+    fn check_enemy_descriptor_member(
+        &self,
+        _cx: &mut EarlyContext<'_, '_>,
+        _ed_name: &Spanned<String>,
+        _name: &Spanned<String>,
+        _val: &Spanned<Json>,
+    ) {
+    }
+
+    /// Called for each number anywhere in the Custom Difficulty.
+    fn check_num(&self, _cx: &mut EarlyContext<'_, '_>, _n: &Spanned<f64>) {}
+}
+
+/// What an early lint has access to while it runs.
+pub(crate) struct EarlyContext<'a, 'd> {
+    pub config: &'a Config,
+    pub path: &'d String,
+    diag: &'a mut Diagnostics<'d>,
+    lint: &'static str,
+}
+
+impl<'d> EarlyContext<'_, 'd> {
+    /// Reports a diagnostic on behalf of the lint which is currently running.
+    pub fn emit(&mut self, mut report: Report<'d>) {
+        report.lint = Some(self.lint);
+        self.diag.push(report);
+    }
+}
+
+pub(crate) type EarlyLint = Lint<&'static (dyn EarlyLintPass + Sync)>;
+
+/// Early lints in the order they are run.
+pub(crate) const EARLY_LINTS: [EarlyLint; 5] = [
+    Lint {
+        name: "out-of-bounds-values",
+        group: LintGroup::Default,
+        check: &OutOfBoundsValues,
+    },
+    Lint {
+        name: "f32-precision",
+        group: LintGroup::Default,
+        check: &F32Precision,
+    },
+    Lint {
+        name: "fractional-integer-values",
+        group: LintGroup::Default,
+        check: &FractionalIntegerValues,
+    },
+    Lint {
+        name: "non-functional-members",
+        group: LintGroup::Default,
+        check: &NonFunctionalMembers,
+    },
+    Lint {
+        name: "redundant-self-base",
+        group: LintGroup::Default,
+        check: &RedundantSelfBase,
+    },
+];
+
+/// Runs the early lints enabled by the config and the lint selection over the top-level members.
+pub(crate) fn run_early_lints<'d>(
+    config: &Config,
+    selection: &LintSelection,
+    top_level_members: &[(Spanned<String>, Spanned<Json>)],
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    for lint in EARLY_LINTS
+        .iter()
+        .filter(|lint| lint.is_enabled(config, selection))
+    {
+        debug!(lint = lint.name, "running early lint");
+        let _lint = crash_report::enter_lint(lint.name);
+        let mut cx = EarlyContext {
+            config,
+            path,
+            diag,
+            lint: lint.name,
+        };
+        time(Pass::Lint(lint.name), || {
+            walk_top_level_members(lint.check, &mut cx, top_level_members)
+        });
+    }
+}
+
+fn walk_top_level_members(
+    pass: &dyn EarlyLintPass,
+    cx: &mut EarlyContext<'_, '_>,
+    top_level_members: &[(Spanned<String>, Spanned<Json>)],
+) {
+    for (name, val) in top_level_members {
+        pass.check_top_level_member(cx, name, val);
+
+        if name.val == "EnemyDescriptors" {
+            if let Json::Object(eds) = &val.val {
+                for (ed_name, ed) in &eds.val {
+                    let Json::Object(ed_members) = &ed.val else {
+                        continue;
+                    };
+                    pass.check_enemy_descriptor(cx, ed_name, &ed_members.val);
+                    for (ed_member_name, ed_member_val) in &ed_members.val {
+                        pass.check_enemy_descriptor_member(
+                            cx,
+                            ed_name,
+                            ed_member_name,
+                            ed_member_val,
+                        );
+                    }
+                }
+            }
+        }
+
+        walk_nums(pass, cx, val);
+    }
+}
+
+fn walk_nums(pass: &dyn EarlyLintPass, cx: &mut EarlyContext<'_, '_>, val: &Spanned<Json>) {
+    match &val.val {
+        Json::Num(n) => pass.check_num(cx, n),
+        Json::Array(a) => {
+            for elem in &a.val {
+                walk_nums(pass, cx, elem);
+            }
+        }
+        Json::Object(o) => {
+            for (_, member_val) in &o.val {
+                walk_nums(pass, cx, member_val);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,52 @@
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::early_lints::{EarlyContext, EarlyLintPass};
+use crate::parser::Json;
+use crate::spanned::Spanned;
+
+/// Top-level members which the game used to read, but now ignores.
+const NON_FUNCTIONAL_TOP_LEVEL_MEMBERS: [&str; 1] = ["SeasonalEvents"];
+
+/// Enemy Descriptor members which the game used to read, but now ignores.
+const NON_FUNCTIONAL_ENEMY_DESCRIPTOR_MEMBERS: [&str; 1] = ["UseSpawnRarityModifiers"];
+
+/// Members which have no effect in game anymore are still accepted, but they make a Custom
+/// Difficulty look like it does something it doesn't.
+pub struct NonFunctionalMembers;
+
+impl EarlyLintPass for NonFunctionalMembers {
+    fn check_top_level_member(
+        &self,
+        cx: &mut EarlyContext<'_, '_>,
+        name: &Spanned<String>,
+        _val: &Spanned<Json>,
+    ) {
+        if NON_FUNCTIONAL_TOP_LEVEL_MEMBERS.contains(&name.as_str()) {
+            check_member(cx, name);
+        }
+    }
+
+    fn check_enemy_descriptor_member(
+        &self,
+        cx: &mut EarlyContext<'_, '_>,
+        _ed_name: &Spanned<String>,
+        name: &Spanned<String>,
+        _val: &Spanned<Json>,
+    ) {
+        if NON_FUNCTIONAL_ENEMY_DESCRIPTOR_MEMBERS.contains(&name.as_str()) {
+            check_member(cx, name);
+        }
+    }
+}
+
+fn check_member(cx: &mut EarlyContext<'_, '_>, name: &Spanned<String>) {
+    let path = cx.path;
+    cx.emit(
+        Report::build(ReportKind::Warning, path, name.span.start)
+            .with_message(format!(
+                "\"{}\" is no longer functional",
+                name.as_str().fg(Color::Blue)
+            ))
+            .with_label(Label::new((path, name.span.into_range())).with_color(Color::Yellow))
+            .finish(),
+    );
+}
@@ -0,0 +1,80 @@
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::early_lints::{EarlyContext, EarlyLintPass};
+use crate::early_lints::{FieldBounds, ENEMY_DESCRIPTOR_FIELD_BOUNDS, TOP_LEVEL_FIELD_BOUNDS};
+use crate::parser::Json;
+use crate::spanned::Spanned;
+
+/// Some numeric fields only make sense within a certain range, e.g. a `Scale` of 0 or a
+/// `ResupplyCost` of 0. The valid range of each field is described by the bounds tables.
+pub struct OutOfBoundsValues;
+
+impl EarlyLintPass for OutOfBoundsValues {
+    fn check_top_level_member(
+        &self,
+        cx: &mut EarlyContext<'_, '_>,
+        name: &Spanned<String>,
+        val: &Spanned<Json>,
+    ) {
+        check_member(cx, &TOP_LEVEL_FIELD_BOUNDS, name, val);
+    }
+
+    fn check_enemy_descriptor_member(
+        &self,
+        cx: &mut EarlyContext<'_, '_>,
+        _ed_name: &Spanned<String>,
+        name: &Spanned<String>,
+        val: &Spanned<Json>,
+    ) {
+        check_member(cx, &ENEMY_DESCRIPTOR_FIELD_BOUNDS, name, val);
+    }
+}
+
+fn check_member(
+    cx: &mut EarlyContext<'_, '_>,
+    bounds_table: &[FieldBounds],
+    member_name: &Spanned<String>,
+    member_val: &Spanned<Json>,
+) {
+    let Some(bounds) = bounds_table.iter().find(|b| b.field == member_name.val) else {
+        return;
+    };
+
+    let mut check_num = |n: &Spanned<f64>| {
+        // Negative and non-finite values are already reported when the field is lowered.
+        if bounds.contains(n.val) || n.is_sign_negative() || !n.is_finite() {
+            return;
+        }
+
+        let path = cx.path;
+        cx.emit(
+            Report::build(ReportKind::Error, path, n.span.start)
+                .with_message(format!(
+                    "value {} of \"{}\" is out of bounds",
+                    n.val.fg(Color::Blue),
+                    bounds.field.fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, n.span.into_range()))
+                        .with_color(Color::Red)
+                        .with_message(format!(
+                            "\"{}\" must be in the range {}",
+                            bounds.field.fg(Color::Blue),
+                            bounds.fg(Color::Blue)
+                        )),
+                )
+                .finish(),
+        );
+    };
+
+    match &member_val.val {
+        Json::Num(n) => check_num(n),
+        Json::Array(a) => {
+            for elem in &a.val {
+                if let Json::Num(n) = &elem.val {
+                    check_num(n);
+                }
+            }
+        }
+        _ => {}
+    }
+}
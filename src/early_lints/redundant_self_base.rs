@@ -0,0 +1,81 @@
+use std::ops::Range;
+
+use crate::diagnostic::{Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion};
+use crate::early_lints::{EarlyContext, EarlyLintPass};
+use crate::parser::Json;
+use crate::spanned::Spanned;
+
+/// `"ED_Spider_Grunt": { "Base": "ED_Spider_Grunt", ... }` is a common copy-paste pattern when
+/// modifying a vanilla Enemy Descriptor. Any field not specified already keeps its vanilla value,
+/// so basing the vanilla Enemy Descriptor on itself does nothing.
+pub struct RedundantSelfBase;
+
+impl EarlyLintPass for RedundantSelfBase {
+    fn check_enemy_descriptor(
+        &self,
+        cx: &mut EarlyContext<'_, '_>,
+        ed_name: &Spanned<String>,
+        members: &[(Spanned<String>, Spanned<Json>)],
+    ) {
+        if !cx
+            .config
+            .game_data()
+            .is_vanilla_enemy_descriptor(&ed_name.val)
+        {
+            return;
+        }
+
+        for (i, (name, val)) in members.iter().enumerate() {
+            let Json::Str(base) = &val.val else {
+                continue;
+            };
+            if name.val != "Base" || base.val != ed_name.val {
+                continue;
+            }
+
+            let path = cx.path;
+            cx.emit(
+                Report::build(ReportKind::Advice, path, val.span.start)
+                    .with_message(format!(
+                        "vanilla Enemy Descriptor \"{}\" is based on itself",
+                        ed_name.as_str().fg(Color::Blue)
+                    ))
+                    .with_label(
+                        Label::new((path, val.span.into_range()))
+                            .with_color(Color::Cyan)
+                            .with_message(format!(
+                                "this \"{}\" is redundant",
+                                "Base".fg(Color::Blue)
+                            )),
+                    )
+                    .with_note(format!(
+                        "fields not specified for \"{}\" already keep their vanilla values",
+                        ed_name.as_str().fg(Color::Blue)
+                    ))
+                    .with_suggestion(Suggestion::remove(
+                        member_removal_span(members, i),
+                        format!(
+                            "\"{}\": \"{}\"",
+                            "Base".fg(Color::Blue),
+                            base.as_str().fg(Color::Blue)
+                        ),
+                        Applicability::MachineApplicable,
+                    ))
+                    .finish(),
+            );
+        }
+    }
+}
+
+/// The span to remove to remove the `i`th member together with the `,` separating it from its
+/// neighbours.
+fn member_removal_span(members: &[(Spanned<String>, Spanned<Json>)], i: usize) -> Range<usize> {
+    let (name, val) = &members[i];
+    if let Some((next_name, _)) = members.get(i + 1) {
+        name.span.start..next_name.span.start
+    } else if let Some((_, prev_val)) = i.checked_sub(1).map(|prev| &members[prev]) {
+        prev_val.span.end..val.span.end
+    } else {
+        name.span.start..val.span.end
+    }
+}
@@ -1,10 +1,57 @@
-//! Taken verbatim from
+//! Suggestions for misspelled names, see [`suggest_similar`].
+//!
+//! Everything else is taken verbatim from
 //! <https://doc.rust-lang.org/nightly/nightly-rustc/src/rustc_span/edit_distance.rs.html#1-288>.
 #![allow(dead_code)]
 
 use std::cmp;
 use std::mem;
 
+/// Prefixes of the game's names, e.g. `ED_Spider_Grunt`, `PST_MovementSpeed` and `SE_RivalBomb`,
+/// which are often left out or written in a different case.
+const NAME_PREFIXES: &[&str] = &["ED_", "PST_", "SE_"];
+
+/// Suggests which of the candidates an unknown name was meant to be, for "did you mean"
+/// suggestions:
+///
+/// - names are compared case-insensitively, so `descriptin` suggests `Description`;
+/// - [`NAME_PREFIXES`] are ignored, so `Spider_Grunt` suggests `ED_Spider_Grunt`;
+/// - the closest candidate is only suggested if it is within a third of the name's length, so
+///   that short names don't get unrelated suggestions;
+/// - otherwise a candidate with the same `_`-separated words in a different order is suggested.
+pub fn suggest_similar<'c>(candidates: &[&'c str], input: &str) -> Option<&'c str> {
+    let normalize = |name: &str| {
+        let name = NAME_PREFIXES
+            .iter()
+            .find_map(|prefix| {
+                let start = name.get(..prefix.len())?;
+                start
+                    .eq_ignore_ascii_case(prefix)
+                    .then(|| &name[prefix.len()..])
+            })
+            .unwrap_or(name);
+        name.to_lowercase()
+    };
+
+    let input = normalize(input);
+    let limit = cmp::max(input.chars().count(), 3) / 3;
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let distance = edit_distance(&input, &normalize(candidate), limit)?;
+            Some((distance, *candidate))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+        .or_else(|| {
+            let words = sort_by_words(&input);
+            candidates
+                .iter()
+                .find(|candidate| sort_by_words(&normalize(candidate)) == words)
+                .copied()
+        })
+}
+
 /// Finds the [edit distance] between two strings.
 ///
 /// Returns `None` if the distance exceeds the limit.
@@ -26,16 +73,18 @@ pub fn edit_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
     }
 
     // Strip common prefix.
-    while let Some(((b_char, b_rest), (a_char, a_rest))) = b.split_first().zip(a.split_first())
-        && a_char == b_char
-    {
+    while let Some(((b_char, b_rest), (a_char, a_rest))) = b.split_first().zip(a.split_first()) {
+        if a_char != b_char {
+            break;
+        }
         a = a_rest;
         b = b_rest;
     }
     // Strip common suffix.
-    while let Some(((b_char, b_rest), (a_char, a_rest))) = b.split_last().zip(a.split_last())
-        && a_char == b_char
-    {
+    while let Some(((b_char, b_rest), (a_char, a_rest))) = b.split_last().zip(a.split_last()) {
+        if a_char != b_char {
+            break;
+        }
         a = a_rest;
         b = b_rest;
     }
@@ -107,7 +156,7 @@ pub fn edit_distance_with_substrings(a: &str, b: &str, limit: usize) -> Option<u
     // Check one isn't less than half the length of the other. If this is true then there is a
     // big difference in length.
     let big_len_diff = (n * 2) < m || (m * 2) < n;
-    let len_diff = if n < m { m - n } else { n - m };
+    let len_diff = m.abs_diff(n);
     let distance = edit_distance(a, b, limit + len_diff)?;
 
     // This is the crux, subtracting length difference means exact substring matches will now be 0
@@ -119,7 +168,7 @@ pub fn edit_distance_with_substrings(a: &str, b: &str, limit: usize) -> Option<u
         1 // Exact substring match, but not a total word match so return non-zero
     } else if !big_len_diff {
         // Not a big difference in length, discount cost of length difference
-        score + (len_diff + 1) / 2
+        score + len_diff.div_ceil(2)
     } else {
         // A big difference in length, add back the difference in length to the score
         score + len_diff
@@ -0,0 +1,108 @@
+//! Decoding the input file, which is expected to be UTF-8 but is often exported by Windows editors
+//! with a byte-order mark or as UTF-16.
+
+use crate::diagnostic::{Color, Label, Report, ReportKind};
+use crate::DiagnosticReport;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingIssue {
+    /// The file was UTF-16 encoded, and was transcoded to UTF-8.
+    Utf16 { big_endian: bool },
+    /// The file is not valid UTF-8. Invalid sequences were replaced by `U+FFFD`, the first of
+    /// which is at `offset` in the decoded text.
+    InvalidUtf8 { offset: usize },
+}
+
+impl EncodingIssue {
+    pub fn report<'d>(&self, path: &'d String) -> DiagnosticReport<'d> {
+        match self {
+            EncodingIssue::Utf16 { big_endian } => Report::build(ReportKind::Advice, path, 0)
+                .with_message(format!(
+                    "file is UTF-16{} encoded",
+                    if *big_endian { "BE" } else { "LE" }
+                ))
+                .with_note(
+                    "the file was converted to UTF-8 before linting, so the source shown in \
+                     diagnostics is the converted text",
+                )
+                .finish(),
+            EncodingIssue::InvalidUtf8 { offset } => {
+                Report::build(ReportKind::Error, path, *offset)
+                    .with_message("file is not valid UTF-8")
+                    .with_label(
+                        Label::new((
+                            path,
+                            *offset..*offset + char::REPLACEMENT_CHARACTER.len_utf8(),
+                        ))
+                        .with_color(Color::Red)
+                        .with_message("invalid UTF-8 starts here"),
+                    )
+                    .with_help("save the file with UTF-8 encoding")
+                    .finish()
+            }
+        }
+    }
+}
+
+/// Decodes the file contents, stripping a byte-order mark if present.
+pub fn decode(bytes: &[u8]) -> (String, Option<EncodingIssue>) {
+    if let Some(bytes) = bytes.strip_prefix(UTF8_BOM) {
+        return decode_utf8(bytes);
+    }
+    if let Some(bytes) = bytes.strip_prefix(UTF16_LE_BOM) {
+        return (
+            decode_utf16(bytes, u16::from_le_bytes),
+            Some(EncodingIssue::Utf16 { big_endian: false }),
+        );
+    }
+    if let Some(bytes) = bytes.strip_prefix(UTF16_BE_BOM) {
+        return (
+            decode_utf16(bytes, u16::from_be_bytes),
+            Some(EncodingIssue::Utf16 { big_endian: true }),
+        );
+    }
+    // UTF-16 without a byte-order mark: a Custom Difficulty starts with an ASCII character, so one
+    // of its first two bytes is zero.
+    match bytes {
+        [b, 0, ..] if b.is_ascii() && *b != 0 => (
+            decode_utf16(bytes, u16::from_le_bytes),
+            Some(EncodingIssue::Utf16 { big_endian: false }),
+        ),
+        [0, b, ..] if b.is_ascii() && *b != 0 => (
+            decode_utf16(bytes, u16::from_be_bytes),
+            Some(EncodingIssue::Utf16 { big_endian: true }),
+        ),
+        _ => decode_utf8(bytes),
+    }
+}
+
+fn decode_utf8(bytes: &[u8]) -> (String, Option<EncodingIssue>) {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => (s.to_string(), None),
+        Err(e) => (
+            String::from_utf8_lossy(bytes).into_owned(),
+            Some(EncodingIssue::InvalidUtf8 {
+                offset: e.valid_up_to(),
+            }),
+        ),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let pairs = bytes.chunks_exact(2);
+    // A byte left over after the last pair is half a code unit, which is replaced like an unpaired
+    // surrogate rather than dropped.
+    let odd_byte = !pairs.remainder().is_empty();
+    let units = pairs.map(|pair| from_bytes([pair[0], pair[1]]));
+    let mut decoded = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect::<String>();
+    if odd_byte {
+        decoded.push(char::REPLACEMENT_CHARACTER);
+    }
+    decoded
+}
@@ -0,0 +1,186 @@
+//! Vanilla game data which changes between game seasons, e.g. which Enemy Descriptors exist. Each
+//! season the data is known for has its own data pack, and lints use the one selected by the
+//! `game_version` config or `--game-version`.
+//!
+//! Besides the data packs built into CDLint, data packs downloaded by `cdlint data update` are
+//! loaded from the [`DOWNLOAD_DIR`] next to the executable, so that a new game version can be
+//! linted against before the next CDLint release.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use tracing::*;
+
+mod season05;
+
+/// The data packs built into CDLint, oldest first.
+pub const BUILT_IN: [&GameData; 1] = [&season05::SEASON_05];
+
+/// Selects the newest data pack, built in or downloaded.
+pub const LATEST: &str = "latest";
+
+/// The directory next to the executable which downloaded data packs are kept in, one
+/// `<version>.json` file per game version.
+pub const DOWNLOAD_DIR: &str = "game-data";
+
+static DOWNLOADED: OnceLock<Vec<&'static GameData>> = OnceLock::new();
+
+/// The vanilla game data of one game version.
+#[derive(Debug)]
+pub struct GameData {
+    /// The name the data pack is selected by, e.g. `season05`.
+    pub version: &'static str,
+    pub enemy_descriptors: &'static [&'static str],
+    /// Vanilla Enemy Descriptors which don't move around once spawned, and are expected to be
+    /// spawned from the stationary enemy pool.
+    pub stationary_enemy_descriptors: &'static [&'static str],
+    pub seasonal_events: &'static [&'static str],
+    pub pawn_stats: &'static [&'static str],
+    /// Pawn stats which can only affect some enemies, if any. Pawn stats not listed here may affect
+    /// any enemy.
+    pub pawn_stat_targets: &'static [(&'static str, PawnStatTarget)],
+}
+
+impl GameData {
+    /// The data pack of the game version, e.g. `season05`, or the newest one for [`LATEST`].
+    pub fn for_version(version: &str) -> Option<&'static GameData> {
+        let available = available();
+        if version == LATEST {
+            return available.last().copied();
+        }
+        available
+            .into_iter()
+            .find(|game_data| game_data.version == version)
+    }
+
+    pub fn is_vanilla_enemy_descriptor(&self, name: &str) -> bool {
+        self.enemy_descriptors.contains(&name)
+    }
+
+    /// Parses a data pack as written by `cdlint data update`. The data pack lives for the rest of
+    /// the program, like the built in ones.
+    pub fn from_json(json: &str) -> anyhow::Result<&'static GameData> {
+        let pack = serde_json::from_str::<GameDataPack>(json).context("invalid game data pack")?;
+        if !is_valid_version(&pack.version) {
+            bail!(
+                "invalid game version `{}` in game data pack, expected only letters, digits, `_` \
+                 and `-`",
+                pack.version
+            );
+        }
+        if pack.enemy_descriptors.is_empty() {
+            bail!("game data pack `{}` has no Enemy Descriptors", pack.version);
+        }
+
+        fn leak(strings: Vec<String>) -> &'static [&'static str] {
+            strings
+                .into_iter()
+                .map(|s| &*s.leak())
+                .collect::<Vec<_>>()
+                .leak()
+        }
+        Ok(Box::leak(Box::new(GameData {
+            version: pack.version.leak(),
+            enemy_descriptors: leak(pack.enemy_descriptors),
+            stationary_enemy_descriptors: leak(pack.stationary_enemy_descriptors),
+            seasonal_events: leak(pack.seasonal_events),
+            pawn_stats: leak(pack.pawn_stats),
+            pawn_stat_targets: pack
+                .pawn_stat_targets
+                .into_iter()
+                .map(|(stat, target)| (&*stat.leak(), target))
+                .collect::<Vec<_>>()
+                .leak(),
+        })))
+    }
+
+    /// Whether the data pack was downloaded rather than built into CDLint.
+    pub fn is_downloaded(&self) -> bool {
+        DOWNLOADED
+            .get()
+            .is_some_and(|downloaded| downloaded.iter().any(|d| std::ptr::eq(*d, self)))
+    }
+}
+
+/// Which enemies a pawn stat can have an effect on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PawnStatTarget {
+    /// The pawn stat is only used by dwarves (e.g. mining, carrying, resupplying), and has no
+    /// effect on any enemy.
+    PlayersOnly,
+    /// The pawn stat only affects enemies which move around, and has no effect on stationary
+    /// enemies (see [`GameData::stationary_enemy_descriptors`]).
+    MobileEnemies,
+}
+
+/// A data pack as it is published and downloaded.
+#[derive(Debug, Deserialize)]
+struct GameDataPack {
+    version: String,
+    enemy_descriptors: Vec<String>,
+    #[serde(default)]
+    stationary_enemy_descriptors: Vec<String>,
+    #[serde(default)]
+    seasonal_events: Vec<String>,
+    #[serde(default)]
+    pawn_stats: Vec<String>,
+    #[serde(default)]
+    pawn_stat_targets: IndexMap<String, PawnStatTarget>,
+}
+
+/// Game versions are used as file names, so they are restricted to a safe set of characters.
+pub fn is_valid_version(version: &str) -> bool {
+    !version.is_empty()
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Loads the downloaded data packs from `dir`. Data packs which can't be loaded are skipped with a
+/// warning, so that a broken download doesn't stop linting against the built in data packs.
+pub fn load_downloaded(dir: &Path) {
+    let mut downloaded = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            debug!(?path, "loading downloaded game data");
+            let game_data = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| GameData::from_json(&json));
+            match game_data {
+                Ok(game_data) => downloaded.push(game_data),
+                Err(e) => warn!("skipping game data `{}`: {:#}", path.display(), e),
+            }
+        }
+    }
+    downloaded.sort_by_key(|game_data| game_data.version);
+    let _ = DOWNLOADED.set(downloaded);
+}
+
+/// The data packs which can be selected, oldest first. A downloaded data pack replaces the built
+/// in data pack of the same game version, and newer game versions are only available downloaded.
+pub fn available() -> Vec<&'static GameData> {
+    let downloaded = DOWNLOADED.get().map(Vec::as_slice).unwrap_or_default();
+    let mut available = BUILT_IN
+        .iter()
+        .map(|&built_in| {
+            downloaded
+                .iter()
+                .copied()
+                .find(|d| d.version == built_in.version)
+                .unwrap_or(built_in)
+        })
+        .collect::<Vec<_>>();
+    available.extend(downloaded.iter().filter(|d| {
+        BUILT_IN
+            .iter()
+            .all(|built_in| built_in.version != d.version)
+    }));
+    available
+}
@@ -0,0 +1,211 @@
+//! The game data of Season 5.
+
+use super::{GameData, PawnStatTarget};
+
+pub const SEASON_05: GameData = GameData {
+    version: "season05",
+    enemy_descriptors: &ENEMY_DESCRIPTORS,
+    stationary_enemy_descriptors: &STATIONARY_ENEMY_DESCRIPTORS,
+    seasonal_events: &SEASONAL_EVENTS,
+    pawn_stats: &PAWN_STATS,
+    pawn_stat_targets: &PAWN_STAT_TARGETS,
+};
+
+const ENEMY_DESCRIPTORS: [&str; 87] = [
+    "ED_Bomber",
+    "ED_Bomber_Explosive",
+    "ED_Bomber_Ice",
+    "ED_Bomber_Rockpox_Plague",
+    "ED_Butterfly",
+    "ED_CaveLeech",
+    "ED_EggSpider",
+    "ED_FacilityTurret_Barrier",
+    "ED_FacilityTurret_Burst",
+    "ED_FacilityTurret_Sniper",
+    "ED_Flea",
+    "ED_FlyingSmartRock",
+    "ED_Grabber",
+    "ED_GreatEggHunt_SpringBunny",
+    "ED_HydraWeed",
+    "ED_InfectedMule",
+    "ED_InfestationLarva",
+    "ED_InsectSwarm_Spawner",
+    "ED_Jelly_Spawn",
+    "ED_Jelly_Swarmer",
+    "ED_JellyBreeder",
+    "ED_JellyBreeder_RockpoxPlague",
+    "ED_Mactera_Shooter_Amber",
+    "ED_Mactera_Shooter_HeavyVeteran",
+    "ED_Mactera_Shooter_Normal",
+    "ED_Mactera_TripleShooter",
+    "ED_Nisse",
+    "ED_PatrolBot",
+    "ED_PatrolBot_Caretaker",
+    "ED_PlagueLarva",
+    "ED_Prospector",
+    "ED_PumpkinLootBug",
+    "ED_Shark",
+    "ED_ShootingPlant",
+    "ED_Shredder",
+    "ED_Spider_Alarm",
+    "ED_Spider_Amber_Shooter",
+    "ED_Spider_Boss_Heavy",
+    "ED_Spider_Boss_TwinA",
+    "ED_Spider_Boss_TwinB",
+    "ED_Spider_Buffer",
+    "ED_Spider_Charger",
+    "ED_Spider_Drone",
+    "ED_Spider_Exploder",
+    "ED_Spider_Exploder_Rockpox_Plague",
+    "ED_Spider_Exploder_Warning",
+    "ED_Spider_ExploderTank",
+    "ED_Spider_ExploderTank_King",
+    "ED_Spider_ExploderTankGhost",
+    "ED_Spider_Grunt",
+    "ED_Spider_Grunt_Attacker",
+    "ED_Spider_Grunt_Guard",
+    "ED_Spider_Grunt_Ice",
+    "ED_Spider_Grunt_Mutated",
+    "ED_Spider_Grunt_Rock",
+    "ED_Spider_Grunt_RockpoxPlague",
+    "ED_Spider_GruntTutorial",
+    "ED_Spider_Hoarder",
+    "ED_Spider_Lobber",
+    "ED_Spider_RapidShooter",
+    "ED_Spider_ShieldTank",
+    "ED_Spider_Shooter",
+    "ED_Spider_Shooter_Ground",
+    "ED_Spider_Shooter_Rockpox_Plague",
+    "ED_Spider_ShooterQueen",
+    "ED_Spider_Spawn",
+    "ED_Spider_Spitter",
+    "ED_Spider_Stinger",
+    "ED_Spider_Swarmer",
+    "ED_Spider_Swarmer_Ice",
+    "ED_Spider_Swarmer_Mutated",
+    "ED_Spider_Swarmer_Pheromone_NOFX",
+    "ED_Spider_Tank",
+    "ED_Spider_Tank_Amber",
+    "ED_Spider_Tank_Boss",
+    "ED_Spider_Tank_HeavySpawn",
+    "ED_Spider_Tank_Ice",
+    "ED_Spider_Tank_Mutated",
+    "ED_Spider_Tank_Rock",
+    "ED_Spider_Tank_RockpoxPlague",
+    "ED_SpiderSpawner",
+    "ED_Terminator",
+    "ED_TunnelSwarmer",
+    "ED_WalkingPlagueheart",
+    "ED_Woodlouse",
+    "ED_Woodlouse_Youngling",
+    "ED_Spider_Stalker",
+];
+
+const STATIONARY_ENEMY_DESCRIPTORS: [&str; 9] = [
+    "ED_CaveLeech",
+    "ED_FacilityTurret_Barrier",
+    "ED_FacilityTurret_Burst",
+    "ED_FacilityTurret_Sniper",
+    "ED_HydraWeed",
+    "ED_JellyBreeder",
+    "ED_JellyBreeder_RockpoxPlague",
+    "ED_ShootingPlant",
+    "ED_SpiderSpawner",
+];
+
+const SEASONAL_EVENTS: [&str; 3] = [
+    "SE_ProspectorDrone",
+    "SE_ProspectorDataDeposit",
+    "SE_RivalBomb",
+];
+
+const PAWN_STATS: [&str; 49] = [
+    "PST_BarrelKicking",
+    "PST_CarriableThrowing",
+    "PST_CarryingCapacity",
+    "PST_CarryingSpeedModifier",
+    "PST_CaveLeechSense",
+    "PST_ColdResistance",
+    "PST_CorrosiveResistance",
+    "PST_DamageBonus",
+    "PST_DamageFromPlayers",
+    "PST_DamageResistance",
+    "PST_DepositSpeed",
+    "PST_DirtMiningStrength",
+    "PST_ElectricResistance",
+    "PST_EventExplosionResistance",
+    "PST_ExplodeOnDeath",
+    "PST_ExplosionResistance",
+    "PST_FallingResistance",
+    "PST_FireResistance",
+    "PST_FlareThrowStrength",
+    "PST_FriendlyFire",
+    "PST_GoldMining",
+    "PST_HoverBootsDuration",
+    "PST_InternalDamageResistance",
+    "PST_KineticResistance",
+    "PST_MaxHealth",
+    "PST_MaxShield",
+    "PST_MeleeDamage",
+    "PST_MorkiteMining",
+    "PST_MovementSpeed",
+    "PST_MovementSpeedEnvironmentalPenalty",
+    "PST_MovementSpeedEnvironmentalPenaltyReduction",
+    "PST_MovementSpeedPenalty",
+    "PST_MovementSpeedPenaltyReduction",
+    "PST_PhysicalResistance",
+    "PST_PoisonResistance",
+    "PST_PowerAttackCooldownRate",
+    "PST_RadiationResistance",
+    "PST_RedSugarHeal",
+    "PST_ResourceMiningStrength",
+    "PST_ResupplyHealing",
+    "PST_ResupplySpeed",
+    "PST_ReviveSpeed",
+    "PST_RockMiningStrength",
+    "PST_ShieldRegeneratoinRate",
+    "PST_SlipperyFloor",
+    "PST_SprintSpeed",
+    "PST_Ziplline_DownBoost",
+    "PST_ZipllineSpee",
+    "PST_PiercingResistance",
+];
+
+const PAWN_STAT_TARGETS: [(&str, PawnStatTarget); 27] = [
+    ("PST_BarrelKicking", PawnStatTarget::PlayersOnly),
+    ("PST_CarriableThrowing", PawnStatTarget::PlayersOnly),
+    ("PST_CarryingCapacity", PawnStatTarget::PlayersOnly),
+    ("PST_CarryingSpeedModifier", PawnStatTarget::PlayersOnly),
+    ("PST_CaveLeechSense", PawnStatTarget::PlayersOnly),
+    ("PST_DepositSpeed", PawnStatTarget::PlayersOnly),
+    ("PST_DirtMiningStrength", PawnStatTarget::PlayersOnly),
+    ("PST_FlareThrowStrength", PawnStatTarget::PlayersOnly),
+    ("PST_GoldMining", PawnStatTarget::PlayersOnly),
+    ("PST_HoverBootsDuration", PawnStatTarget::PlayersOnly),
+    ("PST_MorkiteMining", PawnStatTarget::PlayersOnly),
+    ("PST_PowerAttackCooldownRate", PawnStatTarget::PlayersOnly),
+    ("PST_RedSugarHeal", PawnStatTarget::PlayersOnly),
+    ("PST_ResourceMiningStrength", PawnStatTarget::PlayersOnly),
+    ("PST_ResupplyHealing", PawnStatTarget::PlayersOnly),
+    ("PST_ResupplySpeed", PawnStatTarget::PlayersOnly),
+    ("PST_ReviveSpeed", PawnStatTarget::PlayersOnly),
+    ("PST_RockMiningStrength", PawnStatTarget::PlayersOnly),
+    ("PST_SprintSpeed", PawnStatTarget::PlayersOnly),
+    ("PST_Ziplline_DownBoost", PawnStatTarget::PlayersOnly),
+    ("PST_ZipllineSpee", PawnStatTarget::PlayersOnly),
+    ("PST_MovementSpeed", PawnStatTarget::MobileEnemies),
+    (
+        "PST_MovementSpeedEnvironmentalPenalty",
+        PawnStatTarget::MobileEnemies,
+    ),
+    (
+        "PST_MovementSpeedEnvironmentalPenaltyReduction",
+        PawnStatTarget::MobileEnemies,
+    ),
+    ("PST_MovementSpeedPenalty", PawnStatTarget::MobileEnemies),
+    (
+        "PST_MovementSpeedPenaltyReduction",
+        PawnStatTarget::MobileEnemies,
+    ),
+    ("PST_SlipperyFloor", PawnStatTarget::MobileEnemies),
+];
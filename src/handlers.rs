@@ -1,2243 +1,406 @@
-#![allow(clippy::too_many_arguments)]
+//! Lowering of the spanned JSON of a Custom Difficulty into a [`CustomDifficulty`]. Lowering of
+//! the structs in `custom_difficulty` is generated by `#[derive(FromSpannedJson)]`, which relies on
+//! the impls for the types of their fields here.
 
-use std::any::Any;
-use std::collections::BTreeSet;
-
-use anyhow::bail;
-use ariadne::{Color, Fmt, Label, Report, ReportKind, Source};
+use anyhow::{anyhow, bail};
 use chumsky::span::SimpleSpan;
 use indexmap::IndexMap;
-use tracing::*;
 
-use crate::custom_difficulty::{
-    ArrayOrSingleItem, CustomDifficulty, EnemyDescriptor, EnemyPool, EscortMule, PawnStats, Range,
-    WeightedRange,
+use crate::custom_difficulty::{ArrayOrSingleItem, CustomDifficulty, PawnStats};
+use crate::diagnostic::{
+    string_literal, Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion,
 };
 use crate::parser::Json;
 use crate::spanned::Spanned;
-use crate::{dummy_sp, edit_distance, DiagnosticReport, Diagnostics, ValidationResult};
+use crate::symbol::{Interner, Symbol};
+use crate::{edit_distance, DiagnosticReport, Diagnostics};
+
+/// A value which can be lowered from the spanned JSON of a Custom Difficulty.
+pub(crate) trait FromSpannedJson: Sized {
+    /// The JSON kind the value is lowered from, as described in diagnostics.
+    const EXPECTED: &'static str;
+
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self>;
+}
 
-const SUGGESTION_MAX_EDIT_DISTANCE: usize = 7;
+/// A struct which is lowered from the members of a JSON object. Implemented by
+/// `#[derive(FromSpannedJson)]`.
+pub(crate) trait FromSpannedMembers: Sized {
+    /// The names of the members which are lowered into fields.
+    const MEMBERS: &'static [&'static str];
+
+    /// `span` is the span of the object, which diagnostics about absent members point at.
+    fn from_members(
+        cx: &mut LoweringContext<'_, '_>,
+        span: SimpleSpan,
+        members: &[(Spanned<String>, Spanned<Json>)],
+    ) -> anyhow::Result<Self>;
+}
 
-fn handle_str<'d>(
-    _diag: &mut Diagnostics<'d>,
-    path: &'d String,
-    src: &str,
-    target: &mut Spanned<String>,
-    member_val: &Spanned<Json>,
-    member_name: &str,
-) -> anyhow::Result<()> {
-    *target = if let Json::Str(s) = &member_val.val {
-        s.to_owned()
-    } else {
-        unexpected_value_kind(path, member_val, "string").print((path, Source::from(src)))?;
-        bail!("unexpected JSON kind found in \"{member_name}\" member value");
-    };
-    Ok(())
+/// A member which is not a member of the object it is found in.
+pub(crate) struct UnknownMember {
+    pub name: Spanned<String>,
+    /// The members of the object.
+    pub expected: &'static [&'static str],
 }
 
-#[instrument(skip(diag, path, src, expected_ty, target, validate))]
-fn handle_single_item_or_array_number<'d, 'a, 'n, T: 'static>(
-    diag: &mut Diagnostics<'d>,
-    path: &'d String,
-    src: &'a str,
-    expected_ty: &'static str,
-    target: &mut Spanned<ArrayOrSingleItem<T>>,
-    member_val: &Spanned<Json>,
-    member_name: &'n str,
-    validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, T>,
-) -> anyhow::Result<()> {
-    match &member_val.val {
-        Json::Array(a) => {
-            let mut arr = Vec::new();
-            for elem in &a.val {
-                trace!("elem.val = {:?}", &elem.val);
-                match &elem.val {
-                    Json::Num(a) => match validate(Box::new(a.val.to_owned()), a.span) {
-                        ValidationResult::Ok(val) => {
-                            arr.push(val);
-                        }
-                        ValidationResult::Err(report) => diag.push(report),
-                    },
-                    Json::Str(s) => match validate(Box::new(s.val.to_owned()), a.span) {
-                        ValidationResult::Ok(val) => {
-                            arr.push(val);
-                        }
-                        ValidationResult::Err(report) => diag.push(report),
-                    },
-                    Json::Object(_obj) => {
-                        unimplemented!()
-                    }
-                    _ => {
-                        unexpected_value_kind(path, member_val, expected_ty)
-                            .print((path, Source::from(src)))?;
-                        bail!("unexpected JSON kind {} found in \"{member_name}\" member value; expected {expected_ty}", elem.val.kind_desc());
-                    }
-                }
-            }
-            *target = Spanned {
-                span: member_val.span,
-                val: ArrayOrSingleItem::Array(arr),
-            };
-        }
-        Json::Num(n) => match validate(Box::new(n.val), n.span) {
-            ValidationResult::Ok(val) => {
-                *target = Spanned {
-                    span: member_val.span,
-                    val: ArrayOrSingleItem::SingleItem(val),
-                }
-            }
-            ValidationResult::Err(report) => diag.push(report),
-        },
-        Json::Str(s) => match validate(Box::new(s.val.to_owned()), s.span) {
-            ValidationResult::Ok(val) => {
-                *target = Spanned {
-                    span: member_val.span,
-                    val: ArrayOrSingleItem::SingleItem(val),
-                }
-            }
-            ValidationResult::Err(report) => diag.push(report),
-        },
-        _ => {
-            unexpected_value_kind(
-                path,
-                member_val,
-                &format!("{expected_ty} or array of {expected_ty}"),
-            )
-            .print((path, Source::from(src)))?;
-            bail!("unexpected JSON kind {} found in \"{member_name}\" member value; expected {expected_ty} or array of {expected_ty}", member_val.val.kind_desc());
-        }
+impl UnknownMember {
+    fn report<'d>(&self, path: &'d String) -> DiagnosticReport<'d> {
+        let mut report = Report::build(ReportKind::Error, path, self.name.span.start)
+            .with_message(format!("unexpected member: \"{}\"", self.name.val))
+            .with_label(Label::new((path, self.name.span.into_range())).with_color(Color::Red));
+        if let Some(suggestion) = edit_distance::suggest_similar(self.expected, &self.name.val) {
+            report.add_suggestion(Suggestion::new(
+                self.name.span.into_range(),
+                string_literal(suggestion),
+                Applicability::MaybeIncorrect,
+            ));
+        }
+        report.finish()
     }
-    Ok(())
 }
 
-fn handle_weighted_range_vec<'d, 'a, 'n, T>(
-    _diag: &mut Diagnostics<'d>,
-    path: &'d String,
-    src: &'a str,
-    target: &mut Spanned<Vec<Spanned<WeightedRange<T>>>>,
-    member_val: &Spanned<Json>,
-    member_name: &'n str,
-    weight_validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, f64>,
-    range_validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, T>,
-) -> anyhow::Result<()> {
-    let mut arr = Vec::new();
+/// A member which is already defined earlier in the same object. Only the first definition is
+/// lowered.
+pub(crate) struct DuplicateMember {
+    pub first: Spanned<String>,
+    pub later: Spanned<String>,
+}
 
-    let Json::Array(a) = &member_val.val else {
-        Report::build(ReportKind::Error, path, member_val.span.start)
+impl DuplicateMember {
+    fn report<'d>(&self, path: &'d String) -> DiagnosticReport<'d> {
+        Report::build(ReportKind::Error, path, self.later.span.start)
             .with_message(format!(
-                "expected \"{}\"'s value to be an array of weighted ranges",
-                member_name.fg(Color::Blue)
+                "member \"{}\" defined multiple times",
+                self.later.as_str().fg(Color::Blue)
             ))
-            .with_label(Label::new((path, member_val.span.into_range())).with_color(Color::Red))
-            .finish()
-            .print((path, Source::from(src)))?;
-        bail!("expected an array of weighted ranges");
-    };
-
-    for elem in &a.val {
-        let Json::Object(obj) = &elem.val else {
-            Report::build(ReportKind::Error, path, elem.span.start)
-                .with_message("expected a weighted range object")
-                .with_label(Label::new((path, member_val.span.into_range())).with_color(Color::Red))
-                .finish()
-                .print((path, Source::from(src)))?;
-            bail!("expected a weighted range object");
-        };
-
-        let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-        let mut unique_member_names = BTreeSet::new();
-        for (member_name, member_val) in &obj.val {
-            if !unique_member_names.insert(member_name.val.to_owned()) {
-                Report::build(ReportKind::Error, path, member_name.span.start)
+            .with_label(
+                Label::new((path, self.later.span.into_range()))
+                    .with_color(Color::Red)
                     .with_message(format!(
-                        "member \"{}\" defined multiple times",
-                        member_name.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_label(
-                        Label::new((
-                            path,
-                            unique_members
-                                .iter()
-                                .find(|(k, _)| k.val == member_name.val)
-                                .unwrap()
-                                .0
-                                .span
-                                .into_range(),
-                        ))
-                        .with_message(format!(
-                            "member \"{}\" first defined here",
-                            member_name.val.as_str().fg(Color::Blue)
-                        ))
-                        .with_color(Color::Red),
-                    )
-                    .with_label(
-                        Label::new((path, member_name.span.into_range()))
-                            .with_color(Color::Red)
-                            .with_message(format!(
-                                "member \"{}\" later redefined here",
-                                member_name.val.as_str().fg(Color::Blue)
-                            )),
-                    )
-                    .finish()
-                    .print((path, Source::from(src)))?;
-                bail!("duplicate member detected");
-            } else {
-                unique_members.insert(member_name.to_owned(), member_val.to_owned());
-            }
-        }
-
-        const EXPECTED_MEMBERS: [&str; 2] = ["weight", "range"];
-
-        for found_member_name in unique_members.keys() {
-            if !EXPECTED_MEMBERS.contains(&found_member_name.val.as_str()) {
-                let mut report =
-                    Report::build(ReportKind::Error, path, found_member_name.span.start)
-                        .with_message(format!(
-                            "unexpected member \"{}\" when expecting a weighted range",
-                            found_member_name.val
-                        ))
-                        .with_label(
-                            Label::new((path, found_member_name.span.into_range()))
-                                .with_color(Color::Red),
-                        );
-                if let Some(suggestion) = edit_distance::find_best_match_for_name(
-                    &EXPECTED_MEMBERS,
-                    &found_member_name.val,
-                    Some(SUGGESTION_MAX_EDIT_DISTANCE),
-                ) {
-                    report.set_help(format!(
-                        "did you mean {} instead?",
-                        suggestion.fg(Color::Blue)
-                    ));
-                }
-                report.finish().print((path, Source::from(src)))?;
-                bail!("unexpected member when trying to process a weighted range object");
-            }
-        }
-
-        let Some(weight_member) = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "weight")
-            .map(|(_, v)| v)
-        else {
-            missing_expected_member(path, elem, "weight").print((path, Source::from(src)))?;
-            bail!("missing expected member: \"weight\"");
-        };
-        let weight = {
-            let Json::Num(n) = &weight_member.val else {
-                unexpected_value_kind(path, elem, "number").print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"weight\" member value; expected number",
-                    elem.val.kind_desc()
-                );
-            };
-            match weight_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid weight value");
-                }
-            }
-        };
-
-        let Some(range_member) = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "range")
-            .map(|(_, v)| v)
-        else {
-            missing_expected_member(path, elem, "range").print((path, Source::from(src)))?;
-            bail!("missing expected member: \"range\"");
-        };
-        let range = {
-            let Json::Object(obj) = &range_member.val else {
-                Report::build(ReportKind::Error, path, range_member.span.start)
-                    .with_message("expected a range object")
-                    .with_label(
-                        Label::new((path, range_member.span.into_range())).with_color(Color::Red),
-                    )
-                    .finish()
-                    .print((path, Source::from(src)))?;
-                bail!("expected a range object");
-            };
-
-            let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-            let mut unique_member_names = BTreeSet::new();
-
-            for (member_name, member_val) in &obj.val {
-                if !unique_member_names.insert(member_name.val.to_owned()) {
-                    Report::build(ReportKind::Error, path, member_name.span.start)
-                        .with_message(format!(
-                            "member \"{}\" defined multiple times",
-                            member_name.val.as_str().fg(Color::Blue)
-                        ))
-                        .with_label(
-                            Label::new((
-                                path,
-                                unique_members
-                                    .iter()
-                                    .find(|(k, _)| k.val == member_name.val)
-                                    .unwrap()
-                                    .0
-                                    .span
-                                    .into_range(),
-                            ))
-                            .with_message(format!(
-                                "member \"{}\" first defined here",
-                                member_name.val.as_str().fg(Color::Blue)
-                            ))
-                            .with_color(Color::Red),
-                        )
-                        .with_label(
-                            Label::new((path, member_name.span.into_range()))
-                                .with_color(Color::Red)
-                                .with_message(format!(
-                                    "member \"{}\" later redefined here",
-                                    member_name.val.as_str().fg(Color::Blue)
-                                )),
-                        )
-                        .finish()
-                        .print((path, Source::from(src)))?;
-                    bail!("duplicate member detected");
-                } else {
-                    unique_members.insert(member_name.to_owned(), member_val.to_owned());
-                }
-            }
-
-            const EXPECTED_MEMBERS: [&str; 2] = ["min", "max"];
-
-            for found_member_name in unique_members.keys() {
-                if !EXPECTED_MEMBERS.contains(&found_member_name.val.as_str()) {
-                    let mut report =
-                        Report::build(ReportKind::Error, path, found_member_name.span.start)
-                            .with_message(format!(
-                                "unexpected member \"{}\" when expecting a range",
-                                found_member_name.val
-                            ))
-                            .with_label(
-                                Label::new((path, found_member_name.span.into_range()))
-                                    .with_color(Color::Red),
-                            );
-                    if let Some(suggestion) = edit_distance::find_best_match_for_name(
-                        &EXPECTED_MEMBERS,
-                        &found_member_name.val,
-                        Some(1),
-                    ) {
-                        report.set_help(format!(
-                            "did you mean {} instead?",
-                            suggestion.fg(Color::Blue)
-                        ));
-                    }
-                    report.finish().print((path, Source::from(src)))?;
-                    bail!("unexpected member when trying to process a range object");
-                }
-            }
-
-            let Some(min_member) = unique_members
-                .iter()
-                .find(|(k, _)| k.val == "min")
-                .map(|(_, v)| v)
-            else {
-                trace!(?range_member);
-                trace!("\n{}", &src[range_member.span.into_range()]);
-                missing_expected_member(path, range_member, "min")
-                    .print((path, Source::from(src)))?;
-                bail!("missing expected member: \"min\"");
-            };
-            let min = {
-                let Json::Num(n) = &min_member.val else {
-                    unexpected_value_kind(path, range_member, "number")
-                        .print((path, Source::from(src)))?;
-                    bail!(
-                        "unexpected JSON kind {} found in \"min\" member value; expected number",
-                        range_member.val.kind_desc()
-                    );
-                };
-                match range_validate(Box::new(n.val), n.span) {
-                    ValidationResult::Ok(val) => val,
-                    ValidationResult::Err(report) => {
-                        report.print((path, Source::from(src)))?;
-                        bail!("invalid min value");
-                    }
-                }
-            };
-
-            let Some(max_member) = unique_members
-                .iter()
-                .find(|(k, _)| k.val == "max")
-                .map(|(_, v)| v)
-            else {
-                missing_expected_member(path, range_member, "max")
-                    .print((path, Source::from(src)))?;
-                bail!("missing expected member: \"max\"");
-            };
-            let max = {
-                let Json::Num(n) = &max_member.val else {
-                    unexpected_value_kind(path, range_member, "number")
-                        .print((path, Source::from(src)))?;
-                    bail!(
-                        "unexpected JSON kind {} found in \"max\" member value; expected number",
-                        range_member.val.kind_desc()
-                    );
-                };
-                match range_validate(Box::new(n.val), n.span) {
-                    ValidationResult::Ok(val) => val,
-                    ValidationResult::Err(report) => {
-                        report.print((path, Source::from(src)))?;
-                        bail!("invalid max value");
-                    }
-                }
-            };
-
-            Range {
-                min: Spanned {
-                    span: min_member.span,
-                    val: min,
-                },
-                max: Spanned {
-                    span: max_member.span,
-                    val: max,
-                },
-            }
-        };
-
-        arr.push(Spanned {
-            span: elem.span,
-            val: WeightedRange {
-                weight: Spanned {
-                    span: weight_member.span,
-                    val: weight,
-                },
-                range: Spanned {
-                    span: range_member.span,
-                    val: range,
-                },
-            },
-        });
+                        "member \"{}\" later redefined here",
+                        self.later.as_str().fg(Color::Blue)
+                    )),
+            )
+            .with_span_note(
+                self.first.span.into_range(),
+                format!(
+                    "member \"{}\" first defined here",
+                    self.first.as_str().fg(Color::Blue)
+                ),
+            )
+            .finish()
     }
-
-    *target = Spanned {
-        span: member_val.span,
-        val: arr,
-    };
-
-    Ok(())
 }
 
-fn handle_number<'d, 'a, 'n, T>(
-    _diag: &mut Diagnostics<'d>,
+/// The state of lowering a Custom Difficulty. Values which can't be lowered are reported right
+/// away and abort lowering, while unknown and duplicate members are recorded so that they can all
+/// be reported once lowering is done.
+pub(crate) struct LoweringContext<'a, 'd> {
     path: &'d String,
-    src: &'a str,
-    target: &mut Spanned<T>,
-    member_val: &Spanned<Json>,
-    member_name: &'n str,
-    validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, T>,
-) -> anyhow::Result<()> {
-    let Json::Num(n) = &member_val.val else {
-        unexpected_value_kind(path, member_val, "number").print((path, Source::from(src)))?;
-        bail!(
-            "unexpected JSON kind {} found in \"{member_name}\" member value; expected number",
-            member_val.val.kind_desc()
-        );
-    };
-    let val = match validate(Box::new(n.val), n.span) {
-        ValidationResult::Ok(val) => val,
-        ValidationResult::Err(report) => {
-            report.print((path, Source::from(src)))?;
-            bail!("invalid value");
-        }
-    };
-
-    *target = Spanned {
-        span: member_val.span,
-        val,
-    };
-
-    Ok(())
+    diag: &'a mut Diagnostics<'d>,
+    interner: &'a mut Interner,
+    pub unknown_members: Vec<UnknownMember>,
+    pub duplicate_members: Vec<DuplicateMember>,
 }
 
-fn handle_range<'d, 'a, 'n, T>(
-    _diag: &mut Diagnostics<'d>,
-    path: &'d String,
-    src: &'a str,
-    target: &mut Spanned<Range<T>>,
-    member_val: &Spanned<Json>,
-    _member_name: &'n str,
-    validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, T>,
-) -> anyhow::Result<()> {
-    let Json::Object(obj) = &member_val.val else {
-        Report::build(ReportKind::Error, path, member_val.span.start)
-            .with_message("expected a range object")
-            .with_label(Label::new((path, member_val.span.into_range())).with_color(Color::Red))
-            .finish()
-            .print((path, Source::from(src)))?;
-        bail!("expected a range object");
-    };
-
-    let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-    let mut unique_member_names = BTreeSet::new();
-    for (member_name, member_val) in &obj.val {
-        if !unique_member_names.insert(member_name.val.to_owned()) {
-            Report::build(ReportKind::Error, path, member_name.span.start)
-                .with_message(format!(
-                    "member \"{}\" defined multiple times",
-                    member_name.val.as_str().fg(Color::Blue)
-                ))
-                .with_label(
-                    Label::new((
-                        path,
-                        unique_members
-                            .iter()
-                            .find(|(k, _)| k.val == member_name.val)
-                            .unwrap()
-                            .0
-                            .span
-                            .into_range(),
-                    ))
-                    .with_message(format!(
-                        "member \"{}\" first defined here",
-                        member_name.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_color(Color::Red),
-                )
-                .with_label(
-                    Label::new((path, member_name.span.into_range()))
-                        .with_color(Color::Red)
-                        .with_message(format!(
-                            "member \"{}\" later redefined here",
-                            member_name.val.as_str().fg(Color::Blue)
-                        )),
-                )
-                .finish()
-                .print((path, Source::from(src)))?;
-            bail!("duplicate member detected");
-        } else {
-            unique_members.insert(member_name.to_owned(), member_val.to_owned());
-        }
+impl<'d> LoweringContext<'_, 'd> {
+    /// Reports a value which can't be lowered, and returns the error to abort lowering with.
+    fn fatal(&mut self, report: DiagnosticReport<'d>, message: String) -> anyhow::Error {
+        self.diag.push(report);
+        anyhow!(message)
     }
 
-    const EXPECTED_MEMBERS: [&str; 2] = ["min", "max"];
-
-    for found_member_name in unique_members.keys() {
-        if !EXPECTED_MEMBERS.contains(&found_member_name.val.as_str()) {
-            let mut report = Report::build(ReportKind::Error, path, found_member_name.span.start)
-                .with_message(format!(
-                    "unexpected member \"{}\" when expecting a range",
-                    found_member_name.val
-                ))
-                .with_label(
-                    Label::new((path, found_member_name.span.into_range())).with_color(Color::Red),
-                );
-            if let Some(suggestion) = edit_distance::find_best_match_for_name(
-                &EXPECTED_MEMBERS,
-                &found_member_name.val,
-                Some(1),
-            ) {
-                report.set_help(format!(
-                    "did you mean {} instead?",
-                    suggestion.fg(Color::Blue)
-                ));
-            }
-            report.finish().print((path, Source::from(src)))?;
-            bail!("unexpected member when trying to process a range object");
-        }
+    fn unexpected_kind(&mut self, val: &Spanned<Json>, expected: &str) -> anyhow::Error {
+        self.fatal(
+            unexpected_value_kind(self.path, val, expected),
+            format!(
+                "unexpected JSON kind {} found; expected {expected}",
+                val.kind_desc()
+            ),
+        )
     }
 
-    let Some(min_member) = unique_members
-        .iter()
-        .find(|(k, _)| k.val == "min")
-        .map(|(_, v)| v)
-    else {
-        missing_expected_member(path, member_val, "min").print((path, Source::from(src)))?;
-        bail!("missing expected member: \"min\"");
-    };
-    let min = {
-        let Json::Num(n) = &min_member.val else {
-            unexpected_value_kind(path, member_val, "number").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"min\" member value; expected number",
-                member_val.val.kind_desc()
-            );
-        };
-        match validate(Box::new(n.val), n.span) {
-            ValidationResult::Ok(val) => val,
-            ValidationResult::Err(report) => {
-                report.print((path, Source::from(src)))?;
-                bail!("invalid min value");
-            }
+    pub fn expect_object<'j>(
+        &mut self,
+        val: &'j Spanned<Json>,
+    ) -> anyhow::Result<&'j [(Spanned<String>, Spanned<Json>)]> {
+        match &val.val {
+            Json::Object(obj) => Ok(&obj.val),
+            _ => Err(self.unexpected_kind(val, "object")),
         }
-    };
+    }
 
-    let Some(max_member) = unique_members
-        .iter()
-        .find(|(k, _)| k.val == "max")
-        .map(|(_, v)| v)
-    else {
-        missing_expected_member(path, member_val, "max").print((path, Source::from(src)))?;
-        bail!("missing expected member: \"max\"");
-    };
-    let max = {
-        let Json::Num(n) = &max_member.val else {
-            unexpected_value_kind(path, member_val, "number").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"max\" member value; expected number",
-                member_val.val.kind_desc()
-            );
-        };
-        match validate(Box::new(n.val), n.span) {
-            ValidationResult::Ok(val) => val,
-            ValidationResult::Err(report) => {
-                report.print((path, Source::from(src)))?;
-                bail!("invalid max value");
+    /// The members of an object, in order, except for members which are defined again. These are
+    /// recorded instead.
+    pub fn unique_members<'j>(
+        &mut self,
+        members: &'j [(Spanned<String>, Spanned<Json>)],
+    ) -> Vec<&'j (Spanned<String>, Spanned<Json>)> {
+        let mut unique: IndexMap<&str, &(Spanned<String>, Spanned<Json>)> = IndexMap::new();
+        for member in members {
+            match unique.get(member.0.as_str()) {
+                Some((first, _)) => self.duplicate_members.push(DuplicateMember {
+                    first: first.clone(),
+                    later: member.0.clone(),
+                }),
+                None => {
+                    unique.insert(member.0.as_str(), member);
+                }
             }
         }
-    };
+        unique.into_values().collect()
+    }
 
-    *target = Spanned {
-        span: member_val.span,
-        val: Range {
-            min: Spanned {
-                span: min_member.span,
-                val: min,
-            },
-            max: Spanned {
-                span: max_member.span,
-                val: max,
-            },
-        },
-    };
+    pub fn unknown_member(&mut self, name: &Spanned<String>, expected: &'static [&'static str]) {
+        self.unknown_members.push(UnknownMember {
+            name: name.clone(),
+            expected,
+        });
+    }
 
-    Ok(())
+    pub fn missing_member(&mut self, span: SimpleSpan, name: &str) -> anyhow::Error {
+        self.fatal(
+            missing_expected_member(self.path, span, name),
+            format!("missing expected member: \"{name}\""),
+        )
+    }
 }
 
-fn handle_enemy_pool<'d>(
-    _diag: &mut Diagnostics<'d>,
-    path: &'d String,
-    src: &str,
-    target: &mut Spanned<EnemyPool>,
-    member_val: &Spanned<Json>,
-    _member_name: &str,
-) -> anyhow::Result<()> {
-    let Json::Object(obj) = &member_val.val else {
-        Report::build(ReportKind::Error, path, member_val.span.start)
-            .with_message("expected an enemy pool object")
-            .with_label(Label::new((path, member_val.span.into_range())).with_color(Color::Red))
-            .finish()
-            .print((path, Source::from(src)))?;
-        bail!("expected a enemy pool object");
-    };
+impl<T: FromSpannedJson> FromSpannedJson for Spanned<T> {
+    const EXPECTED: &'static str = T::EXPECTED;
 
-    let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-    let mut unique_member_names = BTreeSet::new();
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        Ok(Spanned::new(T::from_spanned_json(cx, val)?, val.span))
+    }
+}
 
-    const EXPECTED_MEMBERS: [&str; 3] = ["clear", "add", "remove"];
+impl FromSpannedJson for String {
+    const EXPECTED: &'static str = "string";
 
-    for (member_name, member_val) in &obj.val {
-        if !unique_member_names.insert(member_name.val.to_owned()) {
-            Report::build(ReportKind::Error, path, member_name.span.start)
-                .with_message(format!(
-                    "member \"{}\" defined multiple times",
-                    member_name.val.as_str().fg(Color::Blue)
-                ))
-                .with_label(
-                    Label::new((
-                        path,
-                        unique_members
-                            .iter()
-                            .find(|(k, _)| k.val == member_name.val)
-                            .unwrap()
-                            .0
-                            .span
-                            .into_range(),
-                    ))
-                    .with_message(format!(
-                        "member \"{}\" first defined here",
-                        member_name.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_color(Color::Red),
-                )
-                .with_label(
-                    Label::new((path, member_name.span.into_range()))
-                        .with_color(Color::Red)
-                        .with_message(format!(
-                            "member \"{}\" later redefined here",
-                            member_name.val.as_str().fg(Color::Blue)
-                        )),
-                )
-                .finish()
-                .print((path, Source::from(src)))?;
-
-            bail!("duplicate member detected");
-        } else {
-            unique_members.insert(member_name.to_owned(), member_val.to_owned());
-        }
-
-        if !EXPECTED_MEMBERS.contains(&member_name.val.as_str()) {
-            let mut report = Report::build(ReportKind::Error, path, member_name.span.start)
-                .with_message(format!("unexpected member: \"{}\"", member_name.val))
-                .with_label(
-                    Label::new((path, member_name.span.into_range())).with_color(Color::Red),
-                );
-            if let Some(suggestion) = edit_distance::find_best_match_for_name(
-                &EXPECTED_MEMBERS,
-                &member_name.val,
-                Some(SUGGESTION_MAX_EDIT_DISTANCE),
-            ) {
-                report.set_help(format!(
-                    "did you mean {} instead?",
-                    suggestion.fg(Color::Blue)
-                ));
-            }
-            report.finish().print((path, Source::from(src)))?;
-            bail!("unexpected member");
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        match &val.val {
+            Json::Str(s) => Ok(s.val.clone()),
+            _ => Err(cx.unexpected_kind(val, Self::EXPECTED)),
         }
     }
+}
 
-    let clear_member = unique_members.iter().find(|(k, _)| k.val == "clear");
-    let clear = if let Some((_, clear_member_val)) = clear_member {
-        let Json::Bool(b) = &clear_member_val.val else {
-            unexpected_value_kind(path, member_val, "bool").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"clear\" member value; expected bool",
-                member_val.val.kind_desc()
-            );
-        };
+impl FromSpannedJson for Symbol {
+    const EXPECTED: &'static str = "string";
 
-        b.to_owned()
-    } else {
-        Spanned {
-            span: dummy_sp(),
-            val: false,
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        match &val.val {
+            Json::Str(s) => Ok(cx.interner.intern(&s.val)),
+            _ => Err(cx.unexpected_kind(val, Self::EXPECTED)),
         }
-    };
-
-    let add_member = unique_members.iter().find(|(k, _)| k.val == "add");
-    let add = if let Some((_, add_member_val)) = add_member {
-        let Json::Array(a) = &add_member_val.val else {
-            unexpected_value_kind(path, member_val, "array").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"add\" member value; expected array of strings",
-                member_val.val.kind_desc()
-            );
-        };
-
-        let mut descriptors = Vec::new();
+    }
+}
 
-        for elem in &a.val {
-            let Json::Str(s) = &elem.val else {
-                unexpected_value_kind(path, member_val, "string")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "found JSON kind {}, expected a string",
-                    member_val.val.kind_desc()
-                );
-            };
+impl FromSpannedJson for bool {
+    const EXPECTED: &'static str = "bool";
 
-            descriptors.push(s.to_owned());
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        match &val.val {
+            Json::Bool(b) => Ok(b.val),
+            _ => Err(cx.unexpected_kind(val, Self::EXPECTED)),
         }
+    }
+}
 
-        Spanned {
-            span: add_member_val.span,
-            val: descriptors,
-        }
-    } else {
-        Spanned {
-            span: dummy_sp(),
-            val: Vec::new(),
-        }
-    };
+/// Numbers of Custom Difficulty fields have to be non-negative and finite.
+impl FromSpannedJson for f64 {
+    const EXPECTED: &'static str = "number";
 
-    let remove_member = unique_members.iter().find(|(k, _)| k.val == "remove");
-    let remove = if let Some((_, remove_member_val)) = remove_member {
-        let Json::Array(a) = &remove_member_val.val else {
-            unexpected_value_kind(path, member_val, "array").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"remove\" member value; expected array of strings",
-                member_val.val.kind_desc()
-            );
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        let Json::Num(n) = &val.val else {
+            return Err(cx.unexpected_kind(val, Self::EXPECTED));
         };
-
-        let mut pool = Vec::new();
-
-        for elem in &a.val {
-            let Json::Str(s) = &elem.val else {
-                unexpected_value_kind(path, member_val, "string")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "found JSON kind {}, expected a string",
-                    member_val.val.kind_desc()
-                );
-            };
-
-            pool.push(s.to_owned());
+        if n.is_sign_negative() || !n.is_finite() {
+            return Err(cx.fatal(
+                mk_non_negative_and_finite_f64_report(cx.path, n.span, n.val),
+                "invalid value".to_string(),
+            ));
         }
-
-        Spanned {
-            span: remove_member_val.span,
-            val: pool,
-        }
-    } else {
-        Spanned {
-            span: dummy_sp(),
-            val: Vec::new(),
-        }
-    };
-
-    *target = Spanned {
-        span: member_val.span,
-        val: EnemyPool { clear, add, remove },
-    };
-
-    Ok(())
+        Ok(n.val)
+    }
 }
 
-fn handle_enemy_descriptors<'d, 'a, 'n>(
-    diag: &mut Diagnostics<'d>,
-    path: &'d String,
-    src: &'a str,
-    target: &mut Spanned<IndexMap<Spanned<String>, Spanned<EnemyDescriptor>>>,
-    member_val: &Spanned<Json>,
-    _member_name: &'n str,
-    f64_validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, f64>,
-    usize_validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, usize>,
-) -> anyhow::Result<()> {
-    let Json::Object(obj) = &member_val.val else {
-        Report::build(ReportKind::Error, path, member_val.span.start)
-            .with_message("expected an enemy descriptors object")
-            .with_label(Label::new((path, member_val.span.into_range())).with_color(Color::Red))
-            .finish()
-            .print((path, Source::from(src)))?;
-        bail!("expected a enemy descriptors object");
-    };
+/// Fractional numbers are truncated, as the game does.
+impl FromSpannedJson for usize {
+    const EXPECTED: &'static str = "number";
 
-    let mut descriptors = IndexMap::default();
-
-    let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-    let mut unique_member_names = BTreeSet::new();
-    for (name, ed) in &obj.val {
-        if !unique_member_names.insert(name.val.to_owned()) {
-            Report::build(ReportKind::Error, path, name.span.start)
-                .with_message(format!(
-                    "member \"{}\" defined multiple times",
-                    name.val.as_str().fg(Color::Blue)
-                ))
-                .with_label(
-                    Label::new((
-                        path,
-                        unique_members
-                            .iter()
-                            .find(|(k, _)| k.val == name.val)
-                            .unwrap()
-                            .0
-                            .span
-                            .into_range(),
-                    ))
-                    .with_message(format!(
-                        "member \"{}\" first defined here",
-                        name.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_color(Color::Red),
-                )
-                .with_label(
-                    Label::new((path, name.span.into_range()))
-                        .with_color(Color::Red)
-                        .with_message(format!(
-                            "member \"{}\" later redefined here",
-                            name.val.as_str().fg(Color::Blue)
-                        )),
-                )
-                .finish()
-                .print((path, Source::from(src)))?;
-
-            bail!("duplicate member detected");
-        } else {
-            unique_members.insert(name.to_owned(), ed.to_owned());
-        }
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        Ok(f64::from_spanned_json(cx, val)? as u64 as usize)
     }
+}
 
-    for (name, ed) in &unique_members {
-        let Json::Object(ed_obj) = &ed.val else {
-            Report::build(ReportKind::Error, path, ed.span.start)
-                .with_message("expected an enemy descriptors object")
-                .with_label(Label::new((path, ed.span.into_range())).with_color(Color::Red))
-                .finish()
-                .print((path, Source::from(src)))?;
-            bail!("expected a enemy descriptor object");
-        };
-
-        const EXPECTED_MEMBERS: [&str; 14] = [
-            "Base",
-            "SpawnSpread",
-            "IdealSpawnSize",
-            "CanBeUsedForConstantPressure",
-            "CanBeUsedInEncounters",
-            "DifficultyRating",
-            "MinSpawnCount",
-            "MaxSpawnCount",
-            "Rarity",
-            "SpawnAmountModifier",
-            "Elite",
-            "Scale",
-            "TimeDilation",
-            "PawnStats",
-        ];
-
-        let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-        let mut unique_member_names = BTreeSet::new();
-
-        for (ed_member_name, ed_member_value) in &ed_obj.val {
-            if !unique_member_names.insert(ed_member_name.val.to_owned()) {
-                Report::build(ReportKind::Error, path, ed_member_name.span.start)
-                    .with_message(format!(
-                        "member \"{}\" defined multiple times",
-                        ed_member_name.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_label(
-                        Label::new((
-                            path,
-                            unique_members
-                                .iter()
-                                .find(|(k, _)| k.val == ed_member_name.val)
-                                .unwrap()
-                                .0
-                                .span
-                                .into_range(),
-                        ))
-                        .with_message(format!(
-                            "member \"{}\" first defined here",
-                            ed_member_name.val.as_str().fg(Color::Blue)
-                        ))
-                        .with_color(Color::Red),
-                    )
-                    .with_label(
-                        Label::new((path, ed_member_name.span.into_range()))
-                            .with_color(Color::Red)
-                            .with_message(format!(
-                                "member \"{}\" later redefined here",
-                                ed_member_name.val.as_str().fg(Color::Blue)
-                            )),
-                    )
-                    .finish()
-                    .print((path, Source::from(src)))?;
-
-                bail!("duplicate member detected");
-            } else {
-                unique_members.insert(ed_member_name.to_owned(), ed_member_value.to_owned());
-            }
-
-            if !EXPECTED_MEMBERS.contains(&ed_member_name.val.as_str()) {
-                if ed_member_name.val == "UseSpawnRarityModifiers" {
-                    diag.push(defunct_member(
-                        path,
-                        ed_member_name.span,
-                        &ed_member_name.val,
-                    ));
-                } else {
-                    let mut report =
-                        Report::build(ReportKind::Error, path, ed_member_name.span.start)
-                            .with_message(format!("unexpected member: \"{}\"", ed_member_name.val))
-                            .with_label(
-                                Label::new((path, ed_member_name.span.into_range()))
-                                    .with_color(Color::Red),
-                            );
-                    if let Some(suggestion) = edit_distance::find_best_match_for_name(
-                        &EXPECTED_MEMBERS,
-                        &ed_member_name.val,
-                        Some(SUGGESTION_MAX_EDIT_DISTANCE),
-                    ) {
-                        report.set_help(format!(
-                            "did you mean {} instead?",
-                            suggestion.fg(Color::Blue)
-                        ));
-                    }
-                    report.finish().print((path, Source::from(src)))?;
-                    bail!("unexpected member");
-                }
-            }
-        }
-
-        let base_member = unique_members.iter().find(|(k, _)| k.val == "Base");
-        let base = if let Some((_, base_member_val)) = base_member {
-            let Json::Str(n) = &base_member_val.val else {
-                unexpected_value_kind(path, base_member_val, "string")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"Base\" member value; expected string",
-                    base_member_val.val.kind_desc()
-                );
-            };
-            n.to_owned()
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: String::new(),
-            }
-        };
-
-        let spawn_spread_member = unique_members.iter().find(|(k, _)| k.val == "SpawnSpread");
-        let spawn_spread = if let Some((_, spawn_spread_member_val)) = spawn_spread_member {
-            let Json::Num(n) = &spawn_spread_member_val.val else {
-                unexpected_value_kind(path, spawn_spread_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                "unexpected JSON kind {} found in \"SpawnSpread\" member value; expected number",
-                spawn_spread_member_val.val.kind_desc()
-            );
-            };
-            let val = match f64_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid SpawnSpread value");
-                }
-            };
-            Spanned {
-                span: spawn_spread_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0.0,
-            }
-        };
-
-        let ideal_spawn_size_member = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "IdealSpawnSize");
-        let ideal_spawn_size =
-            if let Some((_, ideal_spawn_size_member_val)) = ideal_spawn_size_member {
-                let Json::Num(n) = &ideal_spawn_size_member_val.val else {
-                    unexpected_value_kind(path, ideal_spawn_size_member_val, "number")
-                        .print((path, Source::from(src)))?;
-                    bail!(
-                "unexpected JSON kind {} found in \"IdealSpawnSize\" member value; expected number",
-                ideal_spawn_size_member_val.val.kind_desc()
-            );
-                };
-                let val = match usize_validate(Box::new(n.val), n.span) {
-                    ValidationResult::Ok(val) => val,
-                    ValidationResult::Err(report) => {
-                        report.print((path, Source::from(src)))?;
-                        bail!("invalid IdealSpawnSize value");
-                    }
-                };
-                Spanned {
-                    span: ideal_spawn_size_member_val.span,
-                    val,
-                }
-            } else {
-                Spanned {
-                    span: dummy_sp(),
-                    val: 0,
-                }
-            };
-
-        let can_be_used_for_constant_pressure_member = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "CanBeUsedForConstantPressure");
-        let can_be_used_for_constant_pressure = if let Some((
-            _,
-            can_be_used_for_constant_pressure_member_val,
-        )) = can_be_used_for_constant_pressure_member
-        {
-            let Json::Bool(b) = &can_be_used_for_constant_pressure_member_val.val else {
-                unexpected_value_kind(path, can_be_used_for_constant_pressure_member_val, "bool")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                "unexpected JSON kind {} found in \"CanBeUsedForConstantPressure\" member value; expected bool",
-                can_be_used_for_constant_pressure_member_val.val.kind_desc()
-            );
-            };
-            b.to_owned()
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: false,
-            }
-        };
-
-        let can_be_used_in_encounters_member = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "CanBeUsedInEncounters");
-        let can_be_used_in_encounters = if let Some((_, can_be_used_in_encounters_member_val)) =
-            can_be_used_in_encounters_member
-        {
-            let Json::Bool(b) = &can_be_used_in_encounters_member_val.val else {
-                unexpected_value_kind(path, can_be_used_in_encounters_member_val, "bool")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                "unexpected JSON kind {} found in \"CanBeUsedInEncounters\" member value; expected bool",
-                can_be_used_in_encounters_member_val.val.kind_desc()
-                );
-            };
-            b.to_owned()
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: false,
-            }
-        };
-
-        let difficulty_rating_member = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "DifficultyRating");
-        let difficulty_rating = if let Some((_, difficulty_rating_member_val)) =
-            difficulty_rating_member
-        {
-            let Json::Num(n) = &difficulty_rating_member_val.val else {
-                unexpected_value_kind(path, difficulty_rating_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                "unexpected JSON kind {} found in \"DifficultyRating\" member value; expected number",
-                difficulty_rating_member_val.val.kind_desc()
-            );
-            };
-            let val = match f64_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid DifficultyRating value");
-                }
-            };
-            Spanned {
-                span: difficulty_rating_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0.0,
-            }
-        };
-
-        let min_spawn_count_member = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "MinSpawnCount");
-        let min_spawn_count = if let Some((_, min_spawn_count_member_val)) = min_spawn_count_member
-        {
-            let Json::Num(n) = &min_spawn_count_member_val.val else {
-                unexpected_value_kind(path, min_spawn_count_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                "unexpected JSON kind {} found in \"MinSpawnCount\" member value; expected number",
-                min_spawn_count_member_val.val.kind_desc()
-            );
-            };
-            let val = match usize_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid MinSpawnCount value");
-                }
-            };
-            Spanned {
-                span: min_spawn_count_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0,
-            }
-        };
-
-        let max_spawn_count_member = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "MaxSpawnCount");
-        let max_spawn_count = if let Some((_, max_spawn_count_member_val)) = max_spawn_count_member
-        {
-            let Json::Num(n) = &max_spawn_count_member_val.val else {
-                unexpected_value_kind(path, max_spawn_count_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                "unexpected JSON kind {} found in \"MaxSpawnCount\" member value; expected number",
-                max_spawn_count_member_val.val.kind_desc()
-            );
-            };
-            let val = match usize_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid MaxSpawnCount value");
-                }
-            };
-            Spanned {
-                span: max_spawn_count_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0,
-            }
-        };
-
-        let rarity_member = unique_members.iter().find(|(k, _)| k.val == "Rarity");
-        let rarity = if let Some((_, rarity_member_val)) = rarity_member {
-            let Json::Num(n) = &rarity_member_val.val else {
-                unexpected_value_kind(path, rarity_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"Rarity\" member value; expected number",
-                    rarity_member_val.val.kind_desc()
-                );
-            };
-            let val = match f64_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid Rarity value");
-                }
-            };
-            Spanned {
-                span: rarity_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0.0,
-            }
-        };
-
-        let spawn_amount_modifier_member = unique_members
-            .iter()
-            .find(|(k, _)| k.val == "SpawnAmountModifier");
-        let spawn_amount_modifier = if let Some((_, spawn_amount_modifier_member_val)) =
-            spawn_amount_modifier_member
-        {
-            let Json::Num(n) = &spawn_amount_modifier_member_val.val else {
-                unexpected_value_kind(path, spawn_amount_modifier_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"SpawnAmountModifier\" member value; expected number",
-                    spawn_amount_modifier_member_val.val.kind_desc()
-                );
-            };
-            let val = match f64_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid SpawnAmountModifier value");
-                }
-            };
-            Spanned {
-                span: spawn_amount_modifier_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0.0,
-            }
-        };
-
-        let elite_member = unique_members.iter().find(|(k, _)| k.val == "Elite");
-        let elite = if let Some((_, elite_member_val)) = elite_member {
-            let Json::Bool(b) = &elite_member_val.val else {
-                unexpected_value_kind(path, elite_member_val, "bool")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"Elite\" member value; expected bool",
-                    elite_member_val.val.kind_desc()
-                );
-            };
-            b.to_owned()
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: false,
-            }
-        };
+impl<T: FromSpannedJson> FromSpannedJson for Vec<Spanned<T>> {
+    const EXPECTED: &'static str = "array";
 
-        let scale_member = unique_members.iter().find(|(k, _)| k.val == "Scale");
-        let scale = if let Some((_, scale_member_val)) = scale_member {
-            let Json::Num(n) = &scale_member_val.val else {
-                unexpected_value_kind(path, scale_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"Scale\" member value; expected number",
-                    scale_member_val.val.kind_desc()
-                );
-            };
-            let val = match f64_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid Scale value");
-                }
-            };
-            Spanned {
-                span: scale_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0.0,
-            }
-        };
-
-        let time_dilation_member = unique_members.iter().find(|(k, _)| k.val == "TimeDilation");
-        let time_dilation = if let Some((_, time_dilation_member_val)) = time_dilation_member {
-            let Json::Num(n) = &time_dilation_member_val.val else {
-                unexpected_value_kind(path, time_dilation_member_val, "number")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"TimeDilation\" member value; expected number",
-                    time_dilation_member_val.val.kind_desc()
-                );
-            };
-            let val = match f64_validate(Box::new(n.val), n.span) {
-                ValidationResult::Ok(val) => val,
-                ValidationResult::Err(report) => {
-                    report.print((path, Source::from(src)))?;
-                    bail!("invalid TimeDilation value");
-                }
-            };
-            Spanned {
-                span: time_dilation_member_val.span,
-                val,
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: 0.0,
-            }
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        let Json::Array(a) = &val.val else {
+            return Err(cx.unexpected_kind(val, Self::EXPECTED));
         };
+        a.iter()
+            .map(|elem| Spanned::<T>::from_spanned_json(cx, elem))
+            .collect()
+    }
+}
 
-        let pawn_stats_member = unique_members.iter().find(|(k, _)| k.val == "PawnStats");
-        let pawn_stats = if let Some((_, pawn_stats_member_val)) = pawn_stats_member {
-            let Json::Object(pawn_stats_map) = &pawn_stats_member_val.val else {
-                unexpected_value_kind(path, pawn_stats_member_val, "object")
-                    .print((path, Source::from(src)))?;
-                bail!(
-                    "unexpected JSON kind {} found in \"PawnStats\" member value; expected object",
-                    pawn_stats_member_val.val.kind_desc()
-                );
-            };
-
-            let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-            let mut unique_member_names = BTreeSet::new();
-            for (member_name, member_val) in &pawn_stats_map.val {
-                if !unique_member_names.insert(member_name.val.to_owned()) {
-                    Report::build(ReportKind::Error, path, member_name.span.start)
-                        .with_message(format!(
-                            "member \"{}\" defined multiple times",
-                            member_name.val.as_str().fg(Color::Blue)
-                        ))
-                        .with_label(
-                            Label::new((
-                                path,
-                                unique_members
-                                    .iter()
-                                    .find(|(k, _)| k.val == member_name.val)
-                                    .unwrap()
-                                    .0
-                                    .span
-                                    .into_range(),
-                            ))
-                            .with_message(format!(
-                                "member \"{}\" first defined here",
-                                member_name.val.as_str().fg(Color::Blue)
-                            ))
-                            .with_color(Color::Red),
-                        )
-                        .with_label(
-                            Label::new((path, member_name.span.into_range()))
-                                .with_color(Color::Red)
-                                .with_message(format!(
-                                    "member \"{}\" later redefined here",
-                                    member_name.val.as_str().fg(Color::Blue)
-                                )),
-                        )
-                        .finish()
-                        .print((path, Source::from(src)))?;
-                    bail!("duplicate member detected");
-                } else {
-                    unique_members.insert(member_name.to_owned(), member_val.to_owned());
-                }
-            }
-
-            const EXPECTED_MEMBERS: [&str; 49] = [
-                "PST_BarrelKicking",
-                "PST_CarriableThrowing",
-                "PST_CarryingCapacity",
-                "PST_CarryingSpeedModifier",
-                "PST_CaveLeechSense",
-                "PST_ColdResistance",
-                "PST_CorrosiveResistance",
-                "PST_DamageBonus",
-                "PST_DamageFromPlayers",
-                "PST_DamageResistance",
-                "PST_DepositSpeed",
-                "PST_DirtMiningStrength",
-                "PST_ElectricResistance",
-                "PST_EventExplosionResistance",
-                "PST_ExplodeOnDeath",
-                "PST_ExplosionResistance",
-                "PST_FallingResistance",
-                "PST_FireResistance",
-                "PST_FlareThrowStrength",
-                "PST_FriendlyFire",
-                "PST_GoldMining",
-                "PST_HoverBootsDuration",
-                "PST_InternalDamageResistance",
-                "PST_KineticResistance",
-                "PST_MaxHealth",
-                "PST_MaxShield",
-                "PST_MeleeDamage",
-                "PST_MorkiteMining",
-                "PST_MovementSpeed",
-                "PST_MovementSpeedEnvironmentalPenalty",
-                "PST_MovementSpeedEnvironmentalPenaltyReduction",
-                "PST_MovementSpeedPenalty",
-                "PST_MovementSpeedPenaltyReduction",
-                "PST_PhysicalResistance",
-                "PST_PoisonResistance",
-                "PST_PowerAttackCooldownRate",
-                "PST_RadiationResistance",
-                "PST_RedSugarHeal",
-                "PST_ResourceMiningStrength",
-                "PST_ResupplyHealing",
-                "PST_ResupplySpeed",
-                "PST_ReviveSpeed",
-                "PST_RockMiningStrength",
-                "PST_ShieldRegeneratoinRate",
-                "PST_SlipperyFloor",
-                "PST_SprintSpeed",
-                "PST_Ziplline_DownBoost",
-                "PST_ZipllineSpee",
-                "PST_PiercingResistance",
-            ];
-
-            for found_member_name in unique_members.keys() {
-                if !EXPECTED_MEMBERS.contains(&found_member_name.val.as_str()) {
-                    let mut report =
-                        Report::build(ReportKind::Error, path, found_member_name.span.start)
-                            .with_message(format!(
-                                "unexpected pawn stat \"{}\"",
-                                found_member_name.val
-                            ))
-                            .with_label(
-                                Label::new((path, found_member_name.span.into_range()))
-                                    .with_color(Color::Red),
-                            );
-                    if let Some(suggestion) = edit_distance::find_best_match_for_name(
-                        &EXPECTED_MEMBERS,
-                        &found_member_name.val,
-                        Some(SUGGESTION_MAX_EDIT_DISTANCE),
-                    ) {
-                        report.set_help(format!(
-                            "did you mean {} instead?",
-                            suggestion.fg(Color::Blue)
-                        ));
+/// Strings and numbers which are negative or not finite are reported and skipped instead of
+/// aborting lowering.
+impl<T: FromSpannedJson + Default> FromSpannedJson for ArrayOrSingleItem<T> {
+    const EXPECTED: &'static str = T::EXPECTED;
+
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        match &val.val {
+            Json::Array(a) => {
+                let mut arr = Vec::new();
+                for elem in &a.val {
+                    if !report_skipped_item::<T>(cx, elem) {
+                        arr.push(T::from_spanned_json(cx, elem)?);
                     }
-                    report.finish().print((path, Source::from(src)))?;
-                    bail!("unexpected member when trying to process a pawn stat");
                 }
+                Ok(ArrayOrSingleItem::Array(arr))
             }
-
-            let mut pawn_stats = IndexMap::new();
-
-            for (name, val) in &unique_members {
-                let Json::Num(pawn_stat_val) = &val.val else {
-                    unexpected_value_kind(path, val, "number").print((path, Source::from(src)))?;
-                    bail!(
-                        "unexpected JSON kind {} found in \"PawnStats\" member value; expected number",
-                        val.val.kind_desc()
-                    );
-                };
-
-                pawn_stats.insert(name.to_owned(), pawn_stat_val.to_owned());
-            }
-
-            Spanned {
-                span: member_val.span,
-                val: PawnStats(pawn_stats),
-            }
-        } else {
-            Spanned {
-                span: dummy_sp(),
-                val: PawnStats::default(),
-            }
-        };
-
-        descriptors.insert(
-            name.to_owned(),
-            Spanned {
-                span: name.span,
-                val: EnemyDescriptor {
-                    base,
-                    spawn_spread,
-                    ideal_spawn_size,
-                    can_be_used_for_constant_pressure,
-                    can_be_used_in_encounters,
-                    difficulty_rating,
-                    min_spawn_count,
-                    max_spawn_count,
-                    rarity,
-                    spawn_amount_modifier,
-                    elite,
-                    scale,
-                    time_dilation,
-                    pawn_stats,
-                },
-            },
-        );
+            _ if report_skipped_item::<T>(cx, val) => Ok(ArrayOrSingleItem::default()),
+            _ if val.kind_desc() == T::EXPECTED => Ok(ArrayOrSingleItem::SingleItem(
+                T::from_spanned_json(cx, val)?,
+            )),
+            _ => Err(cx.unexpected_kind(val, &format!("{0} or array of {0}", T::EXPECTED))),
+        }
     }
-
-    *target = Spanned {
-        span: member_val.span,
-        val: descriptors,
-    };
-
-    Ok(())
 }
 
-fn handle_escort_mule<'d, 'a, 'n>(
-    _diag: &mut Diagnostics<'d>,
-    path: &'d String,
-    src: &'a str,
-    target: &mut Spanned<EscortMule>,
-    member_val: &Spanned<Json>,
-    _member_name: &'n str,
-    validate: impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'d, f64>,
-) -> anyhow::Result<()> {
-    let Json::Object(obj) = &member_val.val else {
-        Report::build(ReportKind::Error, path, member_val.span.start)
-            .with_message("expected an escort mule object")
-            .with_label(Label::new((path, member_val.span.into_range())).with_color(Color::Red))
-            .finish()
-            .print((path, Source::from(src)))?;
-        bail!("expected a escort mule object");
-    };
-
-    const EXPECTED_MEMBERS: [&str; 4] = [
-        "FriendlyFireModifier",
-        "NeutralDamageModifier",
-        "BigHitDamageModifier",
-        "BigHitDamageReductionThreshold",
-    ];
-
-    let mut unique_member_names = BTreeSet::new();
-    let mut unique_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-
-    for (member_name, member_val) in &obj.val {
-        if !unique_member_names.insert(member_name.val.to_owned()) {
-            Report::build(ReportKind::Error, path, member_name.span.start)
-                .with_message(format!(
-                    "member \"{}\" defined multiple times",
-                    member_name.val.as_str().fg(Color::Blue)
-                ))
-                .with_label(
-                    Label::new((
-                        path,
-                        unique_members
-                            .iter()
-                            .find(|(k, _)| k.val == member_name.val)
-                            .unwrap()
-                            .0
-                            .span
-                            .into_range(),
-                    ))
-                    .with_message(format!(
-                        "member \"{}\" first defined here",
-                        member_name.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_color(Color::Red),
-                )
-                .with_label(
-                    Label::new((path, member_name.span.into_range()))
-                        .with_color(Color::Red)
-                        .with_message(format!(
-                            "member \"{}\" later redefined here",
-                            member_name.val.as_str().fg(Color::Blue)
-                        )),
-                )
-                .finish()
-                .print((path, Source::from(src)))?;
-
-            bail!("duplicate member detected");
-        } else {
-            unique_members.insert(member_name.to_owned(), member_val.to_owned());
-        }
-
-        if !EXPECTED_MEMBERS.contains(&member_name.val.as_str()) {
-            let mut report = Report::build(ReportKind::Error, path, member_name.span.start)
-                .with_message(format!("unexpected member: \"{}\"", member_name.val))
-                .with_label(
-                    Label::new((path, member_name.span.into_range())).with_color(Color::Red),
-                );
-            if let Some(suggestion) = edit_distance::find_best_match_for_name(
-                &EXPECTED_MEMBERS,
-                &member_name.val,
-                Some(SUGGESTION_MAX_EDIT_DISTANCE),
-            ) {
-                report.set_help(format!(
-                    "did you mean {} instead?",
-                    suggestion.fg(Color::Blue)
-                ));
-            }
-            report.finish().print((path, Source::from(src)))?;
-            bail!("unexpected member");
-        }
+/// Reports an item of an [`ArrayOrSingleItem`] which is skipped rather than lowered, returning
+/// whether it was.
+fn report_skipped_item<T: FromSpannedJson>(
+    cx: &mut LoweringContext<'_, '_>,
+    item: &Spanned<Json>,
+) -> bool {
+    match &item.val {
+        Json::Str(_) => {
+            cx.diag
+                .push(unexpected_value_kind(cx.path, item, T::EXPECTED));
+            true
+        }
+        Json::Num(n) if n.is_sign_negative() || !n.is_finite() => {
+            cx.diag.push(mk_non_negative_and_finite_f64_report(
+                cx.path, n.span, n.val,
+            ));
+            true
+        }
+        _ => false,
     }
+}
 
-    let friendly_fire_modifier_member = unique_members
-        .iter()
-        .find(|(k, _)| k.val == "FriendlyFireModifier");
-    let friendly_fire_modifier = if let Some((_, ffm_member_val)) = friendly_fire_modifier_member {
-        let Json::Num(n) = &ffm_member_val.val else {
-            unexpected_value_kind(path, member_val, "number").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"FriendlyFireModifier\" member value; expected number",
-                ffm_member_val.val.kind_desc()
-            );
-        };
-        let val = match validate(Box::new(n.val), n.span) {
-            ValidationResult::Ok(val) => val,
-            ValidationResult::Err(report) => {
-                report.print((path, Source::from(src)))?;
-                bail!("invalid FriendlyFireModifier value");
-            }
-        };
-        Spanned {
-            span: ffm_member_val.span,
-            val,
-        }
-    } else {
-        Spanned {
-            span: dummy_sp(),
-            val: 0.0,
-        }
-    };
-
-    let neutral_damage_modifier_member = unique_members
-        .iter()
-        .find(|(k, _)| k.val == "NeutralDamageModifier");
-    let neutral_damage_modifier = if let Some((_, ndm_member_val)) = neutral_damage_modifier_member
-    {
-        let Json::Num(n) = &ndm_member_val.val else {
-            unexpected_value_kind(path, member_val, "number").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"NeutralDamageModifier\" member value; expected number",
-                ndm_member_val.val.kind_desc()
+impl<T: FromSpannedJson> FromSpannedJson for IndexMap<Spanned<Symbol>, Spanned<T>> {
+    const EXPECTED: &'static str = "object";
+
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        let members = cx.expect_object(val)?;
+        let mut map = IndexMap::new();
+        for (name, member_val) in cx.unique_members(members) {
+            map.insert(
+                name.as_ref().map(|name| cx.interner.intern(name)),
+                Spanned::<T>::from_spanned_json(cx, member_val)?,
             );
-        };
-        let val = match validate(Box::new(n.val), n.span) {
-            ValidationResult::Ok(val) => val,
-            ValidationResult::Err(report) => {
-                report.print((path, Source::from(src)))?;
-                bail!("invalid NeutralDamageModifier value");
-            }
-        };
-        Spanned {
-            span: ndm_member_val.span,
-            val,
-        }
-    } else {
-        Spanned {
-            span: dummy_sp(),
-            val: 0.0,
         }
-    };
-
-    let big_hit_damage_modifier_member = unique_members
-        .iter()
-        .find(|(k, _)| k.val == "BigHitDamageModifier");
-    let big_hit_damage_modifier = if let Some((_, bhm_member_val)) = big_hit_damage_modifier_member
-    {
-        let Json::Num(n) = &bhm_member_val.val else {
-            unexpected_value_kind(path, member_val, "number").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"BigHitDamageModifier\" member value; expected number",
-                bhm_member_val.val.kind_desc()
-            );
-        };
-        let val = match validate(Box::new(n.val), n.span) {
-            ValidationResult::Ok(val) => val,
-            ValidationResult::Err(report) => {
-                report.print((path, Source::from(src)))?;
-                bail!("invalid BigHitDamageModifier value");
-            }
-        };
-        Spanned {
-            span: bhm_member_val.span,
-            val,
-        }
-    } else {
-        Spanned {
-            span: dummy_sp(),
-            val: 0.0,
-        }
-    };
+        Ok(map)
+    }
+}
 
-    let big_hit_damage_reduction_threshold_member = unique_members
-        .iter()
-        .find(|(k, _)| k.val == "BigHitDamageReductionThreshold");
-    let big_hit_damage_reduction_threshold = if let Some((_, bhm_member_val)) =
-        big_hit_damage_reduction_threshold_member
-    {
-        let Json::Num(n) = &bhm_member_val.val else {
-            unexpected_value_kind(path, member_val, "number").print((path, Source::from(src)))?;
-            bail!(
-                "unexpected JSON kind {} found in \"BigHitDamageReductionThreshold\" member value; expected number",
-                bhm_member_val.val.kind_desc()
-            );
-        };
-        let val = match validate(Box::new(n.val), n.span) {
-            ValidationResult::Ok(val) => val,
-            ValidationResult::Err(report) => {
-                report.print((path, Source::from(src)))?;
-                bail!("invalid BigHitDamageReductionThreshold value");
-            }
-        };
-        Spanned {
-            span: bhm_member_val.span,
-            val,
-        }
-    } else {
-        Spanned {
-            span: dummy_sp(),
-            val: 0.0,
+/// Pawn stats are arbitrary multipliers, so their values are not validated beyond being numbers.
+impl FromSpannedJson for PawnStats {
+    const EXPECTED: &'static str = "object";
+
+    fn from_spanned_json(
+        cx: &mut LoweringContext<'_, '_>,
+        val: &Spanned<Json>,
+    ) -> anyhow::Result<Self> {
+        let members = cx.expect_object(val)?;
+        let mut pawn_stats = IndexMap::new();
+        for (name, member_val) in cx.unique_members(members) {
+            let Json::Num(n) = &member_val.val else {
+                return Err(cx.unexpected_kind(member_val, "number"));
+            };
+            pawn_stats.insert(name.clone(), n.clone());
         }
-    };
-
-    *target = Spanned {
-        span: member_val.span,
-        val: EscortMule {
-            friendly_fire_modifier,
-            neutral_damage_modifier,
-            big_hit_damage_modifier,
-            big_hit_damage_reduction_threshold,
-        },
-    };
-
-    Ok(())
+        Ok(PawnStats(pawn_stats))
+    }
 }
 
-pub(crate) fn handle_top_level_members<'d>(
+/// Lowers the top-level members of a Custom Difficulty, whose object is at `span`. Unknown and
+/// duplicate members anywhere in the Custom Difficulty are collected while lowering and reported
+/// together at the end, in the order they appear in the file.
+///
+/// Every error is reported in `diag` before it is returned, so that it can be shown to the user
+/// like any other diagnostic. Names are interned in `interner`.
+pub fn handle_top_level_members<'d>(
     diag: &mut Diagnostics<'d>,
     path: &'d String,
-    src: &str,
+    interner: &mut Interner,
     cd: &mut CustomDifficulty,
-    top_level_members: &Vec<(Spanned<String>, Spanned<Json>)>,
+    span: SimpleSpan,
+    top_level_members: &[(Spanned<String>, Spanned<Json>)],
 ) -> anyhow::Result<()> {
-    let mut unique_top_level_members: IndexMap<Spanned<String>, Spanned<Json>> = IndexMap::new();
-    let mut unique_top_level_member_names = BTreeSet::new();
-    for (member_name, member_val) in top_level_members {
-        if !unique_top_level_member_names.insert(member_name.val.to_owned()) {
-            Report::build(ReportKind::Error, path, member_name.span.start)
-                .with_message(format!(
-                    "member \"{}\" defined multiple times",
-                    member_name.val.as_str().fg(Color::Blue)
-                ))
-                .with_label(
-                    Label::new((
-                        path,
-                        unique_top_level_members
-                            .iter()
-                            .find(|(k, _)| k.val == member_name.val)
-                            .unwrap()
-                            .0
-                            .span
-                            .into_range(),
-                    ))
-                    .with_message(format!(
-                        "member \"{}\" first defined here",
-                        member_name.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_color(Color::Red),
-                )
-                .with_label(
-                    Label::new((path, member_name.span.into_range()))
-                        .with_color(Color::Red)
-                        .with_message(format!(
-                            "member \"{}\" later redefined here",
-                            member_name.val.as_str().fg(Color::Blue)
-                        )),
-                )
-                .finish()
-                .print((path, Source::from(src)))?;
-
-            bail!("duplicate top-level member detected");
-        } else {
-            unique_top_level_members.insert(member_name.to_owned(), member_val.to_owned());
-        }
-    }
+    let mut cx = LoweringContext {
+        path,
+        diag,
+        interner,
+        unknown_members: Vec::new(),
+        duplicate_members: Vec::new(),
+    };
+    let lowered = CustomDifficulty::from_members(&mut cx, span, top_level_members);
 
-    for (member_name, member_val) in unique_top_level_members {
-        let found_member_name = member_name.val.as_str();
-        match found_member_name {
-            "Name" => handle_str(
-                diag,
-                path,
-                src,
-                &mut cd.name,
-                &member_val,
-                found_member_name,
-            )?,
-            "Description" => handle_str(
-                diag,
-                path,
-                src,
-                &mut cd.description,
-                &member_val,
-                found_member_name,
-            )?,
-            "MaxActiveCritters" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.max_active_critters,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "MaxActiveSwarmers" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.max_active_swarmers,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "MaxActiveEnemies" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.max_active_enemies,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "ResupplyCost" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.resupply_cost,
-                &member_val,
-                "ResupplyCost",
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "StartingNitra" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.starting_nitra,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "ExtraLargeEnemyDamageResistance" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.extra_large_enemy_damage_resistance,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "ExtraLargeEnemyDamageResistanceB" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.extra_large_enemy_damage_resistance_b,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "ExtraLargeEnemyDamageResistanceC" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.extra_large_enemy_damage_resistance_c,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "ExtraLargeEnemyDamageResistanceD" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.extra_large_enemy_damage_resistance_d,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "EnemyDamageResistance" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.enemy_damage_resistance,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "SmallEnemyDamageResistance" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.small_enemy_damage_resistance,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "EnemyDamageModifier" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.enemy_damage_modifier,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "EnemyCountModifier" => handle_single_item_or_array_number(
-                diag,
-                path,
-                src,
-                "number",
-                &mut cd.enemy_count_modifier,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "EncounterDifficulty" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.encounter_difficulty,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "StationaryDifficulty" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.stationary_difficulty,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "EnemyWaveInterval" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.enemy_wave_interval,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "EnemyNormalWaveInterval" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.enemy_normal_wave_interval,
-                &member_val,
-                "EnemyNormalWaveInterval",
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "EnemyNormalWaveDifficulty" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.enemy_normal_wave_difficulty,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "EnemyDiversity" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.enemy_diversity,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "StationaryEnemyDiversity" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.stationary_enemy_diversity,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "VeteranNormal" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.veteran_normal,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "VeteranLarge" => handle_weighted_range_vec(
-                diag,
-                path,
-                src,
-                &mut cd.veteran_large,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "DisruptiveEnemyPoolCount" => handle_range(
-                diag,
-                path,
-                src,
-                &mut cd.disruptive_enemy_pool_count,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "MinPoolSize" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.min_pool_size,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "MaxActiveElites" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.max_active_elites,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "EnvironmentalDamageModifier" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.environmental_damage_modifier,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "PointExtractionScalar" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.point_extraction_scalar,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "HazardBonus" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.hazard_bonus,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "FriendlyFireModifier" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.friendly_fire_modifier,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "WaveStartDelayScale" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.wave_start_delay_scale,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "SpeedModifier" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.speed_modifier,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "AttackCooldownModifier" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.attack_cooldown_modifier,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "ProjectileSpeedModifier" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.projectile_speed_modifier,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "HealthRegenerationMax" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.health_regeneration_max,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "ReviveHealthRatio" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.revive_health_ratio,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "EliteCooldown" => handle_number(
-                diag,
-                path,
-                src,
-                &mut cd.elite_cooldown,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "EnemyPool" => handle_enemy_pool(
-                diag,
-                path,
-                src,
-                &mut cd.enemy_pool,
-                &member_val,
-                found_member_name,
-            )?,
-            "CommonEnemies" => handle_enemy_pool(
-                diag,
-                path,
-                src,
-                &mut cd.common_enemies,
-                &member_val,
-                found_member_name,
-            )?,
-            "DisruptiveEnemies" => handle_enemy_pool(
-                diag,
-                path,
-                src,
-                &mut cd.disruptive_enemies,
-                &member_val,
-                found_member_name,
-            )?,
-            "SpecialEnemies" => handle_enemy_pool(
-                diag,
-                path,
-                src,
-                &mut cd.special_enemies,
-                &member_val,
-                found_member_name,
-            )?,
-            "StationaryEnemies" => handle_enemy_pool(
-                diag,
-                path,
-                src,
-                &mut cd.stationary_enemies,
-                &member_val,
-                found_member_name,
-            )?,
-            "EnemyDescriptors" => handle_enemy_descriptors(
-                diag,
-                path,
-                src,
-                &mut cd.enemy_descriptors,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-                mk_finite_nonnegative_f64_to_usize_validator(path),
-            )?,
-            "EscortMule" => handle_escort_mule(
-                diag,
-                path,
-                src,
-                &mut cd.escort_mule,
-                &member_val,
-                found_member_name,
-                mk_finite_nonnegative_f64_validator(path),
-            )?,
-            "SeasonalEvents" => diag.push(
-                Report::build(ReportKind::Warning, path, member_name.span.start)
-                    .with_message(format!(
-                        "\"{}\" is no longer functional",
-                        found_member_name.fg(Color::Blue)
-                    ))
-                    .with_label(
-                        Label::new((path, member_name.span.into_range())).with_color(Color::Yellow),
-                    )
-                    .finish(),
-            ),
-            m => {
-                handle_unknown_top_level_member(path, src, &member_name, m)?;
-            }
-        }
+    let mut reports = cx
+        .duplicate_members
+        .iter()
+        .map(|member| member.report(path))
+        .chain(cx.unknown_members.iter().map(|member| member.report(path)))
+        .collect::<Vec<_>>();
+    reports.sort_by_key(|report| report.primary_span().start);
+    let has_unexpected_members = !reports.is_empty();
+    cx.diag.extend(reports);
+
+    *cd = lowered?;
+    if has_unexpected_members {
+        bail!("unexpected or duplicate members");
     }
-
     Ok(())
 }
 
-fn mk_finite_nonnegative_f64_to_usize_validator<'a>(
-    path: &'a String,
-) -> impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'a, usize> {
-    |val, span| {
-        let val = val.downcast_ref::<f64>().unwrap();
-        if !val.is_sign_negative() && val.is_finite() {
-            ValidationResult::Ok(*val as u64 as usize)
-        } else {
-            ValidationResult::Err(mk_non_negative_and_finite_f64_report(path, span, *val))
-        }
-    }
-}
-
-fn mk_finite_nonnegative_f64_validator<'a>(
-    path: &'a String,
-) -> impl Fn(Box<dyn Any>, SimpleSpan) -> ValidationResult<'a, f64> {
-    |val, span| {
-        let val = val.downcast_ref::<f64>().unwrap();
-        if !val.is_sign_negative() && val.is_finite() {
-            ValidationResult::Ok(*val)
-        } else {
-            ValidationResult::Err(mk_non_negative_and_finite_f64_report(path, span, *val))
-        }
-    }
-}
-
 fn mk_non_negative_and_finite_f64_report(
     path: &String,
     span: SimpleSpan,
@@ -2252,76 +415,37 @@ fn mk_non_negative_and_finite_f64_report(
         .finish()
 }
 
-fn handle_unknown_top_level_member(
-    path: &String,
-    src: &str,
-    member_name: &Spanned<String>,
-    received_member_name: &str,
-) -> anyhow::Result<()> {
-    const TOP_LEVEL_MEMBER_NAMES: [&str; 46] = [
-        "Name",
-        "Description",
-        "MaxActiveCritters",
-        "MaxActiveSwarmers",
-        "MaxActiveEnemies",
-        "ResupplyCost",
-        "StartingNitra",
-        "ExtraLargeEnemyDamageResistance",
-        "ExtraLargeEnemyDamageResistanceB",
-        "ExtraLargeEnemyDamageResistanceC",
-        "ExtraLargeEnemyDamageResistanceD",
-        "EnemyDamageResistance",
-        "SmallEnemyDamageResistance",
-        "EnemyDamageModifier",
-        "EnemyCountModifier",
-        "EncounterDifficulty",
-        "StationaryDifficulty",
-        "EnemyWaveInterval",
-        "EnemyNormalWaveInterval",
-        "EnemyNormalWaveDifficulty",
-        "EnemyDiversity",
-        "StationaryEnemyDiversity",
-        "VeteranNormal",
-        "VeteranLarge",
-        "DisruptiveEnemyPoolCount",
-        "MinPoolSize",
-        "MaxActiveElites",
-        "EnvironmentalDamageModifier",
-        "PointExtractionScalar",
-        "HazardBonus",
-        "FriendlyFireModifier",
-        "WaveStartDelayScale",
-        "SpeedModifier",
-        "AttackCooldownModifier",
-        "ProjectileSpeedModifier",
-        "HealthRegenerationMax",
-        "ReviveHealthRatio",
-        "EliteCooldown",
-        "EnemyDescriptors",
-        "EnemyPool",
-        "CommonEnemies",
-        "DisruptiveEnemies",
-        "SpecialEnemies",
-        "StationaryEnemies",
-        "SeasonalEvents",
-        "EscortMule",
-    ];
+/// A Custom Difficulty is a JSON object, but the top-level value is something else.
+pub fn unexpected_top_level_kind<'d>(path: &'d String, v: &Spanned<Json>) -> DiagnosticReport<'d> {
+    let mut report = Report::build(ReportKind::Error, path, v.span.start)
+        .with_message(format!(
+            "expected an object describing a Custom Difficulty, found {} {}",
+            if matches!(v.val, Json::Array(_) | Json::Object(_)) {
+                "an"
+            } else {
+                "a"
+            },
+            v.kind_desc().fg(Color::Blue)
+        ))
+        .with_label(
+            Label::new((path, v.span.into_range()))
+                .with_color(Color::Red)
+                .with_message("expected an object"),
+        );
 
-    let mut report = Report::build(ReportKind::Error, path, member_name.span.start)
-        .with_message(format!("unexpected member: \"{}\"", received_member_name))
-        .with_label(Label::new((path, member_name.span.into_range())).with_color(Color::Red));
-    if let Some(suggestion) = edit_distance::find_best_match_for_name(
-        &TOP_LEVEL_MEMBER_NAMES,
-        received_member_name,
-        Some(SUGGESTION_MAX_EDIT_DISTANCE),
-    ) {
-        report.set_help(format!(
-            "did you mean {} instead?",
-            suggestion.fg(Color::Blue)
-        ));
+    match &v.val {
+        Json::Array(a) if a.len() == 1 && matches!(a.val[0].val, Json::Object(_)) => {
+            report.set_help("remove the surrounding `[` and `]` to lint the object inside");
+        }
+        _ => {
+            report.set_help(format!(
+                "wrap the members of the Custom Difficulty in `{{` and `}}`, e.g. {}",
+                "{ \"Name\": \"My Custom Difficulty\", ... }".fg(Color::Blue)
+            ));
+        }
     }
-    report.finish().print((path, Source::from(src)))?;
-    bail!("unexpected top-level member");
+
+    report.finish()
 }
 
 fn unexpected_value_kind<'d>(
@@ -2329,37 +453,74 @@ fn unexpected_value_kind<'d>(
     v: &Spanned<Json>,
     expected_kind: &str,
 ) -> DiagnosticReport<'d> {
-    Report::build(ReportKind::Error, path, v.span.start)
+    let mut report = Report::build(ReportKind::Error, path, v.span.start)
         .with_message(format!(
             "unexpected member value JSON kind: expected {} but found {}",
             expected_kind.fg(Color::Blue),
-            v.val.kind_desc().fg(Color::Blue)
+            v.kind_desc().fg(Color::Blue)
         ))
-        .with_label(Label::new((path, v.span.into_range())).with_color(Color::Red))
-        .finish()
+        .with_label(
+            Label::new((path, v.span.into_range()))
+                .with_color(Color::Red)
+                .with_message(format!("expected {expected_kind}")),
+        );
+
+    match &v.val {
+        // Numbers and booleans wrapped in quotes are strings, and the game silently ignores them.
+        Json::Str(s) => {
+            if let Some(unquoted) = unquote_string_literal(&s.val, expected_kind) {
+                report.set_help(format!(
+                    "remove the quotes: replace \"{}\" with {}",
+                    s.val,
+                    unquoted.fg(Color::Blue)
+                ));
+            }
+        }
+        Json::Null(_) => {
+            report.add_note(format!(
+                "{} does not mean \"use the default value\", the game treats it unpredictably",
+                "null".fg(Color::Blue)
+            ));
+            report.set_help(format!(
+                "remove the member to keep the default value, or supply a {expected_kind}"
+            ));
+        }
+        _ => {}
+    }
+
+    report.finish()
+}
+
+/// If the string is a number or boolean literal of the expected JSON kind, returns the literal.
+fn unquote_string_literal(s: &str, expected_kind: &str) -> Option<String> {
+    let s = s.trim();
+    match expected_kind {
+        "number" => s
+            .parse::<f64>()
+            .is_ok_and(f64::is_finite)
+            .then(|| s.to_string()),
+        "bool" => ["true", "false"]
+            .into_iter()
+            .find(|b| b.eq_ignore_ascii_case(s))
+            .map(ToString::to_string),
+        _ => None,
+    }
 }
 
 fn missing_expected_member<'d>(
     path: &'d String,
-    v: &Spanned<Json>,
+    span: SimpleSpan,
     name: &str,
 ) -> DiagnosticReport<'d> {
-    Report::build(ReportKind::Error, path, v.span.start)
+    Report::build(ReportKind::Error, path, span.start)
         .with_message(format!(
             "missing mandatory attribute: \"{}\"",
             name.fg(Color::Blue),
         ))
         .with_label(
-            Label::new((path, v.span.into_range()))
+            Label::new((path, span.into_range()))
                 .with_color(Color::Red)
                 .with_message(format!("this is missing \"{}\"", name.fg(Color::Blue))),
         )
         .finish()
 }
-
-fn defunct_member<'d>(path: &'d String, span: SimpleSpan, name: &str) -> DiagnosticReport<'d> {
-    Report::build(ReportKind::Warning, path, span.start)
-        .with_message(format!("\"{}\" is non-functional", name.fg(Color::Blue),))
-        .with_label(Label::new((path, span.into_range())).with_color(Color::Yellow))
-        .finish()
-}
@@ -0,0 +1,149 @@
+//! Estimates which vanilla hazard level a Custom Difficulty is comparable to, per category.
+
+use crate::custom_difficulty::{ArrayOrSingleItem, CustomDifficulty, WeightedRange};
+use crate::hazards::{Hazard, PLAYER_COUNTS, VANILLA_HAZARDS};
+use crate::spanned::Spanned;
+
+/// How a category of the Custom Difficulty compares to the vanilla hazard levels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HazardEstimate {
+    /// E.g. `enemy damage`.
+    pub category: &'static str,
+    /// The member the category is estimated from.
+    pub member: &'static str,
+    /// The value of the member averaged over player counts (or weighted bins).
+    pub average: f64,
+    /// The value of the member at Hazard 5, averaged the same way.
+    pub hazard_5_average: f64,
+    /// The hazard level with a comparable value, interpolated between hazard levels and
+    /// extrapolated past Hazard 1 and Hazard 5.
+    pub level: f64,
+}
+
+impl HazardEstimate {
+    /// E.g. `enemy damage is between Hazard 5 and Hazard 6 (EnemyDamageModifier averages 3.7,
+    /// Hazard 5 averages 3.1)`.
+    pub fn describe(&self) -> String {
+        let level = if self.level < 0.95 {
+            "below Hazard 1".to_string()
+        } else if (self.level - self.level.round()).abs() < 0.05 {
+            format!("about Hazard {}", self.level.round())
+        } else {
+            let lower = self.level.floor();
+            format!("between Hazard {lower} and Hazard {}", lower + 1.0)
+        };
+        format!(
+            "{} is {level} ({} averages {}, Hazard 5 averages {})",
+            self.category,
+            self.member,
+            round(self.average),
+            round(self.hazard_5_average)
+        )
+    }
+}
+
+/// Estimates for the categories the Custom Difficulty changes: enemy damage, enemy count and wave
+/// pacing.
+pub fn estimate_hazards(cd: &CustomDifficulty) -> Vec<HazardEstimate> {
+    let mut estimates = Vec::new();
+    let mut estimate = |category, member, average: Option<f64>, vanilla: fn(&Hazard) -> f64| {
+        let Some(average) = average else {
+            return;
+        };
+        let table = VANILLA_HAZARDS.map(|hazard| vanilla(&hazard));
+        estimates.push(HazardEstimate {
+            category,
+            member,
+            average,
+            hazard_5_average: table[4],
+            level: hazard_level(average, &table),
+        });
+    };
+
+    estimate(
+        "enemy damage",
+        "EnemyDamageModifier",
+        per_player_count_average(&cd.enemy_damage_modifier),
+        |hazard| average(&hazard.enemy_damage_modifier),
+    );
+    estimate(
+        "enemy count",
+        "EnemyCountModifier",
+        per_player_count_average(&cd.enemy_count_modifier),
+        |hazard| average(&hazard.enemy_count_modifier),
+    );
+    estimate(
+        "wave pacing",
+        "EnemyNormalWaveInterval",
+        weighted_average(&cd.enemy_normal_wave_interval),
+        |hazard| hazard.normal_wave_interval,
+    );
+
+    estimates
+}
+
+/// The fractional hazard level with the value, where `table` has the value of each hazard level.
+/// The table may increase or decrease with the hazard level, but must do so monotonically.
+fn hazard_level(val: f64, table: &[f64; 5]) -> f64 {
+    // The segment between two adjacent hazard levels the value is in, or the first or last segment
+    // to extrapolate from if it is outside the table.
+    let i = (0..table.len() - 1)
+        .find(|&i| {
+            let (a, b) = (table[i], table[i + 1]);
+            a.min(b) <= val && val <= a.max(b)
+        })
+        .unwrap_or_else(|| {
+            let increasing = table[4] > table[0];
+            if (val > table[4]) == increasing {
+                table.len() - 2
+            } else {
+                0
+            }
+        });
+    let (a, b) = (table[i], table[i + 1]);
+    let t = if a == b { 0.0 } else { (val - a) / (b - a) };
+    (i + 1) as f64 + t
+}
+
+/// The average over player counts, where an array with fewer values than player counts uses its
+/// last value for the remaining player counts. `None` if the member is not specified.
+fn per_player_count_average(val: &Spanned<ArrayOrSingleItem<f64>>) -> Option<f64> {
+    if !val.is_specified() {
+        return None;
+    }
+    let vals = match &val.val {
+        ArrayOrSingleItem::Array(vals) => vals.as_slice(),
+        ArrayOrSingleItem::SingleItem(val) => std::slice::from_ref(val),
+    };
+    let last = *vals.last()?;
+    let vals = (0..PLAYER_COUNTS)
+        .map(|i| vals.get(i).copied().unwrap_or(last))
+        .collect::<Vec<_>>();
+    Some(average(&vals))
+}
+
+/// The average of the bins' midpoints, weighted by the bins' weights. `None` if the member is not
+/// specified or has no weight.
+fn weighted_average(bins: &Spanned<Vec<Spanned<WeightedRange<usize>>>>) -> Option<f64> {
+    if !bins.is_specified() {
+        return None;
+    }
+    let total_weight = bins.iter().map(|bin| bin.weight.val).sum::<f64>();
+    if total_weight <= 0.0 {
+        return None;
+    }
+    let weighted_sum = bins
+        .iter()
+        .map(|bin| bin.weight.val * (bin.range.min.val + bin.range.max.val) as f64 / 2.0)
+        .sum::<f64>();
+    Some(weighted_sum / total_weight)
+}
+
+fn average(vals: &[f64]) -> f64 {
+    vals.iter().sum::<f64>() / vals.len() as f64
+}
+
+/// Rounds to two decimal places for display.
+fn round(val: f64) -> f64 {
+    (val * 100.0).round() / 100.0
+}
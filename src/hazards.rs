@@ -0,0 +1,80 @@
+//! Values of the vanilla hazard levels, which Custom Difficulties are usually modelled after.
+//!
+//! Hazard 5 matches the game's defaults (see `cd.schema.json`), except for the wave intervals. The
+//! lower hazard levels and the wave intervals are approximate, as the game does not expose them
+//! directly.
+
+/// The number of player counts values are given for, i.e. 1 to 4 players.
+pub const PLAYER_COUNTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hazard {
+    /// 1 to 5.
+    pub level: usize,
+    pub hazard_bonus: f64,
+    pub enemy_count_modifier: [f64; PLAYER_COUNTS],
+    pub enemy_damage_modifier: [f64; PLAYER_COUNTS],
+    pub enemy_damage_resistance: [f64; PLAYER_COUNTS],
+    pub max_active_enemies: usize,
+    pub resupply_cost: f64,
+    /// The average number of seconds between unannounced waves.
+    pub normal_wave_interval: f64,
+}
+
+pub const VANILLA_HAZARDS: [Hazard; 5] = [
+    Hazard {
+        level: 1,
+        hazard_bonus: 0.25,
+        enemy_count_modifier: [0.35, 0.35, 0.5, 0.6],
+        enemy_damage_modifier: [0.5, 0.55, 0.6, 0.65],
+        enemy_damage_resistance: [0.4, 0.5, 0.8, 1.0],
+        max_active_enemies: 60,
+        resupply_cost: 80.0,
+        normal_wave_interval: 300.0,
+    },
+    Hazard {
+        level: 2,
+        hazard_bonus: 0.5,
+        enemy_count_modifier: [0.45, 0.45, 0.65, 0.75],
+        enemy_damage_modifier: [0.9, 1.0, 1.1, 1.2],
+        enemy_damage_resistance: [0.5, 0.6, 0.9, 1.1],
+        max_active_enemies: 60,
+        resupply_cost: 80.0,
+        normal_wave_interval: 250.0,
+    },
+    Hazard {
+        level: 3,
+        hazard_bonus: 0.75,
+        enemy_count_modifier: [0.55, 0.55, 0.8, 0.95],
+        enemy_damage_modifier: [1.5, 1.6, 1.7, 1.8],
+        enemy_damage_resistance: [0.6, 0.7, 1.0, 1.3],
+        max_active_enemies: 60,
+        resupply_cost: 80.0,
+        normal_wave_interval: 200.0,
+    },
+    Hazard {
+        level: 4,
+        hazard_bonus: 1.0,
+        enemy_count_modifier: [0.7, 0.7, 1.0, 1.2],
+        enemy_damage_modifier: [2.2, 2.4, 2.6, 2.8],
+        enemy_damage_resistance: [0.7, 0.75, 1.1, 1.5],
+        max_active_enemies: 60,
+        resupply_cost: 80.0,
+        normal_wave_interval: 160.0,
+    },
+    Hazard {
+        level: 5,
+        hazard_bonus: 1.33,
+        enemy_count_modifier: [0.85, 0.85, 1.25, 1.5],
+        enemy_damage_modifier: [2.8, 3.0, 3.2, 3.4],
+        enemy_damage_resistance: [0.75, 0.8, 1.2, 1.7],
+        max_active_enemies: 60,
+        resupply_cost: 80.0,
+        normal_wave_interval: 130.0,
+    },
+];
+
+/// The vanilla hazard level, 1 to 5.
+pub fn vanilla_hazard(level: usize) -> Option<&'static Hazard> {
+    VANILLA_HAZARDS.iter().find(|hazard| hazard.level == level)
+}
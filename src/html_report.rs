@@ -0,0 +1,269 @@
+//! A standalone HTML page with the lint results, for sharing them with people who don't run CDLint
+//! themselves.
+//!
+//! The page shows the diagnostics grouped by lint, the hazard estimates, the Enemy Descriptor graph
+//! and the Custom Difficulty's source with the labelled spans highlighted.
+
+use std::fmt::Write as _;
+use std::ops::Range;
+
+use indexmap::IndexMap;
+
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{strip_ansi, ReportKind};
+use crate::driver::Input;
+use crate::hazard_estimate::HazardEstimate;
+use crate::reference_graph::{escape_xml as escape, ReferenceGraph};
+use crate::spanned::is_synthetic;
+use crate::summary::Summary;
+use crate::DiagnosticReport;
+
+/// Diagnostics which are not produced by a lint, e.g. syntax errors, are grouped under this name.
+const NOT_FROM_A_LINT: &str = "loading";
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 72em; padding: 0 1em; }
+code, pre { font-family: monospace; }
+details { border: 1px solid #ccc; border-radius: 4px; margin: 0.5em 0; padding: 0.25em 0.75em; }
+summary { cursor: pointer; font-weight: bold; }
+.diagnostic { border-left: 4px solid; margin: 0.75em 0; padding-left: 0.75em; }
+.diagnostic p { margin: 0.25em 0; }
+.error { border-color: #d33; }
+.warning { border-color: #d90; }
+.advice { border-color: #39c; }
+.level { font-weight: bold; }
+.error .level { color: #d33; }
+.warning .level { color: #d90; }
+.advice .level { color: #39c; }
+.source { background: #f6f6f6; border: 1px solid #ccc; overflow-x: auto; padding: 0.5em 0; }
+.source div { white-space: pre; }
+.source div:target { background: #ffd; }
+.line-number { color: #999; display: inline-block; padding-right: 1em; text-align: right; \
+width: 4em; user-select: none; }
+mark.error { background: #fcc; }
+mark.warning { background: #fe9; }
+mark.advice { background: #cdf; }
+";
+
+/// The whole page. `diagnostics` are the diagnostics which are not suppressed.
+pub fn render(
+    input: &Input,
+    cd: &CustomDifficulty,
+    diagnostics: &[&DiagnosticReport<'_>],
+    summary: &Summary,
+    estimates: &[HazardEstimate],
+) -> String {
+    let title = if cd.name.is_specified() && !cd.name.is_empty() {
+        format!("{} ({})", cd.name.val, input.path)
+    } else {
+        input.path.clone()
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "<title>CDLint report: {}</title>", escape(&title));
+    let _ = writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>");
+    let _ = writeln!(html, "<h1>CDLint report: {}</h1>", escape(&title));
+    let _ = writeln!(html, "<p>{}</p>", escape(&summary.counts()));
+
+    html.push_str("<h2>Diagnostics</h2>\n");
+    if diagnostics.is_empty() {
+        html.push_str("<p>No diagnostics.</p>\n");
+    }
+    let mut groups = IndexMap::<&str, Vec<&DiagnosticReport<'_>>>::new();
+    for &diagnostic in diagnostics {
+        groups
+            .entry(diagnostic.lint.unwrap_or(NOT_FROM_A_LINT))
+            .or_default()
+            .push(diagnostic);
+    }
+    for (group, diagnostics) in &groups {
+        // Groups with errors are open, since those are what needs fixing first.
+        let open = if diagnostics.iter().any(|d| d.kind == ReportKind::Error) {
+            " open"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            html,
+            "<details{open}>\n<summary>{} ({})</summary>",
+            escape(group),
+            diagnostics.len()
+        );
+        for diagnostic in diagnostics {
+            render_diagnostic(&mut html, &input.src, diagnostic);
+        }
+        html.push_str("</details>\n");
+    }
+
+    if !estimates.is_empty() {
+        html.push_str("<h2>Hazard estimates</h2>\n<ul>\n");
+        for estimate in estimates {
+            let _ = writeln!(html, "<li>{}</li>", escape(&estimate.describe()));
+        }
+        html.push_str(
+            "</ul>\n<p>Hazard levels are estimated from approximate vanilla values.</p>\n",
+        );
+    }
+
+    if !cd.enemy_descriptors.is_empty() {
+        html.push_str("<h2>Enemy Descriptors</h2>\n");
+        html.push_str(&ReferenceGraph::from_custom_difficulty(cd).to_svg());
+    }
+
+    html.push_str("<h2>Source</h2>\n");
+    render_source(&mut html, &input.src, diagnostics);
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_diagnostic(html: &mut String, src: &str, diagnostic: &DiagnosticReport<'_>) {
+    let level = level(&diagnostic.kind);
+    let line = line_of(src, diagnostic.primary_span().start);
+    let _ = writeln!(
+        html,
+        "<div class=\"diagnostic {level}\">\n<p><span class=\"level\">{level}</span>: {} \
+         <a href=\"#L{line}\">line {line}</a></p>",
+        escape(&strip_ansi(
+            diagnostic.message.as_deref().unwrap_or_default()
+        ))
+    );
+    let messages = diagnostic
+        .labels
+        .iter()
+        .filter_map(|label| label.message.as_deref())
+        .collect::<Vec<_>>();
+    if !messages.is_empty() {
+        html.push_str("<ul>\n");
+        for message in messages {
+            let _ = writeln!(html, "<li>{}</li>", escape(&strip_ansi(message)));
+        }
+        html.push_str("</ul>\n");
+    }
+    if let Some(help) = &diagnostic.help {
+        let _ = writeln!(html, "<p>help: {}</p>", escape(&strip_ansi(help)));
+    }
+    for suggestion in &diagnostic.suggestions {
+        let line = line_of(src, suggestion.span.start);
+        let _ = writeln!(
+            html,
+            "<p>help: {} <a href=\"#L{line}\">line {line}</a></p>",
+            escape(&suggestion.message())
+        );
+    }
+    for note in &diagnostic.notes {
+        let _ = write!(html, "<p>note: {}", escape(&strip_ansi(&note.message)));
+        if let Some(span) = note.file_span() {
+            let line = line_of(src, span.start);
+            let _ = write!(html, " <a href=\"#L{line}\">line {line}</a>");
+        }
+        html.push_str("</p>\n");
+    }
+    html.push_str("</div>\n");
+}
+
+/// The source with line numbers, where each line can be linked to as `#L<line>`. Labelled spans are
+/// highlighted by the most severe diagnostic labelling them, with the labels' messages as tooltips.
+fn render_source(html: &mut String, src: &str, diagnostics: &[&DiagnosticReport<'_>]) {
+    let spans = diagnostics
+        .iter()
+        .flat_map(|diagnostic| {
+            diagnostic.labels.iter().map(|label| {
+                let message = label.message.as_deref().or(diagnostic.message.as_deref());
+                (
+                    label.span.1.clone(),
+                    &diagnostic.kind,
+                    strip_ansi(message.unwrap_or_default()),
+                )
+            })
+        })
+        .filter(|(span, _, _)| {
+            !is_synthetic(span)
+                && span.start < span.end
+                && span.end <= src.len()
+                && src.is_char_boundary(span.start)
+                && src.is_char_boundary(span.end)
+        })
+        .collect::<Vec<_>>();
+
+    html.push_str("<pre class=\"source\"><code>");
+    let mut line_start = 0;
+    for (i, line) in src.split('\n').enumerate() {
+        let line_range = line_start..line_start + line.len();
+        line_start = line_range.end + 1;
+        let _ = write!(
+            html,
+            "<div id=\"L{0}\"><span class=\"line-number\">{0}</span>",
+            i + 1
+        );
+
+        // Split the line where highlighted spans start or end, so each segment is either
+        // highlighted as a whole or not at all.
+        let mut boundaries = vec![line_range.start, line_range.end];
+        for (span, _, _) in &spans {
+            for at in [span.start, span.end] {
+                if line_range.contains(&at) {
+                    boundaries.push(at);
+                }
+            }
+        }
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        for segment in boundaries.windows(2) {
+            let segment = segment[0]..segment[1];
+            let text = escape(src[segment.clone()].trim_end_matches('\r'));
+            let covering = spans
+                .iter()
+                .filter(|(span, _, _)| covers(span, &segment))
+                .collect::<Vec<_>>();
+            let Some(most_severe) = covering.iter().map(|(_, kind, _)| severity(kind)).max() else {
+                html.push_str(&text);
+                continue;
+            };
+            let class = ["advice", "warning", "error"][most_severe];
+            let title = covering
+                .iter()
+                .map(|(_, _, message)| message.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let _ = write!(
+                html,
+                "<mark class=\"{class}\" title=\"{}\">{text}</mark>",
+                escape(&title)
+            );
+        }
+        html.push_str("</div>");
+    }
+    html.push_str("</code></pre>\n");
+}
+
+fn covers(span: &Range<usize>, segment: &Range<usize>) -> bool {
+    span.start <= segment.start && segment.end <= span.end
+}
+
+fn severity(kind: &ReportKind<'_>) -> usize {
+    match kind {
+        ReportKind::Error => 2,
+        ReportKind::Warning => 1,
+        _ => 0,
+    }
+}
+
+fn level(kind: &ReportKind<'_>) -> &'static str {
+    match kind {
+        ReportKind::Error => "error",
+        ReportKind::Warning => "warning",
+        _ => "advice",
+    }
+}
+
+/// The 1-based line the byte offset is on.
+fn line_of(src: &str, offset: usize) -> usize {
+    let offset = offset.min(src.len());
+    src.as_bytes()[..offset]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count()
+        + 1
+}
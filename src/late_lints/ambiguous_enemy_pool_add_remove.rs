@@ -1,7 +1,6 @@
-use ariadne::{Color, Fmt, Label, Report, ReportKind};
-
 use crate::config::Config;
 use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
 use crate::spanned::Spanned;
 use crate::Diagnostics;
 
@@ -15,7 +14,7 @@ pub fn lint_ambiguous_enemy_pool_add_remove<'d>(
         for Spanned {
             val: add_name,
             span: add_span,
-        } in pool.val.add.val.iter()
+        } in pool.val.add.iter()
         {
             if let Some(Spanned {
                 val: remove_name,
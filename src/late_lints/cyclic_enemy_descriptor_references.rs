@@ -1,12 +1,11 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
-use anyhow::bail;
-use ariadne::{Color, Fmt, Label, Report, ReportKind};
+use anyhow::Context;
+use chumsky::span::SimpleSpan;
 use indexmap::{IndexMap, IndexSet};
 use petgraph::{
     algo::tarjan_scc,
-    dot::{Config as DotConfig, Dot},
     graph::{DiGraph, EdgeIndex, NodeIndex},
     prelude::EdgeRef,
     visit::IntoNodeReferences,
@@ -16,100 +15,102 @@ use tracing::*;
 
 use crate::config::Config;
 use crate::custom_difficulty::CustomDifficulty;
-use crate::late_lints::VANILLA_ENEMY_DESCRIPTORS;
+use crate::diagnostic::{Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion};
+use crate::reference_graph::ReferenceGraph;
+use crate::symbol::{Interner, Symbol};
 use crate::Diagnostics;
 
 /// Enemy descriptors may cyclically reference each other via their "Base" field, but this is not
 /// handled by Custom Difficulty and can crash the game.
 ///
-/// This lint assumes that there are no undefined Enemy Descriptors used in "Base" references.
+/// "Base" references to undefined Enemy Descriptors are left out, since they can't be part of a
+/// cycle.
 ///
 /// We first build a directed graph from Enemy Descriptor nodes and "based-on" directed edges. If
 /// we detect any cycle in the graph, then it can potentially crash the game so we should
 /// report an error for it.
 ///
 /// A graphviz plot can be generated optionally to show the "based-on" relationships between
-/// enemy descriptors.
+/// enemy descriptors. It is written next to the executable as `graph_name` with the extensions
+/// `.dot` and `.svg`.
+///
+/// The vanilla Enemy Descriptors and the ones in `extra_enemy_descriptors` are interned in
+/// `interner`, which the names in `cd` were interned in.
 pub fn lint_cyclic_enemy_descriptor_references<'d>(
     config: &Config,
     cd: &CustomDifficulty,
+    interner: &mut Interner,
     path: &'d String,
+    graph_name: &str,
     diag: &mut Diagnostics<'d>,
 ) -> anyhow::Result<()> {
-    // An unweighted directed graph consisting of Enemy Descriptor nodes and "based-on" directed
-    // edges.
-    let mut defined_descriptors: BTreeSet<String> = BTreeSet::new();
-    defined_descriptors.extend(VANILLA_ENEMY_DESCRIPTORS.iter().map(ToString::to_string));
-    defined_descriptors.extend(
+    // Every Enemy Descriptor a "Base" can name: the vanilla ones, the ones in
+    // `extra_enemy_descriptors` and the ones defined in the Custom Difficulty, wherever in it they
+    // are defined.
+    let mut vertices: IndexSet<Symbol> = IndexSet::new();
+    vertices.extend(
         config
             .extra_enemy_descriptors
             .iter()
-            .map(ToString::to_string),
+            .map(|ed| interner.intern(ed)),
+    );
+    vertices.extend(
+        config
+            .game_data()
+            .enemy_descriptors
+            .iter()
+            .map(|&ed| interner.intern(ed)),
     );
+    vertices.extend(cd.enemy_descriptors.keys().map(|name| name.val.clone()));
 
-    let mut graph: IndexMap<String, IndexSet<String>> = IndexMap::new();
+    // The "based-on" edges between them.
+    let mut graph: IndexMap<&Symbol, IndexSet<&Symbol>> = IndexMap::new();
 
     for (name, ed) in &cd.enemy_descriptors.val {
         let name = &name.val;
         let ed = &ed.val;
 
-        defined_descriptors.insert(name.to_string());
-
-        if !defined_descriptors.contains(&ed.base.val) {
-            // We haven't seen this descriptor, it is not a vanilla or custom descriptor,
-            // this might be an undefined reference that would be handled by another lint.
-            // Bail now!
-            bail!(format!(
-                "undefined Enemy Descriptor \"{}\" encountered",
-                ed.base.val
-            ));
+        if !ed.base.is_specified() {
+            // No "Base" means no "based-on" edge.
+            continue;
         }
 
-        graph
-            .entry(name.to_string())
-            .and_modify(|e| {
-                e.insert(ed.base.val.to_string());
-            })
-            .or_insert_with(|| IndexSet::from([ed.base.val.to_string()]));
+        let Some(base) = vertices.get(config.resolve_alias(&ed.base.val)) else {
+            // An undefined Enemy Descriptor can't be part of a cycle, and is reported by
+            // `undefined-enemy-descriptors`, so the edge is left out and the rest of the graph is
+            // still checked.
+            trace!(?name, base = ?ed.base.val, "skipping undefined base");
+            continue;
+        };
+
+        graph.entry(name).or_default().insert(base);
     }
 
     trace!("graph =\n{:#?}", graph);
 
-    // Assign IDs to each of the Enemy Descriptors.
-    let mut vertices = IndexSet::new();
-    vertices.extend(
-        config
-            .extra_enemy_descriptors
-            .iter()
-            .map(ToString::to_string),
-    );
-    vertices.extend(VANILLA_ENEMY_DESCRIPTORS.iter().map(ToString::to_string));
-    vertices.extend(graph.keys().map(ToString::to_string));
-    let mut string_edges = IndexSet::new();
-    for (name, adjs) in &graph {
-        for adj in adjs {
-            string_edges.insert((name.to_owned(), adj.to_owned()));
+    let mut name_edges = IndexSet::new();
+    for (&name, adjs) in &graph {
+        for &adj in adjs {
+            name_edges.insert((name, adj));
         }
     }
 
     trace!("vertices = {:#?}", vertices);
-    trace!("string_edges = {:#?}", string_edges);
-
-    let mut digraph: DiGraph<String, ()> = DiGraph::new();
-    let mut name_to_id: BTreeMap<String, NodeIndex> = BTreeMap::new();
-    let mut id_to_name: BTreeMap<NodeIndex, String> = BTreeMap::new();
-    for node in vertices {
-        let node_idx = digraph.add_node(node.to_string());
-        name_to_id.insert(node.to_string(), node_idx);
-        id_to_name.insert(node_idx, node.to_string());
+    trace!("name_edges = {:#?}", name_edges);
+
+    // Each node's weight is the name of its Enemy Descriptor.
+    let mut digraph: DiGraph<&Symbol, ()> = DiGraph::new();
+    let mut name_to_id: HashMap<&Symbol, NodeIndex> = HashMap::new();
+    for node in &vertices {
+        let node_idx = digraph.add_node(node);
+        name_to_id.insert(node, node_idx);
     }
 
     trace!("name_to_id = {:#?}", name_to_id);
-    trace!("id_to_name = {:#?}", id_to_name);
 
     let mut edges = IndexSet::new();
 
-    for (v, w) in string_edges {
+    for (v, w) in name_edges {
         trace!(?v, ?w);
         let edge_idx = digraph.add_edge(
             *name_to_id.get(&v).unwrap(),
@@ -121,26 +122,22 @@ pub fn lint_cyclic_enemy_descriptor_references<'d>(
 
     let mut cycles = elementary_circuits(&digraph);
     let self_cycles = cycles
-        .extract_if(|cycle| cycle.len() == 1)
+        .extract_if(.., |cycle| cycle.len() == 1)
         .map(|v| v[0])
         .collect::<Vec<_>>();
 
-    if !cycles.is_empty() {
-        diag.push(
-            Report::build(ReportKind::Error, path, cd.enemy_descriptors.span.start)
-                .with_message("cycle detected in Enemy Descriptor \"Base\" references")
-                .finish(),
-        );
-    }
-
     let unspanned_enemy_descriptors = cd
         .enemy_descriptors
         .val
         .iter()
         .map(|(name, ed)| {
             (
-                name.val.to_owned(),
-                (ed.val.base.val.to_owned(), name.span, ed.val.base.span),
+                &name.val,
+                (
+                    config.resolve_alias(&ed.val.base.val),
+                    name.span,
+                    ed.val.base.span,
+                ),
             )
         })
         .collect::<IndexMap<_, _>>();
@@ -153,17 +150,15 @@ pub fn lint_cyclic_enemy_descriptor_references<'d>(
             .find(|er| er.id() == self_cycle)
             .map(|er| er.source())
             .unwrap();
-        let name = id_to_name.get(&node_idx).unwrap();
-        let self_cycle_idx = unspanned_enemy_descriptors
-            .get_index_of(name.as_str())
-            .unwrap();
+        let name = digraph[node_idx];
+        let self_cycle_idx = unspanned_enemy_descriptors.get_index_of(&name).unwrap();
         let Some(rest) = unspanned_enemy_descriptors.get_range((self_cycle_idx + 1)..) else {
             break;
         };
         trace!(?rest);
 
         for (other_name, (based_on, other_name_span, ed_base_span)) in rest {
-            if based_on == name {
+            if *based_on == name.as_str() {
                 diag.push(
                     Report::build(ReportKind::Error, path, other_name_span.start)
                         .with_message(format!(
@@ -190,66 +185,106 @@ pub fn lint_cyclic_enemy_descriptor_references<'d>(
         }
     }
 
-    for (i, cycle) in cycles.iter().enumerate() {
-        let mut cycle_string = String::new();
-
-        let mut cycle_nodes = IndexSet::new();
-        cycle.iter().for_each(|edge_idx| {
-            let (src, dst) = digraph
-                .edge_references()
-                .find(|er| er.id() == *edge_idx)
-                .map(|er| (er.source(), er.target()))
+    let base_span = |name: &Symbol| unspanned_enemy_descriptors[name].2;
+    // Each cycle as the Enemy Descriptors in it, each based on the next one and the last one on the
+    // first, starting with the one whose `Base` comes first in the file.
+    let mut cycles = cycles
+        .iter()
+        .map(|cycle| {
+            let mut names = cycle
+                .iter()
+                .map(|&edge_idx| digraph[digraph.edge_endpoints(edge_idx).unwrap().0])
+                .collect::<Vec<_>>();
+            let first = (0..names.len())
+                .min_by_key(|&i| base_span(names[i]).start)
                 .unwrap();
-
-            cycle_nodes.insert(src);
-            cycle_nodes.insert(dst);
-        });
-
-        for (j, node_idx) in cycle_nodes.iter().enumerate() {
-            let name = id_to_name.get(node_idx).unwrap();
-            let partial = if j == 0 {
-                format!("\"{}\"", name.fg(Color::Blue))
-            } else {
-                format!(" -> \"{}\"", name.fg(Color::Blue))
-            };
-
-            cycle_string.push_str(&partial);
-        }
-
-        cycle_string.push_str(&format!(" -> \"{}\"", {
-            let node_idx = cycle_nodes.first().unwrap();
-            let name = id_to_name.get(node_idx).unwrap();
-            name.fg(Color::Blue)
-        }));
-
-        diag.push(
-            Report::build(ReportKind::Error, path, cd.enemy_descriptors.span.start)
-                .with_message(format!("cycle [{}]: {}", i + 1, cycle_string))
-                .finish(),
-        );
+            names.rotate_left(first);
+            let mut starts = names
+                .iter()
+                .map(|&name| base_span(name).start)
+                .collect::<Vec<_>>();
+            starts.sort_unstable();
+            (starts, names)
+        })
+        .collect::<Vec<_>>();
+    // Reported in the order their `Base` references appear in the file.
+    cycles.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (_, names) in cycles {
+        diag.push(cycle_report(path, &names, base_span));
     }
 
     if config.generate_cyclic_reference_graph {
-        trace!(
-            "{:?}",
-            Dot::with_config(&digraph, &[DotConfig::EdgeNoLabel])
-        );
+        let graph = ReferenceGraph::from_custom_difficulty(cd);
+        let dot = graph.to_dot();
+        trace!("{}", dot);
 
         let exe_path = std::env::current_exe()?;
         let out_dir = exe_path.parent().unwrap();
-        let out_file = out_dir.join("cyclic_enemy_descriptor_references.dot");
-        std::fs::write(
-            out_file,
-            format!(
-                "{:?}",
-                Dot::with_config(&digraph, &[DotConfig::EdgeNoLabel])
-            ),
-        )?;
+        for (extension, contents) in [("dot", dot), ("svg", graph.to_svg())] {
+            let out_path = out_dir.join(format!("{graph_name}.{extension}"));
+            std::fs::write(&out_path, contents)
+                .with_context(|| format!("failed to write `{}`", out_path.display()))?;
+        }
     }
 
     Ok(())
 }
 
+/// Reports the cycle of Enemy Descriptors, each based on the next one and the last one on the
+/// first, labelling each `Base` in the cycle. Breaking the cycle is suggested at the last `Base` in
+/// the file, which closes the cycle.
+fn cycle_report<'d>(
+    path: &'d String,
+    names: &[&Symbol],
+    base_span: impl Fn(&Symbol) -> SimpleSpan,
+) -> Report<'d> {
+    let cycle = names
+        .iter()
+        .chain(names.first())
+        .map(|name| format!("\"{}\"", name.fg(Color::Blue)))
+        .collect::<Vec<_>>()
+        .join(" -> ");
+
+    let mut bases = names
+        .iter()
+        .enumerate()
+        .map(|(i, &name)| (name, names[(i + 1) % names.len()], base_span(name)))
+        .collect::<Vec<_>>();
+    bases.sort_by_key(|(_, _, span)| span.start);
+    let (closing, _, closing_span) = bases[bases.len() - 1];
+
+    let labels = bases.iter().map(|&(name, base, span)| {
+        let message = format!(
+            "\"{}\" is based on \"{}\" here",
+            name.fg(Color::Blue),
+            base.fg(Color::Blue)
+        );
+        let message = if name == closing {
+            format!("{message}, which closes the cycle")
+        } else {
+            message
+        };
+        Label::new((path, span.into_range()))
+            .with_color(Color::Red)
+            .with_message(message)
+    });
+    Report::build(ReportKind::Error, path, bases[0].2.start)
+        .with_message(format!(
+            "cycle in Enemy Descriptor \"Base\" references: {cycle}"
+        ))
+        .with_labels(labels)
+        .with_help(format!(
+            "break the cycle, e.g. by basing \"{}\" on an Enemy Descriptor outside of it",
+            closing.fg(Color::Blue)
+        ))
+        .with_suggestion(Suggestion::new(
+            closing_span.into_range(),
+            "\"<Enemy Descriptor outside the cycle>\"",
+            Applicability::HasPlaceholders,
+        ))
+        .finish()
+}
+
 index_vec::define_index_type! {
     struct NameIdx = usize;
 }
@@ -301,7 +336,7 @@ fn johnson_cycle_search(
                         blocked.remove(&node);
 
                         if let Some(nodes) = blocked_subgraph.remove(&node) {
-                            unblock.extend(nodes.into_iter());
+                            unblock.extend(nodes);
                         }
                     }
                 }
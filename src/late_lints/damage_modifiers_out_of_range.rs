@@ -0,0 +1,68 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// Damage modifiers such as `FriendlyFireModifier` are multipliers on the damage dealt, so values
+/// above 1 or below 0 are usually typos, e.g. entering `50` instead of `0.5`.
+pub fn lint_damage_modifiers_out_of_range<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let check = |diag: &mut Diagnostics<'d>, field: &str, modifier: &Spanned<f64>| {
+        if !modifier.is_specified() || (0.0..=1.0).contains(&modifier.val) {
+            return;
+        }
+
+        let mut report = Report::build(ReportKind::Warning, path, modifier.span.start)
+            .with_message(format!(
+                "\"{}\" of {} is outside the range [0, 1]",
+                field.fg(Color::Blue),
+                modifier.val.fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, modifier.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!(
+                        "this means {}% of the normal damage",
+                        (modifier.val * 100.0).fg(Color::Blue)
+                    )),
+            )
+            .with_note(format!(
+                "\"{}\" is a multiplier, e.g. 0.5 means half of the normal damage",
+                field.fg(Color::Blue)
+            ));
+
+        let percentage = modifier.val / 100.0;
+        if (0.0..=1.0).contains(&percentage) {
+            report.set_help(format!(
+                "if this is a percentage, use {} instead",
+                percentage.fg(Color::Blue)
+            ));
+        }
+
+        diag.push(report.finish());
+    };
+
+    check(diag, "FriendlyFireModifier", &cd.friendly_fire_modifier);
+
+    let escort_mule = &cd.escort_mule.val;
+    check(
+        diag,
+        "EscortMule.FriendlyFireModifier",
+        &escort_mule.friendly_fire_modifier,
+    );
+    check(
+        diag,
+        "EscortMule.NeutralDamageModifier",
+        &escort_mule.neutral_damage_modifier,
+    );
+    check(
+        diag,
+        "EscortMule.BigHitDamageModifier",
+        &escort_mule.big_hit_damage_modifier,
+    );
+}
@@ -0,0 +1,58 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// Long `Base` chains (e.g. `ED_A` based on `ED_B` based on `ED_C` ...) are hard to reason about,
+/// because the values of an Enemy Descriptor may come from any of its bases. Warn when the chain is
+/// longer than `max_enemy_descriptor_base_depth`.
+///
+/// Cyclic `Base` chains are reported by `cyclic-enemy-descriptor-references` instead.
+pub fn lint_deep_enemy_descriptor_base_chains<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let max_depth = config.max_enemy_descriptor_base_depth;
+
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        let Some(chain) = cd.base_chain(&ed_name.val) else {
+            continue;
+        };
+
+        let depth = chain.len() - 1;
+        if depth <= max_depth {
+            continue;
+        }
+
+        let chain_desc = chain
+            .iter()
+            .map(|name| format!("\"{}\"", name.fg(Color::Blue)))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        let report = Report::build(ReportKind::Warning, path, ed_name.span.start)
+            .with_message(format!(
+                "Enemy Descriptor \"{}\" has a \"{}\" chain of depth {}, which exceeds the limit of {}",
+                ed_name.as_str().fg(Color::Blue),
+                "Base".fg(Color::Blue),
+                depth,
+                max_depth
+            ))
+            .with_label(
+                Label::new((path, ed_def.val.base.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!("\"{}\" chain starts here", "Base".fg(Color::Blue))),
+            )
+            .with_note(format!("the full chain is {chain_desc}"))
+            .with_help(format!(
+                "consider basing \"{}\" directly on \"{}\", or raise \
+                `max_enemy_descriptor_base_depth` in the config",
+                ed_name.as_str().fg(Color::Blue),
+                chain[chain.len() - 1].fg(Color::Blue)
+            ))
+            .finish();
+        diag.push(report);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// The enemy pool is filled up to `MinPoolSize` Enemy Descriptors in order: common enemies, then
+/// `DisruptiveEnemyPoolCount` disruptive enemies, then special enemies. If the disruptive count can
+/// exceed the pool size, the pool may end up with only disruptive enemies and no room for common or
+/// special enemies.
+pub fn lint_disruptive_count_exceeds_pool_size<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let disruptive_max = &cd.disruptive_enemy_pool_count.val.max;
+    let min_pool_size = &cd.min_pool_size;
+    if !disruptive_max.is_specified() || !min_pool_size.is_specified() {
+        return;
+    }
+
+    if disruptive_max.val <= min_pool_size.val {
+        return;
+    }
+
+    diag.push(
+        Report::build(ReportKind::Warning, path, disruptive_max.span.start)
+            .with_message(format!(
+                "\"{}\" can exceed \"{}\"",
+                "DisruptiveEnemyPoolCount".fg(Color::Blue),
+                "MinPoolSize".fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, disruptive_max.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!(
+                        "up to {} disruptive Enemy Descriptors are picked",
+                        disruptive_max.val.fg(Color::Blue)
+                    )),
+            )
+            .with_label(
+                Label::new((path, min_pool_size.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!(
+                        "but the enemy pool only holds {} Enemy Descriptors",
+                        min_pool_size.val.fg(Color::Blue)
+                    )),
+            )
+            .with_note(
+                "the enemy pool is filled with common enemies, then disruptive enemies, then \
+                special enemies",
+            )
+            .with_help(format!(
+                "lower the \"{}\" of \"{}\" to at most {}",
+                "max".fg(Color::Blue),
+                "DisruptiveEnemyPoolCount".fg(Color::Blue),
+                min_pool_size.val.fg(Color::Blue)
+            ))
+            .finish(),
+    );
+}
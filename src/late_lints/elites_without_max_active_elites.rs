@@ -0,0 +1,66 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// If `MaxActiveElites` is 0, no elite can ever spawn, so elite-related tuning such as
+/// `EliteCooldown` or Enemy Descriptors with `"Elite": true` has no effect.
+pub fn lint_elites_without_max_active_elites<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let max_active_elites = &cd.max_active_elites;
+    if !max_active_elites.is_specified() || max_active_elites.val != 0 {
+        return;
+    }
+
+    let mut labels = Vec::new();
+    if cd.elite_cooldown.is_specified() {
+        labels.push(
+            Label::new((path, cd.elite_cooldown.span.into_range()))
+                .with_color(Color::Yellow)
+                .with_message(format!(
+                    "\"{}\" has no effect",
+                    "EliteCooldown".fg(Color::Blue)
+                )),
+        );
+    }
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        let elite = &ed_def.val.elite;
+        if elite.is_specified() && elite.val {
+            labels.push(
+                Label::new((path, elite.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!(
+                        "\"{}\" can never spawn as an elite",
+                        ed_name.as_str().fg(Color::Blue)
+                    )),
+            );
+        }
+    }
+
+    if labels.is_empty() {
+        return;
+    }
+
+    diag.push(
+        Report::build(ReportKind::Warning, path, max_active_elites.span.start)
+            .with_message(format!(
+                "elite settings are present but \"{}\" is 0",
+                "MaxActiveElites".fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, max_active_elites.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message("no elites can ever spawn"),
+            )
+            .with_labels(labels)
+            .with_help(format!(
+                "raise \"{}\" above 0, or remove the elite settings",
+                "MaxActiveElites".fg(Color::Blue)
+            ))
+            .finish(),
+    );
+}
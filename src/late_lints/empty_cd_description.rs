@@ -0,0 +1,25 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+pub fn lint_empty_cd_description<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    // A missing `Description` is fine, only an explicitly empty one is suspicious.
+    if cd.description.is_specified() && cd.description.is_empty() {
+        diag.push(
+            Report::build(ReportKind::Warning, path, cd.description.span.start)
+                .with_message("custom difficulty description is empty")
+                .with_label(
+                    Label::new((path, cd.description.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message("the description is shown when selecting the difficulty"),
+                )
+                .finish(),
+        );
+    }
+}
@@ -1,7 +1,6 @@
-use ariadne::{Color, Label, Report, ReportKind};
-
 use crate::config::Config;
 use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Label, Report, ReportKind};
 use crate::Diagnostics;
 
 pub fn lint_empty_cd_name<'d>(
@@ -10,7 +9,7 @@ pub fn lint_empty_cd_name<'d>(
     path: &'d String,
     diag: &mut Diagnostics<'d>,
 ) {
-    if cd.name.val.is_empty() {
+    if cd.name.is_empty() {
         diag.push(
             Report::build(ReportKind::Warning, path, cd.name.span.start)
                 .with_message("custom difficulty name is empty")
@@ -0,0 +1,68 @@
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// An enemy pool which is cleared must have at least one Enemy Descriptor left in it after all the
+/// additions and removals are applied, otherwise the game has nothing to pull from when it tries
+/// to spawn enemies from that pool.
+///
+/// We can only compute the effective pool if it is cleared, because we don't know the vanilla
+/// contents of each pool.
+pub fn lint_empty_effective_enemy_pool<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let check_enemy_pool = |diag: &mut Diagnostics<'d>, pool: &Spanned<EnemyPool>, name: &str| {
+        let Some(effective_pool) = pool.val.effective_pool() else {
+            return;
+        };
+
+        if !effective_pool.is_empty() {
+            return;
+        }
+
+        let mut report = Report::build(ReportKind::Error, path, pool.span.start)
+            .with_message(format!(
+                "enemy pool \"{}\" is empty after applying \"{}\", \"{}\" and \"{}\"",
+                name.fg(Color::Blue),
+                "clear".fg(Color::Blue),
+                "add".fg(Color::Blue),
+                "remove".fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, pool.val.clear.span.into_range()))
+                    .with_color(Color::Red)
+                    .with_message("the pool is cleared here"),
+            );
+
+        if pool.val.add.is_empty() {
+            report.set_help(format!(
+                "add at least one Enemy Descriptor to \"{}\", or don't clear the pool",
+                "add".fg(Color::Blue)
+            ));
+        } else {
+            if pool.val.remove.is_specified() {
+                report.add_span_note(
+                    pool.val.remove.span.into_range(),
+                    "every added Enemy Descriptor is removed again here",
+                );
+            }
+            report.set_help(format!(
+                "an empty enemy pool breaks enemy spawning at runtime; keep at least one Enemy Descriptor in \"{}\"",
+                name.fg(Color::Blue)
+            ));
+        }
+
+        diag.push(report.finish());
+    };
+
+    check_enemy_pool(diag, &cd.enemy_pool, "EnemyPool");
+    check_enemy_pool(diag, &cd.common_enemies, "CommonEnemies");
+    check_enemy_pool(diag, &cd.disruptive_enemies, "DisruptiveEnemies");
+    check_enemy_pool(diag, &cd.special_enemies, "SpecialEnemies");
+    check_enemy_pool(diag, &cd.stationary_enemies, "StationaryEnemies");
+}
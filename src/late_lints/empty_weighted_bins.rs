@@ -0,0 +1,47 @@
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, WeightedBinWeights};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// A weighted bin array which is present but empty (e.g. `"EnemyWaveInterval": []`) leaves the
+/// game with nothing to pick from, which leads to degenerate behavior. This is different from the
+/// member being absent, in which case the game just uses its default bins.
+pub fn lint_empty_weighted_bins<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    for (member_name, bins) in cd.weighted_bin_weights() {
+        check_bins(diag, path, &bins, member_name);
+    }
+}
+
+fn check_bins<'d>(
+    diag: &mut Diagnostics<'d>,
+    path: &'d String,
+    bins: &WeightedBinWeights<'_>,
+    member_name: &str,
+) {
+    if !bins.is_specified() || !bins.is_empty() {
+        return;
+    }
+
+    diag.push(
+        Report::build(ReportKind::Warning, path, bins.span.start)
+            .with_message(format!(
+                "\"{}\" is an empty array of weighted bins",
+                member_name.fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, bins.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message("there are no bins to pick from"),
+            )
+            .with_help(format!(
+                "add at least one weighted bin, or remove \"{}\" to keep the default bins",
+                member_name.fg(Color::Blue)
+            ))
+            .finish(),
+    );
+}
@@ -0,0 +1,70 @@
+use indexmap::IndexMap;
+
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::symbol::Symbol;
+use crate::Diagnostics;
+
+/// The enemy pool is filled from `CommonEnemies`, then `DisruptiveEnemies`, then `SpecialEnemies`.
+/// Adding the same Enemy Descriptor to more than one of these pools has confusing interactions
+/// with the pool fill order, e.g. it can take up multiple slots of the enemy pool or make a
+/// "special" enemy as common as any common enemy.
+///
+/// This is a heuristic and it is not clear how bad the interactions are in game, so this is only
+/// reported as advice.
+pub fn lint_enemy_descriptor_in_multiple_pools<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let mut additions: IndexMap<&str, Vec<(&str, &Spanned<Symbol>)>> = IndexMap::new();
+
+    let pools: [(&Spanned<EnemyPool>, &str); 3] = [
+        (&cd.common_enemies, "CommonEnemies"),
+        (&cd.disruptive_enemies, "DisruptiveEnemies"),
+        (&cd.special_enemies, "SpecialEnemies"),
+    ];
+
+    for (pool, pool_name) in pools {
+        for name in &pool.val.add.val {
+            let pools = additions.entry(name.as_str()).or_default();
+            // Multiple additions to the same pool is a different problem.
+            if !pools.iter().any(|(p, _)| *p == pool_name) {
+                pools.push((pool_name, name));
+            }
+        }
+    }
+
+    for (name, pools) in additions {
+        if pools.len() < 2 {
+            continue;
+        }
+
+        let pool_names = pools
+            .iter()
+            .map(|(pool_name, _)| format!("\"{}\"", pool_name.fg(Color::Blue)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut report = Report::build(ReportKind::Advice, path, pools[0].1.span.start)
+            .with_message(format!(
+                "Enemy Descriptor \"{}\" is added to multiple enemy pools: {}",
+                name.fg(Color::Blue),
+                pool_names
+            ))
+            .with_note(
+                "the enemy pool is filled from common enemies, then disruptive enemies, then special enemies, so the same Enemy Descriptor may be picked more than once",
+            );
+        for (pool_name, add) in &pools {
+            report.add_label(
+                Label::new((path, add.span.into_range()))
+                    .with_color(Color::Cyan)
+                    .with_message(format!("added to \"{}\" here", pool_name.fg(Color::Blue))),
+            );
+        }
+        diag.push(report.finish());
+    }
+}
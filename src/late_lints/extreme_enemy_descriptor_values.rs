@@ -0,0 +1,70 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// `Scale` and `TimeDilation` values orders of magnitude outside of what vanilla Enemy Descriptors
+/// use can crash or visually break the game. The sane ranges are configurable.
+///
+/// Non-positive values are already reported by `out-of-bounds-values`.
+pub fn lint_extreme_enemy_descriptor_values<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let check = |diag: &mut Diagnostics<'d>,
+                 ed_name: &str,
+                 field: &str,
+                 val: &Spanned<f64>,
+                 [min, max]: [f64; 2],
+                 config_key: &str| {
+        if !val.is_specified() || val.val <= 0.0 || (min..=max).contains(&val.val) {
+            return;
+        }
+
+        diag.push(
+            Report::build(ReportKind::Warning, path, val.span.start)
+                .with_message(format!(
+                    "extreme \"{}\" value {} for Enemy Descriptor \"{}\"",
+                    field.fg(Color::Blue),
+                    val.val.fg(Color::Blue),
+                    ed_name.fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, val.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message(format!(
+                            "this is outside the sane range [{}, {}]",
+                            min.fg(Color::Blue),
+                            max.fg(Color::Blue)
+                        )),
+                )
+                .with_note("extreme values can crash or visually break the game")
+                .with_help(format!(
+                    "if this is intended, widen `{config_key}` in the config"
+                ))
+                .finish(),
+        );
+    };
+
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        check(
+            diag,
+            &ed_name.val,
+            "Scale",
+            &ed_def.val.scale,
+            config.sane_scale_range,
+            "sane_scale_range",
+        );
+        check(
+            diag,
+            &ed_name.val,
+            "TimeDilation",
+            &ed_def.val.time_dilation,
+            config.sane_time_dilation_range,
+            "sane_time_dilation_range",
+        );
+    }
+}
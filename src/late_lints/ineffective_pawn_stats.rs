@@ -0,0 +1,57 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::game_data::PawnStatTarget;
+use crate::late_lints::vanilla_root;
+use crate::Diagnostics;
+
+/// Some pawn stats can't affect the Enemy Descriptor they are set on, e.g. mining related stats
+/// are only used by dwarves, and movement speed doesn't matter for stationary enemies.
+///
+/// Which pawn stats affect which enemies is not documented, so this lint is in the nursery.
+pub fn lint_ineffective_pawn_stats<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let game_data = config.game_data();
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        let is_stationary = vanilla_root(config, cd, &ed_name.val)
+            .is_some_and(|root| game_data.stationary_enemy_descriptors.contains(&root));
+
+        for (stat, val) in &ed_def.val.pawn_stats.val.0 {
+            let Some((_, target)) = game_data
+                .pawn_stat_targets
+                .iter()
+                .find(|(name, _)| *name == stat.val)
+            else {
+                continue;
+            };
+
+            let reason = match target {
+                PawnStatTarget::PlayersOnly => "this pawn stat is only used by dwarves",
+                PawnStatTarget::MobileEnemies if is_stationary => {
+                    "this pawn stat only affects enemies which move around"
+                }
+                PawnStatTarget::MobileEnemies => continue,
+            };
+
+            diag.push(
+                Report::build(ReportKind::Warning, path, stat.span.start)
+                    .with_message(format!(
+                        "pawn stat \"{}\" has no effect on Enemy Descriptor \"{}\"",
+                        stat.as_str().fg(Color::Blue),
+                        ed_name.as_str().fg(Color::Blue)
+                    ))
+                    .with_label(
+                        Label::new((path, stat.span.start..val.span.end))
+                            .with_color(Color::Yellow)
+                            .with_message(reason),
+                    )
+                    .with_help(format!("remove \"{}\"", stat.as_str().fg(Color::Blue)))
+                    .finish(),
+            );
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// Long custom difficulty names get truncated in the in-game UI.
+pub fn lint_long_cd_name<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let len = cd.name.val.chars().count();
+    if len <= config.max_cd_name_length {
+        return;
+    }
+
+    diag.push(
+        Report::build(ReportKind::Warning, path, cd.name.span.start)
+            .with_message(format!(
+                "custom difficulty name is {} characters long, which exceeds the limit of {}",
+                len.fg(Color::Blue),
+                config.max_cd_name_length.fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, cd.name.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message("this may get truncated in the in-game UI"),
+            )
+            .with_help("consider moving details into the \"Description\" instead")
+            .finish(),
+    );
+}
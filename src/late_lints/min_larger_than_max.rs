@@ -1,13 +1,13 @@
-use ariadne::{Color, Fmt, Label, Report, ReportKind};
-
 use crate::config::Config;
 use crate::custom_difficulty::{CustomDifficulty, Range, WeightedRange};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
 use crate::spanned::Spanned;
 use crate::Diagnostics;
 
 /// This lint goes through all `Range`s and `WeightedRange`s (by implication) to find any cases
 /// where `min > max`. This is extremely confusing, and its behavior in Custom Difficulty and in
-/// game isn't very clear or obvious.
+/// game isn't very clear or obvious. The same goes for `MinSpawnCount` and `MaxSpawnCount` of each
+/// Enemy Descriptor.
 pub fn lint_min_larger_than_max<'d>(
     _config: &Config,
     cd: &CustomDifficulty,
@@ -120,4 +120,45 @@ pub fn lint_min_larger_than_max<'d>(
         .iter()
         .for_each(|r| weighted_float_range_check(diag, r));
     int_range_check(diag, &cd.disruptive_enemy_pool_count);
+
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        let min = &ed_def.val.min_spawn_count;
+        let max = &ed_def.val.max_spawn_count;
+
+        // Either of them not being specified means the value comes from the "Base", which we don't
+        // know about.
+        if !min.is_specified() || !max.is_specified() {
+            continue;
+        }
+
+        if min.val > max.val {
+            diag.push(
+                Report::build(ReportKind::Warning, path, min.span.start)
+                    .with_message(format!(
+                        "{} in Enemy Descriptor \"{}\", which may lead to surprising behavior in Custom Difficulty and in game",
+                        "MinSpawnCount > MaxSpawnCount".fg(Color::Blue),
+                        ed_name.as_str().fg(Color::Blue)
+                    ))
+                    .with_label(
+                        Label::new((path, min.span.into_range()))
+                            .with_color(Color::Yellow)
+                            .with_message(format!(
+                                "\"{}\" is {}",
+                                "MinSpawnCount".fg(Color::Blue),
+                                min.val.fg(Color::Blue)
+                            )),
+                    )
+                    .with_label(
+                        Label::new((path, max.span.into_range()))
+                            .with_color(Color::Yellow)
+                            .with_message(format!(
+                                "\"{}\" is {}",
+                                "MaxSpawnCount".fg(Color::Blue),
+                                max.val.fg(Color::Blue)
+                            )),
+                    )
+                    .finish(),
+            );
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::game_data::GameData;
+use crate::{edit_distance, Diagnostics};
+
+/// A new Enemy Descriptor (i.e. one that is neither vanilla nor user-specified via
+/// `extra_enemy_descriptors`) must specify a `Base` to copy its values from, otherwise there is
+/// nothing to create the new Enemy Descriptor from.
+pub fn lint_missing_enemy_descriptor_base<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        if config.game_data().is_vanilla_enemy_descriptor(&ed_name.val)
+            || config.is_extra_enemy_descriptor(&ed_name.val)
+        {
+            continue;
+        }
+
+        if ed_def.val.base.is_specified() {
+            continue;
+        }
+
+        let mut report = Report::build(ReportKind::Error, path, ed_name.span.start)
+            .with_message(format!(
+                "new Enemy Descriptor \"{}\" is missing a \"{}\"",
+                ed_name.as_str().fg(Color::Blue),
+                "Base".fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, ed_name.span.into_range()))
+                    .with_color(Color::Red)
+                    .with_message(format!(
+                        "\"{}\" is defined here",
+                        ed_name.as_str().fg(Color::Blue)
+                    )),
+            )
+            .with_note(format!(
+                "\"{}\" is required when defining a new Enemy Descriptor",
+                "Base".fg(Color::Blue)
+            ));
+
+        if let Some(suggestion) = suggest_vanilla_base(config.game_data(), &ed_name.val) {
+            report.set_help(format!(
+                "consider basing it on a vanilla Enemy Descriptor, e.g. \"{}\": \"{}\"",
+                "Base".fg(Color::Blue),
+                suggestion.fg(Color::Blue)
+            ));
+        }
+
+        diag.push(report.finish());
+    }
+}
+
+/// Custom Enemy Descriptors are commonly named after the vanilla Enemy Descriptor they are derived
+/// from, e.g. `ED_Spider_Grunt_red`, so we prefer the longest vanilla Enemy Descriptor that is a
+/// prefix of the name, and otherwise fall back to the closest match by edit distance.
+fn suggest_vanilla_base(game_data: &GameData, name: &str) -> Option<&'static str> {
+    if let Some(prefix) = game_data
+        .enemy_descriptors
+        .iter()
+        .filter(|vanilla| name.starts_with(*vanilla))
+        .max_by_key(|vanilla| vanilla.len())
+    {
+        return Some(prefix);
+    }
+
+    let suggestion = edit_distance::find_best_match_for_name_with_substrings(
+        game_data.enemy_descriptors,
+        name,
+        None,
+    )?;
+    game_data
+        .enemy_descriptors
+        .iter()
+        .find(|vanilla| **vanilla == suggestion)
+        .copied()
+}
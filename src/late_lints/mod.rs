@@ -1,19 +1,199 @@
-// Data
-mod vanilla_enemy_descriptors;
-
-pub(crate) use vanilla_enemy_descriptors::VANILLA_ENEMY_DESCRIPTORS;
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::lint::{Lint, LintGroup};
+use crate::Diagnostics;
 
 // Late lints
 mod ambiguous_enemy_pool_add_remove;
 mod cyclic_enemy_descriptor_references;
+mod damage_modifiers_out_of_range;
+mod deep_enemy_descriptor_base_chains;
+mod disruptive_count_exceeds_pool_size;
+mod elites_without_max_active_elites;
+mod empty_cd_description;
 mod empty_cd_name;
+mod empty_effective_enemy_pool;
+mod empty_weighted_bins;
+mod enemy_descriptor_in_multiple_pools;
+mod extreme_enemy_descriptor_values;
+mod ineffective_pawn_stats;
+mod long_cd_name;
 mod min_larger_than_max;
+mod missing_enemy_descriptor_base;
+mod pool_size_exceeds_available_enemies;
+mod redundant_enemy_descriptor_overrides;
+mod rich_text_markup;
+mod shadowed_vanilla_enemy_descriptors;
+mod stationary_enemy_pool_mismatch;
 mod undefined_enemy_descriptors;
+mod unknown_pawn_stats;
+mod unspawnable_enemy_descriptors;
 mod unused_custom_enemy_descriptors;
+mod veteran_ratio_out_of_range;
+mod zero_weight_bins;
 
 pub(crate) use ambiguous_enemy_pool_add_remove::*;
 pub(crate) use cyclic_enemy_descriptor_references::*;
+pub(crate) use damage_modifiers_out_of_range::*;
+pub(crate) use deep_enemy_descriptor_base_chains::*;
+pub(crate) use disruptive_count_exceeds_pool_size::*;
+pub(crate) use elites_without_max_active_elites::*;
+pub(crate) use empty_cd_description::*;
 pub(crate) use empty_cd_name::*;
+pub(crate) use empty_effective_enemy_pool::*;
+pub(crate) use empty_weighted_bins::*;
+pub(crate) use enemy_descriptor_in_multiple_pools::*;
+pub(crate) use extreme_enemy_descriptor_values::*;
+pub(crate) use ineffective_pawn_stats::*;
+pub(crate) use long_cd_name::*;
 pub(crate) use min_larger_than_max::*;
+pub(crate) use missing_enemy_descriptor_base::*;
+pub(crate) use pool_size_exceeds_available_enemies::*;
+pub(crate) use redundant_enemy_descriptor_overrides::*;
+pub(crate) use rich_text_markup::*;
+pub(crate) use shadowed_vanilla_enemy_descriptors::*;
+pub(crate) use stationary_enemy_pool_mismatch::*;
 pub(crate) use undefined_enemy_descriptors::*;
+pub(crate) use unknown_pawn_stats::*;
+pub(crate) use unspawnable_enemy_descriptors::*;
 pub(crate) use unused_custom_enemy_descriptors::*;
+pub(crate) use veteran_ratio_out_of_range::*;
+pub(crate) use zero_weight_bins::*;
+
+pub(crate) type LateLint =
+    Lint<for<'d> fn(&Config, &CustomDifficulty, &'d String, &mut Diagnostics<'d>)>;
+
+/// Late lints in the order they are run, except for `cyclic-enemy-descriptor-references` which is
+/// run separately because it can fail.
+pub(crate) const LATE_LINTS: [LateLint; 26] = [
+    Lint {
+        name: "empty-cd-name",
+        group: LintGroup::Default,
+        check: lint_empty_cd_name,
+    },
+    Lint {
+        name: "long-cd-name",
+        group: LintGroup::Default,
+        check: lint_long_cd_name,
+    },
+    Lint {
+        name: "empty-cd-description",
+        group: LintGroup::Default,
+        check: lint_empty_cd_description,
+    },
+    Lint {
+        name: "rich-text-markup",
+        group: LintGroup::Default,
+        check: lint_rich_text_markup,
+    },
+    Lint {
+        name: "undefined-enemy-descriptors",
+        group: LintGroup::Default,
+        check: lint_undefined_enemy_descriptors,
+    },
+    Lint {
+        name: "missing-enemy-descriptor-base",
+        group: LintGroup::Default,
+        check: lint_missing_enemy_descriptor_base,
+    },
+    Lint {
+        name: "shadowed-vanilla-enemy-descriptors",
+        group: LintGroup::Default,
+        check: lint_shadowed_vanilla_enemy_descriptors,
+    },
+    Lint {
+        name: "redundant-enemy-descriptor-overrides",
+        group: LintGroup::Default,
+        check: lint_redundant_enemy_descriptor_overrides,
+    },
+    Lint {
+        name: "extreme-enemy-descriptor-values",
+        group: LintGroup::Default,
+        check: lint_extreme_enemy_descriptor_values,
+    },
+    Lint {
+        name: "min-larger-than-max",
+        group: LintGroup::Default,
+        check: lint_min_larger_than_max,
+    },
+    Lint {
+        name: "empty-weighted-bins",
+        group: LintGroup::Default,
+        check: lint_empty_weighted_bins,
+    },
+    Lint {
+        name: "zero-weight-bins",
+        group: LintGroup::Default,
+        check: lint_zero_weight_bins,
+    },
+    Lint {
+        name: "veteran-ratio-out-of-range",
+        group: LintGroup::Default,
+        check: lint_veteran_ratio_out_of_range,
+    },
+    Lint {
+        name: "damage-modifiers-out-of-range",
+        group: LintGroup::Default,
+        check: lint_damage_modifiers_out_of_range,
+    },
+    Lint {
+        name: "elites-without-max-active-elites",
+        group: LintGroup::Default,
+        check: lint_elites_without_max_active_elites,
+    },
+    Lint {
+        name: "unused-custom-enemy-descriptors",
+        group: LintGroup::Default,
+        check: lint_unused_custom_enemy_descriptors,
+    },
+    Lint {
+        name: "ambiguous-enemy-pool-add-remove",
+        group: LintGroup::Default,
+        check: lint_ambiguous_enemy_pool_add_remove,
+    },
+    Lint {
+        name: "unknown-pawn-stats",
+        group: LintGroup::Nursery,
+        check: lint_unknown_pawn_stats,
+    },
+    Lint {
+        name: "ineffective-pawn-stats",
+        group: LintGroup::Nursery,
+        check: lint_ineffective_pawn_stats,
+    },
+    Lint {
+        name: "disruptive-count-exceeds-pool-size",
+        group: LintGroup::Default,
+        check: lint_disruptive_count_exceeds_pool_size,
+    },
+    Lint {
+        name: "pool-size-exceeds-available-enemies",
+        group: LintGroup::Default,
+        check: lint_pool_size_exceeds_available_enemies,
+    },
+    Lint {
+        name: "empty-effective-enemy-pool",
+        group: LintGroup::Default,
+        check: lint_empty_effective_enemy_pool,
+    },
+    Lint {
+        name: "enemy-descriptor-in-multiple-pools",
+        group: LintGroup::Nursery,
+        check: lint_enemy_descriptor_in_multiple_pools,
+    },
+    Lint {
+        name: "unspawnable-enemy-descriptors",
+        group: LintGroup::Default,
+        check: lint_unspawnable_enemy_descriptors,
+    },
+    Lint {
+        name: "stationary-enemy-pool-mismatch",
+        group: LintGroup::Default,
+        check: lint_stationary_enemy_pool_mismatch,
+    },
+    Lint {
+        name: "deep-enemy-descriptor-base-chains",
+        group: LintGroup::Default,
+        check: lint_deep_enemy_descriptor_base_chains,
+    },
+];
@@ -0,0 +1,69 @@
+use indexmap::IndexSet;
+
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// The enemy pool is filled up to `MinPoolSize` Enemy Descriptors from `CommonEnemies`,
+/// `DisruptiveEnemies` and `SpecialEnemies`. If there are fewer distinct Enemy Descriptors available
+/// in those pools than `MinPoolSize`, the enemy pool ends up with repeated or missing entries.
+///
+/// We can only count the available Enemy Descriptors if all three pools are cleared, because we
+/// don't know the vanilla contents of each pool.
+pub fn lint_pool_size_exceeds_available_enemies<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let min_pool_size = &cd.min_pool_size;
+    if !min_pool_size.is_specified() {
+        return;
+    }
+
+    let mut available = IndexSet::new();
+    for pool in [
+        &cd.common_enemies,
+        &cd.disruptive_enemies,
+        &cd.special_enemies,
+    ] {
+        let Some(effective_pool) = pool.val.effective_pool() else {
+            return;
+        };
+        available.extend(effective_pool);
+    }
+
+    if available.len() >= min_pool_size.val {
+        return;
+    }
+
+    diag.push(
+        Report::build(ReportKind::Warning, path, min_pool_size.span.start)
+            .with_message(format!(
+                "\"{}\" is larger than the number of Enemy Descriptors available to fill it",
+                "MinPoolSize".fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, min_pool_size.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!(
+                        "the enemy pool should hold {} Enemy Descriptors, but only {} are available",
+                        min_pool_size.val.fg(Color::Blue),
+                        available.len().fg(Color::Blue)
+                    )),
+            )
+            .with_note(format!(
+                "the enemy pool is filled from \"{}\", \"{}\" and \"{}\", which may lead to repeated or missing entries",
+                "CommonEnemies".fg(Color::Blue),
+                "DisruptiveEnemies".fg(Color::Blue),
+                "SpecialEnemies".fg(Color::Blue)
+            ))
+            .with_help(format!(
+                "lower \"{}\" to at most {}, or add more Enemy Descriptors to those pools",
+                "MinPoolSize".fg(Color::Blue),
+                available.len().fg(Color::Blue)
+            ))
+            .finish(),
+    );
+}
@@ -0,0 +1,155 @@
+use std::fmt::Display;
+use std::ops::Range;
+
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::resolve::Resolved;
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// An Enemy Descriptor field which is overridden to exactly the value it would inherit from its
+/// `Base` anyway is noise, and makes it harder to see what the Enemy Descriptor actually changes.
+///
+/// We only know the inherited value if some Enemy Descriptor in the `Base` chain specifies the
+/// field in this Custom Difficulty; the values of vanilla Enemy Descriptors are unknown to us.
+pub fn lint_redundant_enemy_descriptor_overrides<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    for ed in cd.resolve_enemy_descriptors().into_values() {
+        let Some(base) = ed
+            .chain
+            .get(1)
+            .and_then(|base| cd.resolve_enemy_descriptor(base))
+        else {
+            continue;
+        };
+        let Some(ed_def) = cd.enemy_descriptor(ed.name) else {
+            continue;
+        };
+        let ed_def = &ed_def.val;
+
+        let fields = [
+            (
+                "SpawnSpread",
+                redundant(&ed_def.spawn_spread, base.spawn_spread),
+            ),
+            (
+                "IdealSpawnSize",
+                redundant(&ed_def.ideal_spawn_size, base.ideal_spawn_size),
+            ),
+            (
+                "CanBeUsedForConstantPressure",
+                redundant(
+                    &ed_def.can_be_used_for_constant_pressure,
+                    base.can_be_used_for_constant_pressure,
+                ),
+            ),
+            (
+                "CanBeUsedInEncounters",
+                redundant(
+                    &ed_def.can_be_used_in_encounters,
+                    base.can_be_used_in_encounters,
+                ),
+            ),
+            (
+                "DifficultyRating",
+                redundant(&ed_def.difficulty_rating, base.difficulty_rating),
+            ),
+            (
+                "MinSpawnCount",
+                redundant(&ed_def.min_spawn_count, base.min_spawn_count),
+            ),
+            (
+                "MaxSpawnCount",
+                redundant(&ed_def.max_spawn_count, base.max_spawn_count),
+            ),
+            ("Rarity", redundant(&ed_def.rarity, base.rarity)),
+            (
+                "SpawnAmountModifier",
+                redundant(&ed_def.spawn_amount_modifier, base.spawn_amount_modifier),
+            ),
+            ("Elite", redundant(&ed_def.elite, base.elite)),
+            ("Scale", redundant(&ed_def.scale, base.scale)),
+            (
+                "TimeDilation",
+                redundant(&ed_def.time_dilation, base.time_dilation),
+            ),
+        ];
+        for (field, redundant) in fields {
+            let Some((val, from)) = redundant else {
+                continue;
+            };
+            diag.push(redundant_override_report(
+                path,
+                ed.name,
+                field,
+                &val.val,
+                from,
+                val.span.into_range(),
+            ));
+        }
+
+        for (stat, val) in &ed_def.pawn_stats.val.0 {
+            let Some((val, from)) = redundant(val, base.pawn_stats.get(stat.as_str()).copied())
+            else {
+                continue;
+            };
+            diag.push(redundant_override_report(
+                path,
+                ed.name,
+                &format!("PawnStats.{}", stat.val),
+                &val.val,
+                from,
+                val.span.into_range(),
+            ));
+        }
+    }
+}
+
+/// The specified value, formatted for display, and the Enemy Descriptor it is inherited from if it
+/// is the same as the inherited value. Values are compared as displayed, as that's what the user
+/// sees.
+fn redundant<'a, T: Display>(
+    val: &Spanned<T>,
+    inherited: Option<Resolved<'a, T>>,
+) -> Option<(Spanned<String>, &'a str)> {
+    let inherited = inherited?;
+    let val = val.as_ref().map(ToString::to_string);
+    (val.is_specified() && val.val == inherited.val.to_string()).then_some((val, inherited.from))
+}
+
+fn redundant_override_report<'d>(
+    path: &'d String,
+    ed_name: &str,
+    field: &str,
+    val: &dyn Display,
+    base_name: &str,
+    span: Range<usize>,
+) -> Report<'d> {
+    Report::build(ReportKind::Advice, path, span.start)
+        .with_message(format!(
+            "\"{}\" of Enemy Descriptor \"{}\" is set to the value it already inherits",
+            field.fg(Color::Blue),
+            ed_name.fg(Color::Blue)
+        ))
+        .with_label(
+            Label::new((path, span))
+                .with_color(Color::Cyan)
+                .with_message(format!(
+                    "\"{}\" already inherits {} from \"{}\"",
+                    ed_name.fg(Color::Blue),
+                    val,
+                    base_name.fg(Color::Blue)
+                )),
+        )
+        .with_help(format!(
+            "remove \"{}\" from \"{}\"",
+            field.fg(Color::Blue),
+            ed_name.fg(Color::Blue)
+        ))
+        .finish()
+}
@@ -0,0 +1,148 @@
+use std::ops::Range;
+
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// The difficulty `Name` and `Description` support Unreal rich text markup, e.g.
+/// `<Red>Hard</>`. Unbalanced tags are shown verbatim in game, so report tags which are never
+/// closed, closing tags without an opening tag, and `<` without a matching `>`. If
+/// `rich_text_tags` is configured, also report tags not in that list.
+pub fn lint_rich_text_markup<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    check_markup(config, &cd.name, "Name", path, diag);
+    check_markup(config, &cd.description, "Description", path, diag);
+}
+
+fn check_markup<'d>(
+    config: &Config,
+    text: &Spanned<String>,
+    field: &str,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    if !text.is_specified() {
+        return;
+    }
+
+    // The string value is the raw source text between the quotes (escapes are not unescaped), so
+    // offsets into the value map directly onto the source.
+    let base = text.span.start + 1;
+    let mut report = |offsets: Range<usize>, msg: String, label: String, help: Option<String>| {
+        let span = base + offsets.start..base + offsets.end;
+        let mut report = Report::build(ReportKind::Warning, path, span.start)
+            .with_message(msg)
+            .with_label(
+                Label::new((path, span))
+                    .with_color(Color::Yellow)
+                    .with_message(label),
+            );
+        if let Some(help) = help {
+            report.set_help(help);
+        }
+        diag.push(report.finish());
+    };
+
+    let s = text.as_str();
+    // Open tags, as (tag name, offsets of the whole tag).
+    let mut open_tags: Vec<(&str, Range<usize>)> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(lt) = s[pos..].find('<').map(|i| pos + i) {
+        let Some(gt) = s[lt..].find('>').map(|i| lt + i) else {
+            report(
+                lt..lt + 1,
+                format!(
+                    "unterminated rich text tag in \"{}\"",
+                    field.fg(Color::Blue)
+                ),
+                "this \"<\" has no matching \">\"".to_string(),
+                None,
+            );
+            break;
+        };
+        let tag = &s[lt + 1..gt];
+        let offsets = lt..gt + 1;
+        pos = gt + 1;
+
+        if let Some(closing_name) = tag.strip_prefix('/') {
+            // Both `</>` and `</Name>` close the innermost open tag.
+            match open_tags.pop() {
+                Some((open_name, _)) => {
+                    if !closing_name.is_empty() && closing_name != open_name {
+                        report(
+                            offsets,
+                            format!("mismatched rich text tags in \"{}\"", field.fg(Color::Blue)),
+                            format!("this closes \"<{}>\"", open_name.fg(Color::Blue)),
+                            Some("use \"</>\" to close the innermost tag".to_string()),
+                        );
+                    }
+                }
+                None => report(
+                    offsets,
+                    format!(
+                        "closing rich text tag without an opening tag in \"{}\"",
+                        field.fg(Color::Blue)
+                    ),
+                    "nothing to close here".to_string(),
+                    Some("remove this closing tag".to_string()),
+                ),
+            }
+            continue;
+        }
+
+        // Self-closing tags such as `<img id="..."/>` don't need a closing tag.
+        let self_closing = tag.ends_with('/');
+        let name = tag
+            .trim_end_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+
+        if name.is_empty() {
+            report(
+                offsets,
+                format!("empty rich text tag in \"{}\"", field.fg(Color::Blue)),
+                "this tag has no name".to_string(),
+                None,
+            );
+            continue;
+        }
+
+        if !config.rich_text_tags.is_empty() && !config.rich_text_tags.iter().any(|t| t == name) {
+            report(
+                offsets.clone(),
+                format!(
+                    "unknown rich text tag \"{}\" in \"{}\"",
+                    name.fg(Color::Blue),
+                    field.fg(Color::Blue)
+                ),
+                "this tag is not in `rich_text_tags`".to_string(),
+                None,
+            );
+        }
+
+        if !self_closing {
+            open_tags.push((name, offsets));
+        }
+    }
+
+    for (name, offsets) in open_tags {
+        report(
+            offsets,
+            format!(
+                "rich text tag \"{}\" is never closed in \"{}\"",
+                name.fg(Color::Blue),
+                field.fg(Color::Blue)
+            ),
+            "this tag is opened here".to_string(),
+            Some("close it with \"</>\"".to_string()),
+        );
+    }
+}
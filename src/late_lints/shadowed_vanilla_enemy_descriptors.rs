@@ -0,0 +1,60 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// An Enemy Descriptor definition that reuses a vanilla Enemy Descriptor name does not create a
+/// new Enemy Descriptor, it modifies the vanilla one, and by extension every enemy pool that
+/// contains it. This may very well be intended, but it is easy to do by accident when copying a
+/// vanilla name as the starting point for a new Enemy Descriptor.
+pub fn lint_shadowed_vanilla_enemy_descriptors<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        if !config.game_data().is_vanilla_enemy_descriptor(&ed_name.val) {
+            continue;
+        }
+
+        let mut report = Report::build(ReportKind::Warning, path, ed_name.span.start)
+            .with_message(format!(
+                "\"{}\" modifies the vanilla Enemy Descriptor instead of creating a new Enemy Descriptor",
+                ed_name.as_str().fg(Color::Blue)
+            ))
+            .with_label(
+                Label::new((path, ed_name.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!(
+                        "this changes every \"{}\" spawned in the mission",
+                        ed_name.as_str().fg(Color::Blue)
+                    )),
+            )
+            .with_help(format!(
+                "if you meant to create a new Enemy Descriptor, give it a new name and set its \"{}\" to \"{}\"",
+                "Base".fg(Color::Blue),
+                ed_name.as_str().fg(Color::Blue)
+            ));
+
+        let base = &ed_def.val.base;
+        if !base.is_specified() {
+            report.add_note(format!(
+                "no \"{}\" is set, so any field not specified here keeps its vanilla value",
+                "Base".fg(Color::Blue)
+            ));
+        } else if base.val != ed_name.val {
+            report.add_label(
+                Label::new((path, base.span.into_range()))
+                    .with_color(Color::Yellow)
+                    .with_message(format!(
+                        "the vanilla \"{}\" is replaced by a copy of \"{}\"",
+                        ed_name.as_str().fg(Color::Blue),
+                        base.as_str().fg(Color::Blue)
+                    )),
+            );
+        }
+
+        diag.push(report.finish());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// The game handles stationary enemies (leeches, spitball infectors, brood nexuses, breeders,
+/// etc.) spawned from mobile enemy pools poorly, and likewise mobile enemies spawned from the
+/// stationary enemy pool.
+///
+/// Custom Enemy Descriptors are classified by the vanilla Enemy Descriptor at the root of their
+/// `Base` chain. Enemy Descriptors which cannot be traced back to a vanilla Enemy Descriptor are
+/// not checked.
+pub fn lint_stationary_enemy_pool_mismatch<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let check_pool = |diag: &mut Diagnostics<'d>,
+                      pool: &Spanned<EnemyPool>,
+                      pool_name: &str,
+                      expects_stationary: bool| {
+        for name in &pool.val.add.val {
+            let Some(vanilla) = vanilla_root(config, cd, &name.val) else {
+                continue;
+            };
+            let is_stationary = config
+                .game_data()
+                .stationary_enemy_descriptors
+                .contains(&vanilla);
+            if is_stationary == expects_stationary {
+                continue;
+            }
+
+            let (kind, expected_kind) = if is_stationary {
+                ("stationary", "mobile")
+            } else {
+                ("mobile", "stationary")
+            };
+
+            let mut report = Report::build(ReportKind::Warning, path, name.span.start)
+                .with_message(format!(
+                    "{} Enemy Descriptor \"{}\" is added to the {} enemy pool \"{}\"",
+                    kind,
+                    name.as_str().fg(Color::Blue),
+                    expected_kind,
+                    pool_name.fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, name.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message(format!("this is a {kind} enemy")),
+                )
+                .with_note(format!(
+                    "the game handles {kind} enemies spawned from {expected_kind} enemy pools poorly"
+                ));
+            if name.val != vanilla {
+                report.set_help(format!(
+                    "\"{}\" is ultimately based on \"{}\"",
+                    name.as_str().fg(Color::Blue),
+                    vanilla.fg(Color::Blue)
+                ));
+            }
+            diag.push(report.finish());
+        }
+    };
+
+    check_pool(diag, &cd.stationary_enemies, "StationaryEnemies", true);
+    check_pool(diag, &cd.enemy_pool, "EnemyPool", false);
+    check_pool(diag, &cd.common_enemies, "CommonEnemies", false);
+    check_pool(diag, &cd.disruptive_enemies, "DisruptiveEnemies", false);
+    check_pool(diag, &cd.special_enemies, "SpecialEnemies", false);
+}
+
+/// Follows the `Base` chain of the given Enemy Descriptor until a vanilla Enemy Descriptor is
+/// reached. Returns `None` if the chain ends in a non-vanilla Enemy Descriptor or is cyclic.
+pub(crate) fn vanilla_root<'a>(
+    config: &Config,
+    cd: &'a CustomDifficulty,
+    name: &'a str,
+) -> Option<&'a str> {
+    cd.resolve_enemy_descriptor(name)?
+        .inherits_from
+        .filter(|root| config.game_data().is_vanilla_enemy_descriptor(root))
+}
@@ -1,12 +1,13 @@
 use std::collections::HashSet;
 
-use ariadne::{Color, Fmt, Label, Report, ReportKind};
-
 use crate::config::Config;
 use crate::custom_difficulty::CustomDifficulty;
-use crate::late_lints::VANILLA_ENEMY_DESCRIPTORS;
+use crate::diagnostic::{
+    string_literal, Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion,
+};
 use crate::spanned::Spanned;
-use crate::{dummy_sp, Diagnostics};
+use crate::symbol::Symbol;
+use crate::{edit_distance, Diagnostics};
 
 pub fn lint_undefined_enemy_descriptors<'d>(
     config: &Config,
@@ -15,60 +16,77 @@ pub fn lint_undefined_enemy_descriptors<'d>(
     diag: &mut Diagnostics<'d>,
 ) {
     let mut defined_enemy_descriptors = HashSet::new();
-    defined_enemy_descriptors.extend(VANILLA_ENEMY_DESCRIPTORS.into_iter().map(ToOwned::to_owned));
-    defined_enemy_descriptors.extend(config.extra_enemy_descriptors.iter().map(ToOwned::to_owned));
+    defined_enemy_descriptors.extend(config.game_data().enemy_descriptors.iter().copied());
+    defined_enemy_descriptors.extend(config.extra_enemy_descriptors.iter().map(String::as_str));
+
+    // The "Base" of a new Enemy Descriptor may also name one which is defined after it.
+    let mut defined_anywhere = defined_enemy_descriptors.clone();
+    defined_anywhere.extend(cd.enemy_descriptors.keys().map(|name| name.as_str()));
 
     // 1. First, we collect any custom defined Enemy Descriptors.
     for (ed_name, ed_def) in &cd.enemy_descriptors.val {
-        if !defined_enemy_descriptors.contains(&ed_name.val) {
+        if !defined_enemy_descriptors.contains(ed_name.as_str()) {
             if ed_def.val.base.val == ed_name.val {
                 // We're referencing ourselves, but we haven't defined it yet!
                 diag.push(
                     Report::build(ReportKind::Error, path, ed_name.span.start)
-                        .with_message(format!("attempt to reference \"{}\" in its \"Base\" field that is not a pre-defined Enemy Descriptor", ed_name.val.as_str().fg(Color::Blue)))
+                        .with_message(format!("attempt to reference \"{}\" in its \"Base\" field that is not a pre-defined Enemy Descriptor", ed_name.as_str().fg(Color::Blue)))
                         .with_label(
                             Label::new((path, ed_name.span.into_range())).with_color(Color::Red),
                         )
+                        .with_suggestion(Suggestion::new(
+                            ed_def.val.base.span.into_range(),
+                            "\"<vanilla Enemy Descriptor>\"",
+                            Applicability::HasPlaceholders,
+                        ))
                         .finish(),
                 );
             } else {
-                defined_enemy_descriptors.insert(ed_name.val.to_owned());
+                if ed_def.val.base.is_specified()
+                    && !defined_anywhere.contains(config.resolve_alias(&ed_def.val.base.val))
+                {
+                    diag.push(undefined_base_report(
+                        config,
+                        path,
+                        &ed_def.val.base,
+                        &defined_anywhere,
+                    ));
+                }
+                defined_enemy_descriptors.insert(ed_name.as_str());
             }
-        } else if ed_def.val.base.span != dummy_sp()
-            && !defined_enemy_descriptors.contains(&ed_def.val.base.val)
+        } else if ed_def.val.base.is_specified()
+            && !defined_enemy_descriptors.contains(config.resolve_alias(&ed_def.val.base.val))
         {
-            diag.push(
-                Report::build(ReportKind::Error, path, ed_def.val.base.span.start)
-                    .with_message(format!(
-                        "attempt to reference undefined Enemy Descriptor \"{}\" as \"Base\"",
-                        ed_def.val.base.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_label(Label::new((path, ed_def.span.into_range())).with_color(Color::Red))
-                    .finish(),
-            );
+            diag.push(undefined_base_report(
+                config,
+                path,
+                &ed_def.val.base,
+                &defined_enemy_descriptors,
+            ));
         }
     }
 
-    let mut check_ed = |ed: &Spanned<String>| {
-        if !defined_enemy_descriptors.contains(&ed.val) {
-            diag.push(
-                Report::build(ReportKind::Error, path, ed.span.start)
-                    .with_message(format!(
-                        "attempt to reference undefined Enemy Descriptor \"{}\"",
-                        ed.val.as_str().fg(Color::Blue)
-                    ))
-                    .with_label(Label::new((path, ed.span.into_range())).with_color(Color::Red))
-                    .finish(),
-            );
+    let mut check_ed = |ed: &Spanned<Symbol>| {
+        if !defined_enemy_descriptors.contains(config.resolve_alias(&ed.val)) {
+            let mut report = Report::build(ReportKind::Error, path, ed.span.start)
+                .with_message(format!(
+                    "attempt to reference undefined Enemy Descriptor \"{}\"",
+                    ed.as_str().fg(Color::Blue)
+                ))
+                .with_label(Label::new((path, ed.span.into_range())).with_color(Color::Red));
+            if let Some(suggestion) = suggest_defined(config, &defined_enemy_descriptors, ed) {
+                report.add_suggestion(suggestion);
+            }
+            diag.push(report.finish());
         }
     };
 
     // 2. Now, we need to check each of the enemy pool's add/remove members to see if they attempt
     //    to reference undefined enemy descriptors.
-    cd.enemy_pool.val.add.val.iter().for_each(&mut check_ed);
-    cd.enemy_pool.val.remove.val.iter().for_each(&mut check_ed);
+    cd.enemy_pool.val.add.iter().for_each(&mut check_ed);
+    cd.enemy_pool.val.remove.iter().for_each(&mut check_ed);
 
-    cd.common_enemies.val.add.val.iter().for_each(&mut check_ed);
+    cd.common_enemies.val.add.iter().for_each(&mut check_ed);
     cd.common_enemies
         .val
         .remove
@@ -115,3 +133,44 @@ pub fn lint_undefined_enemy_descriptors<'d>(
         .iter()
         .for_each(&mut check_ed);
 }
+
+/// Reports a "Base" which names an Enemy Descriptor which isn't among the defined ones.
+fn undefined_base_report<'d>(
+    config: &Config,
+    path: &'d String,
+    base: &Spanned<Symbol>,
+    defined_enemy_descriptors: &HashSet<&str>,
+) -> Report<'d> {
+    let mut report = Report::build(ReportKind::Error, path, base.span.start)
+        .with_message(format!(
+            "attempt to reference undefined Enemy Descriptor \"{}\" as \"Base\"",
+            base.as_str().fg(Color::Blue)
+        ))
+        .with_label(Label::new((path, base.span.into_range())).with_color(Color::Red));
+    if let Some(suggestion) = suggest_defined(config, defined_enemy_descriptors, base) {
+        report.add_suggestion(suggestion);
+    }
+    report.finish()
+}
+
+/// Suggests the defined Enemy Descriptor or alias an undefined Enemy Descriptor was most likely
+/// meant to be, e.g. `"ED_Spider_Grunt"` for `"ED_Spider_Gruntt"`.
+fn suggest_defined(
+    config: &Config,
+    defined_enemy_descriptors: &HashSet<&str>,
+    ed: &Spanned<Symbol>,
+) -> Option<Suggestion> {
+    let mut candidates = defined_enemy_descriptors
+        .iter()
+        .copied()
+        .chain(config.aliases.keys().map(String::as_str))
+        .collect::<Vec<_>>();
+    // Sorted so that the same name is suggested on every run.
+    candidates.sort_unstable();
+    let suggestion = edit_distance::suggest_similar(&candidates, &ed.val)?;
+    Some(Suggestion::new(
+        ed.span.into_range(),
+        string_literal(suggestion),
+        Applicability::MaybeIncorrect,
+    ))
+}
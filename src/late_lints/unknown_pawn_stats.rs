@@ -0,0 +1,45 @@
+use crate::config::Config;
+use crate::custom_difficulty::CustomDifficulty;
+use crate::diagnostic::{
+    string_literal, Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion,
+};
+use crate::{edit_distance, Diagnostics};
+
+/// `PawnStats` is a free-form map as far as Custom Difficulty is concerned, so a misspelled
+/// `PST_*` key is silently ignored by the game. The list of known pawn stats is still being
+/// validated, which is why this is a warning and not an error.
+pub fn lint_unknown_pawn_stats<'d>(
+    config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let pawn_stats = config.game_data().pawn_stats;
+    for (ed_name, ed_def) in &cd.enemy_descriptors.val {
+        for pawn_stat in ed_def.val.pawn_stats.val.0.keys() {
+            if pawn_stats.contains(&pawn_stat.as_str()) {
+                continue;
+            }
+
+            let mut report = Report::build(ReportKind::Warning, path, pawn_stat.span.start)
+                .with_message(format!(
+                    "unknown pawn stat \"{}\" in Enemy Descriptor \"{}\"",
+                    pawn_stat.as_str().fg(Color::Blue),
+                    ed_name.as_str().fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, pawn_stat.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message("this pawn stat is not known to CDLint"),
+                );
+            if let Some(suggestion) = edit_distance::suggest_similar(pawn_stats, &pawn_stat.val) {
+                report.add_suggestion(Suggestion::new(
+                    pawn_stat.span.into_range(),
+                    string_literal(suggestion),
+                    Applicability::MaybeIncorrect,
+                ));
+            }
+            diag.push(report.finish());
+        }
+    }
+}
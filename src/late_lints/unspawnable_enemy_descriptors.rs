@@ -0,0 +1,75 @@
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// An Enemy Descriptor added to a (mobile) enemy pool will never actually appear if it can neither
+/// be used in encounters nor for constant pressure, or if its `MaxSpawnCount` or
+/// `DifficultyRating` is 0.
+///
+/// Field values are resolved through the `Base` chain; values only known to come from vanilla
+/// Enemy Descriptors are assumed to be fine.
+pub fn lint_unspawnable_enemy_descriptors<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let check_pool = |diag: &mut Diagnostics<'d>, pool: &Spanned<EnemyPool>, pool_name: &str| {
+        for name in &pool.val.add.val {
+            let Some(reason) = unspawnable_reason(cd, &name.val) else {
+                continue;
+            };
+
+            diag.push(
+                Report::build(ReportKind::Warning, path, name.span.start)
+                    .with_message(format!(
+                        "Enemy Descriptor \"{}\" is added to \"{}\" but can never spawn",
+                        name.as_str().fg(Color::Blue),
+                        pool_name.fg(Color::Blue)
+                    ))
+                    .with_label(
+                        Label::new((path, name.span.into_range()))
+                            .with_color(Color::Yellow)
+                            .with_message(reason),
+                    )
+                    .finish(),
+            );
+        }
+    };
+
+    check_pool(diag, &cd.enemy_pool, "EnemyPool");
+    check_pool(diag, &cd.common_enemies, "CommonEnemies");
+    check_pool(diag, &cd.disruptive_enemies, "DisruptiveEnemies");
+    check_pool(diag, &cd.special_enemies, "SpecialEnemies");
+}
+
+fn unspawnable_reason(cd: &CustomDifficulty, name: &str) -> Option<String> {
+    let ed = cd.resolve_enemy_descriptor(name)?;
+
+    if ed.max_spawn_count.is_some_and(|v| v.val.val == 0) {
+        return Some(format!("its \"{}\" is 0", "MaxSpawnCount".fg(Color::Blue)));
+    }
+
+    if ed.difficulty_rating.is_some_and(|v| v.val.val == 0.0) {
+        return Some(format!(
+            "its \"{}\" is 0",
+            "DifficultyRating".fg(Color::Blue)
+        ));
+    }
+
+    if ed.can_be_used_in_encounters.is_some_and(|v| !v.val.val)
+        && ed
+            .can_be_used_for_constant_pressure
+            .is_some_and(|v| !v.val.val)
+    {
+        return Some(format!(
+            "both \"{}\" and \"{}\" are false",
+            "CanBeUsedInEncounters".fg(Color::Blue),
+            "CanBeUsedForConstantPressure".fg(Color::Blue)
+        ));
+    }
+
+    None
+}
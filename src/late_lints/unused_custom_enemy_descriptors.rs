@@ -1,14 +1,12 @@
 use std::collections::BTreeMap;
 
-use ariadne::{Color, Fmt, Label, Report, ReportKind};
-
 use crate::config::Config;
 use crate::custom_difficulty::{CustomDifficulty, EnemyPool};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
 use crate::spanned::Spanned;
+use crate::symbol::Symbol;
 use crate::Diagnostics;
 
-use super::VANILLA_ENEMY_DESCRIPTORS;
-
 pub fn lint_unused_custom_enemy_descriptors<'d>(
     config: &Config,
     cd: &CustomDifficulty,
@@ -16,20 +14,20 @@ pub fn lint_unused_custom_enemy_descriptors<'d>(
     diag: &mut Diagnostics<'d>,
 ) {
     let mut custom_descriptors_usage = BTreeMap::new();
-    for ed_name in cd.enemy_descriptors.val.keys() {
-        if !VANILLA_ENEMY_DESCRIPTORS.contains(&ed_name.val.as_str())
-            && !config.extra_enemy_descriptors.contains(&ed_name.val)
+    for ed_name in cd.enemy_descriptors.keys() {
+        if !config.game_data().is_vanilla_enemy_descriptor(&ed_name.val)
+            && !config.is_extra_enemy_descriptor(&ed_name.val)
         {
-            custom_descriptors_usage.insert(ed_name.val.to_owned(), (ed_name.span, false));
+            custom_descriptors_usage.insert(&ed_name.val, (ed_name.span, false));
         }
     }
 
     let mut update_usage = |enemy_pool: &Spanned<EnemyPool>| {
-        let mut update = |target: &Spanned<Vec<Spanned<String>>>| {
+        let mut update = |target: &Spanned<Vec<Spanned<Symbol>>>| {
             for name in &target.val {
-                custom_descriptors_usage
-                    .entry(name.val.to_owned())
-                    .and_modify(|(_, is_used)| *is_used = true);
+                if let Some((_, is_used)) = custom_descriptors_usage.get_mut(&name.val) {
+                    *is_used = true;
+                }
             }
         };
 
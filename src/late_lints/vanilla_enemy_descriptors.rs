@@ -1,89 +0,0 @@
-pub const VANILLA_ENEMY_DESCRIPTORS: [&str; 87] = [
-    "ED_Bomber",
-    "ED_Bomber_Explosive",
-    "ED_Bomber_Ice",
-    "ED_Bomber_Rockpox_Plague",
-    "ED_Butterfly",
-    "ED_CaveLeech",
-    "ED_EggSpider",
-    "ED_FacilityTurret_Barrier",
-    "ED_FacilityTurret_Burst",
-    "ED_FacilityTurret_Sniper",
-    "ED_Flea",
-    "ED_FlyingSmartRock",
-    "ED_Grabber",
-    "ED_GreatEggHunt_SpringBunny",
-    "ED_HydraWeed",
-    "ED_InfectedMule",
-    "ED_InfestationLarva",
-    "ED_InsectSwarm_Spawner",
-    "ED_Jelly_Spawn",
-    "ED_Jelly_Swarmer",
-    "ED_JellyBreeder",
-    "ED_JellyBreeder_RockpoxPlague",
-    "ED_Mactera_Shooter_Amber",
-    "ED_Mactera_Shooter_HeavyVeteran",
-    "ED_Mactera_Shooter_Normal",
-    "ED_Mactera_TripleShooter",
-    "ED_Nisse",
-    "ED_PatrolBot",
-    "ED_PatrolBot_Caretaker",
-    "ED_PlagueLarva",
-    "ED_Prospector",
-    "ED_PumpkinLootBug",
-    "ED_Shark",
-    "ED_ShootingPlant",
-    "ED_Shredder",
-    "ED_Spider_Alarm",
-    "ED_Spider_Amber_Shooter",
-    "ED_Spider_Boss_Heavy",
-    "ED_Spider_Boss_TwinA",
-    "ED_Spider_Boss_TwinB",
-    "ED_Spider_Buffer",
-    "ED_Spider_Charger",
-    "ED_Spider_Drone",
-    "ED_Spider_Exploder",
-    "ED_Spider_Exploder_Rockpox_Plague",
-    "ED_Spider_Exploder_Warning",
-    "ED_Spider_ExploderTank",
-    "ED_Spider_ExploderTank_King",
-    "ED_Spider_ExploderTankGhost",
-    "ED_Spider_Grunt",
-    "ED_Spider_Grunt_Attacker",
-    "ED_Spider_Grunt_Guard",
-    "ED_Spider_Grunt_Ice",
-    "ED_Spider_Grunt_Mutated",
-    "ED_Spider_Grunt_Rock",
-    "ED_Spider_Grunt_RockpoxPlague",
-    "ED_Spider_GruntTutorial",
-    "ED_Spider_Hoarder",
-    "ED_Spider_Lobber",
-    "ED_Spider_RapidShooter",
-    "ED_Spider_ShieldTank",
-    "ED_Spider_Shooter",
-    "ED_Spider_Shooter_Ground",
-    "ED_Spider_Shooter_Rockpox_Plague",
-    "ED_Spider_ShooterQueen",
-    "ED_Spider_Spawn",
-    "ED_Spider_Spitter",
-    "ED_Spider_Stinger",
-    "ED_Spider_Swarmer",
-    "ED_Spider_Swarmer_Ice",
-    "ED_Spider_Swarmer_Mutated",
-    "ED_Spider_Swarmer_Pheromone_NOFX",
-    "ED_Spider_Tank",
-    "ED_Spider_Tank_Amber",
-    "ED_Spider_Tank_Boss",
-    "ED_Spider_Tank_HeavySpawn",
-    "ED_Spider_Tank_Ice",
-    "ED_Spider_Tank_Mutated",
-    "ED_Spider_Tank_Rock",
-    "ED_Spider_Tank_RockpoxPlague",
-    "ED_SpiderSpawner",
-    "ED_Terminator",
-    "ED_TunnelSwarmer",
-    "ED_WalkingPlagueheart",
-    "ED_Woodlouse",
-    "ED_Woodlouse_Youngling",
-    "ED_Spider_Stalker",
-];
@@ -0,0 +1,52 @@
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, WeightedRange};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::spanned::Spanned;
+use crate::Diagnostics;
+
+/// `VeteranNormal` and `VeteranLarge` ranges are ratios of enemies to be promoted to their veteran
+/// variants, so anything outside of `[0, 1]` is most likely a percentage entered by mistake.
+pub fn lint_veteran_ratio_out_of_range<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    let check_ratio = |diag: &mut Diagnostics<'d>, ratio: &Spanned<f64>, member_name: &str| {
+        if (0.0..=1.0).contains(&ratio.val) {
+            return;
+        }
+
+        diag.push(
+            Report::build(ReportKind::Warning, path, ratio.span.start)
+                .with_message(format!(
+                    "veteran promotion ratio {} in \"{}\" is outside of {}",
+                    ratio.val.fg(Color::Blue),
+                    member_name.fg(Color::Blue),
+                    "[0, 1]".fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, ratio.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message("this ratio is out of range"),
+                )
+                .with_note(format!(
+                    "this is a ratio and not a percentage: {} already means that 100% of eligible enemies are promoted",
+                    "1.0".fg(Color::Blue)
+                ))
+                .finish(),
+        );
+    };
+
+    let check_weighted_ranges = |diag: &mut Diagnostics<'d>,
+                                 ranges: &Spanned<Vec<Spanned<WeightedRange<f64>>>>,
+                                 member_name: &str| {
+        for r in &ranges.val {
+            check_ratio(diag, &r.val.range.val.min, member_name);
+            check_ratio(diag, &r.val.range.val.max, member_name);
+        }
+    };
+
+    check_weighted_ranges(diag, &cd.veteran_normal, "VeteranNormal");
+    check_weighted_ranges(diag, &cd.veteran_large, "VeteranLarge");
+}
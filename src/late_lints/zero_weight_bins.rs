@@ -0,0 +1,52 @@
+use crate::config::Config;
+use crate::custom_difficulty::{CustomDifficulty, WeightedBinWeights};
+use crate::diagnostic::{Color, Fmt, Label, Report, ReportKind};
+use crate::Diagnostics;
+
+/// A weighted bin is selected with probability `weight / (sum of all weights)`, so a bin with a
+/// weight of 0 can never be selected. Negative weights are already rejected when building the
+/// Custom Difficulty.
+pub fn lint_zero_weight_bins<'d>(
+    _config: &Config,
+    cd: &CustomDifficulty,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) {
+    for (member_name, bins) in cd.weighted_bin_weights() {
+        check_bins(diag, path, &bins, member_name);
+    }
+}
+
+fn check_bins<'d>(
+    diag: &mut Diagnostics<'d>,
+    path: &'d String,
+    bins: &WeightedBinWeights<'_>,
+    member_name: &str,
+) {
+    for bin in &bins.val {
+        let weight = bin.val;
+        if weight.val > 0.0 {
+            continue;
+        }
+
+        diag.push(
+            Report::build(ReportKind::Warning, path, bin.span.start)
+                .with_message(format!(
+                    "weighted bin in \"{}\" has a {} of {} and can never be selected",
+                    member_name.fg(Color::Blue),
+                    "weight".fg(Color::Blue),
+                    weight.val.fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, bin.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message("this bin is never selected"),
+                )
+                .with_note(
+                    "a bin is selected with probability weight / (sum of all weights), so a weight of 0 means the bin is never picked",
+                )
+                .with_help("consider removing the bin, or giving it a positive weight")
+                .finish(),
+        );
+    }
+}
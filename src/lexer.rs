@@ -0,0 +1,230 @@
+//! Lexer for a Custom Difficulty JSON, which splits it into tokens before parsing so that mistakes
+//! inside strings, numbers and comments are reported with precise spans.
+
+use std::fmt;
+use std::ops::Range;
+
+use chumsky::prelude::*;
+
+use crate::parser::{record_issue, SyntaxIssue};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token<'a> {
+    /// `{`, `}`, `[`, `]`, `:` or `,`.
+    Ctrl(char),
+    /// The contents of a string, without its quotes and with escape sequences left as-is.
+    Str(&'a str),
+    Num(f64),
+    /// A bare word, which is either a literal such as `true`, or a mistake such as an unquoted
+    /// object key.
+    Ident(&'a str),
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ctrl(c) => write!(f, "{c}"),
+            Token::Str(s) => write!(f, "\"{s}\""),
+            Token::Num(n) => write!(f, "{n}"),
+            Token::Ident(ident) => write!(f, "{ident}"),
+        }
+    }
+}
+
+/// Syntax issues are collected in the lexer state, shared with the parser, rather than emitted as
+/// errors, because the lexer accepts the input as if the mistake wasn't there.
+pub type LexerExtra<'a> = extra::Full<Rich<'a, char>, Vec<SyntaxIssue>, ()>;
+
+/// Whitespace and comments. Comments are accepted, but recorded so that they can be linted.
+fn padding<'a>() -> impl Parser<'a, &'a str, (), LexerExtra<'a>> + Clone {
+    let line_comment = just("//")
+        .then(any().and_is(text::newline().not()).repeated())
+        .ignored();
+    let block_comment = just("/*")
+        .then(any().and_is(just("*/").not()).repeated())
+        .then(just("*/").or_not())
+        .validate(|(_, close), e, emitter| {
+            if close.is_none() {
+                emitter.emit(Rich::custom(e.span(), "unterminated block comment"));
+            }
+        });
+    let comment = line_comment.or(block_comment).validate(|_, e, _| {
+        let span: SimpleSpan = e.span();
+        record_issue(
+            e.state(),
+            SyntaxIssue::Comment {
+                span: span.into_range(),
+            },
+        );
+    });
+
+    text::whitespace()
+        .then(comment.then(text::whitespace()).repeated())
+        .ignored()
+}
+
+/// A string delimited by `quote`. Strings delimited by `'` are lexed as if they were delimited by
+/// `"`, so that a Custom Difficulty written with them can still be linted.
+fn string<'a>(quote: char) -> impl Parser<'a, &'a str, &'a str, LexerExtra<'a>> + Clone {
+    let escape = just('\\')
+        .then(choice((
+            one_of("\\/bfnrt").ignored(),
+            just(quote).ignored(),
+            just('u').ignore_then(text::digits(16).exactly(4).to_slice().validate(
+                |digits, e, emitter| {
+                    if char::from_u32(u32::from_str_radix(digits, 16).unwrap()).is_none() {
+                        emitter.emit(Rich::custom(e.span(), "invalid unicode character"));
+                    }
+                },
+            )),
+        )))
+        .ignored()
+        .labelled("escape character");
+    // Recorded rather than rejected, since the rest of the string is still fine.
+    let unknown_escape = just('\\').then(any()).validate(|_, e, _| {
+        let span: SimpleSpan = e.span();
+        record_issue(
+            e.state(),
+            SyntaxIssue::UnknownEscape {
+                span: span.into_range(),
+            },
+        );
+    });
+
+    just(quote)
+        .ignore_then(
+            none_of(['\\', quote])
+                .ignored()
+                .or(escape)
+                .or(unknown_escape)
+                .repeated()
+                .to_slice(),
+        )
+        .then(just(quote).or_not())
+        .validate(move |(contents, close): (&str, _), e, emitter| {
+            let span: SimpleSpan = e.span();
+            if close.is_none() {
+                emitter.emit(Rich::custom(
+                    SimpleSpan::new(span.start, span.start + quote.len_utf8()),
+                    "unterminated string",
+                ));
+                return contents;
+            }
+            if quote == '\'' {
+                record_issue(
+                    e.state(),
+                    SyntaxIssue::SingleQuotedString {
+                        span: span.into_range(),
+                    },
+                );
+            }
+            let content_start = span.start + quote.len_utf8();
+            let mut spans: Vec<Range<usize>> = Vec::new();
+            for (i, c) in contents.char_indices() {
+                if !c.is_ascii_control() || c == '\x7F' {
+                    continue;
+                }
+                let at = content_start + i;
+                // A Windows line break is reported as one control character.
+                match spans.last_mut() {
+                    Some(last) if c == '\n' && last.end == at && contents[..i].ends_with('\r') => {
+                        last.end += 1
+                    }
+                    _ => spans.push(at..at + 1),
+                }
+            }
+            if !spans.is_empty() {
+                record_issue(e.state(), SyntaxIssue::ControlCharacters { spans });
+            }
+            contents
+        })
+}
+
+/// Splits the input into tokens, each with its span. Characters which can't start a token are
+/// reported and skipped, so that the parser sees the rest of the input as usual.
+pub fn lexer<'a>() -> impl Parser<'a, &'a str, Vec<(Token<'a>, SimpleSpan)>, LexerExtra<'a>> {
+    let digits = text::digits(10);
+
+    let frac = just('.').then(digits);
+
+    let exp = just('e')
+        .or(just('E'))
+        .then(one_of("+-").or_not())
+        .then(digits)
+        .labelled("exponent");
+
+    // Leading `+` signs, leading zeros and hexadecimal numbers are not valid JSON, but are lexed as
+    // numbers so that a Custom Difficulty written with them can still be linted.
+    let hex = just('0')
+        .then(one_of("xX"))
+        .ignore_then(text::digits(16).to_slice())
+        .map(|digits: &str| {
+            digits
+                .chars()
+                .fold(0.0, |n, d| n * 16.0 + f64::from(d.to_digit(16).unwrap()))
+        });
+    let decimal = text::digits(10)
+        .then(frac.or_not())
+        .then(exp.or_not())
+        .to_slice()
+        .map(|s: &str| {
+            // Leading zeros are stripped, so that what's left is a JSON number.
+            let trimmed = s.trim_start_matches('0');
+            if trimmed.is_empty() || !trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+                format!("0{trimmed}")
+            } else {
+                trimmed.to_string()
+            }
+        });
+    let magnitude = hex
+        .map(|n| (n, None))
+        .or(decimal.map(|s| (s.parse().unwrap(), Some(s))));
+
+    let number = one_of("+-")
+        .or_not()
+        .then(magnitude)
+        .validate(|(sign, (n, decimal)): (_, (f64, _)), e, _| {
+            let n = if sign == Some('-') { -n } else { n };
+            let replacement = match decimal {
+                Some(decimal) if sign == Some('-') => format!("-{decimal}"),
+                Some(decimal) => decimal,
+                None => n.to_string(),
+            };
+            let span: SimpleSpan = e.span();
+            if e.slice() != replacement {
+                record_issue(
+                    e.state(),
+                    SyntaxIssue::NonJsonNumber {
+                        span: span.into_range(),
+                        replacement,
+                    },
+                );
+            }
+            Token::Num(n)
+        })
+        .labelled("number");
+
+    let string = string('"')
+        .or(string('\''))
+        .map(Token::Str)
+        .labelled("string");
+
+    let ctrl = one_of("{}[]:,").map(Token::Ctrl);
+
+    let ident = text::ident().map(Token::Ident);
+
+    let unexpected = any().validate(|c: char, e, emitter| {
+        emitter.emit(Rich::custom(
+            e.span(),
+            format!("unexpected character `{}`", c.escape_debug()),
+        ));
+    });
+
+    choice((ctrl, string, number, ident))
+        .map_with(|token, e| Some((token, e.span())))
+        .or(unexpected.to(None))
+        .padded_by(padding())
+        .repeated()
+        .collect::<Vec<_>>()
+        .map(|tokens| tokens.into_iter().flatten().collect())
+}
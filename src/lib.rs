@@ -0,0 +1,47 @@
+//! The linter behind the `cdlint` executable. It is a library so that the fuzz targets in `fuzz/`
+//! can call into the parser and the handlers.
+
+#![feature(min_specialization)]
+
+use crate::diagnostic::Report;
+use crate::spanned::Spanned;
+
+pub mod archive;
+pub mod cache;
+pub mod commands;
+pub mod config;
+pub mod crash_report;
+pub mod custom_difficulty;
+pub mod diagnostic;
+pub mod driver;
+mod early_lints;
+mod edit_distance;
+mod encoding;
+pub mod game_data;
+pub mod handlers;
+pub mod hazard_estimate;
+mod hazards;
+pub mod html_report;
+mod late_lints;
+mod lexer;
+pub mod lint;
+pub mod logging;
+pub mod parser;
+pub mod plugins;
+mod reference_graph;
+mod resolve;
+pub mod spanned;
+pub mod summary;
+pub mod suppression;
+pub mod symbol;
+pub mod time_passes;
+
+pub type DiagnosticReport<'a> = Report<'a>;
+pub type Diagnostics<'a> = Vec<DiagnosticReport<'a>>;
+
+/// Records which lint produced the diagnostics.
+fn tag_lint(diagnostics: &mut [DiagnosticReport<'_>], lint: &'static str) {
+    for diagnostic in diagnostics {
+        diagnostic.lint = Some(lint);
+    }
+}
@@ -0,0 +1,116 @@
+//! Metadata shared by early and late lints, used to decide which lints are run.
+
+use anyhow::bail;
+
+use crate::config::Config;
+use crate::early_lints::EARLY_LINTS;
+use crate::edit_distance;
+use crate::late_lints::LATE_LINTS;
+use crate::plugins;
+
+/// A group of lints which are enabled or disabled together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintGroup {
+    /// Lints which are run by default.
+    Default,
+    /// Experimental lints with a higher chance of false positives. These are allow-by-default and
+    /// have to be opted into via `enabled_lint_groups`.
+    Nursery,
+}
+
+impl LintGroup {
+    pub const ALL: [LintGroup; 2] = [LintGroup::Default, LintGroup::Nursery];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LintGroup::Default => "cdlint::default",
+            LintGroup::Nursery => "cdlint::nursery",
+        }
+    }
+
+    pub fn is_enabled(self, config: &Config) -> bool {
+        match self {
+            LintGroup::Default => true,
+            LintGroup::Nursery => config
+                .enabled_lint_groups
+                .iter()
+                .any(|group| group == self.name()),
+        }
+    }
+}
+
+/// A lint with its name, group and the function performing the check. `F` is the signature of the
+/// check, which differs between early and late lints.
+pub struct Lint<F> {
+    pub name: &'static str,
+    pub group: LintGroup,
+    pub check: F,
+}
+
+impl<F> Lint<F> {
+    pub fn is_enabled(&self, config: &Config, selection: &LintSelection) -> bool {
+        selection.is_enabled(self.name, self.group, config)
+    }
+}
+
+/// The lints selected to run for a single invocation via `--only` and `--skip`.
+#[derive(Debug, Default)]
+pub struct LintSelection {
+    only: Vec<String>,
+    skip: Vec<String>,
+}
+
+impl LintSelection {
+    /// Fails if any of the lint names is not the name of a lint.
+    pub fn new(only: Vec<String>, skip: Vec<String>) -> anyhow::Result<Self> {
+        let known = lint_names();
+        for (flag, name) in only
+            .iter()
+            .map(|name| ("--only", name))
+            .chain(skip.iter().map(|name| ("--skip", name)))
+        {
+            if known.contains(&name.as_str()) {
+                continue;
+            }
+            match edit_distance::suggest_similar(&known, name) {
+                Some(suggestion) => {
+                    bail!("unknown lint `{name}` passed to `{flag}`; did you mean `{suggestion}`?")
+                }
+                None => bail!("unknown lint `{name}` passed to `{flag}`"),
+            }
+        }
+        Ok(Self { only, skip })
+    }
+
+    /// Whether the lint should run. If `--only` is given, exactly the named lints run regardless of
+    /// their group, minus any which are also skipped.
+    pub fn is_enabled(&self, name: &str, group: LintGroup, config: &Config) -> bool {
+        if self.skip.iter().any(|skipped| skipped == name) {
+            return false;
+        }
+        if self.only.is_empty() {
+            group.is_enabled(config)
+        } else {
+            self.only.iter().any(|only| only == name)
+        }
+    }
+}
+
+/// The name of the `cyclic-enemy-descriptor-references` lint, which is not part of `LATE_LINTS`
+/// because it can fail.
+pub const CYCLIC_ENEMY_DESCRIPTOR_REFERENCES: &str = "cyclic-enemy-descriptor-references";
+
+/// The name of the `json-comments` lint, which is not part of `EARLY_LINTS` because comments are
+/// reported by the parser.
+pub const JSON_COMMENTS: &str = "json-comments";
+
+/// The names of all lints, including those of the loaded plugins.
+pub fn lint_names() -> Vec<&'static str> {
+    let mut names = Vec::new();
+    names.extend(EARLY_LINTS.iter().map(|lint| lint.name));
+    names.extend(LATE_LINTS.iter().map(|lint| lint.name));
+    names.push(CYCLIC_ENEMY_DESCRIPTOR_REFERENCES);
+    names.push(JSON_COMMENTS);
+    names.extend(plugins::names());
+    names
+}
@@ -1,6 +1,6 @@
 use tracing::*;
 
-pub(crate) fn setup_logging() {
+pub fn setup_logging() {
     use tracing::metadata::LevelFilter;
     use tracing_subscriber::prelude::*;
     use tracing_subscriber::{fmt, EnvFilter};
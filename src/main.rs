@@ -1,52 +1,123 @@
-#![feature(let_chains)]
-#![feature(min_specialization)]
-#![feature(extract_if)]
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use std::path::PathBuf;
-
-use anyhow::{bail, Context};
-use ariadne::{Color, Label, Report, ReportKind, Source};
-use chumsky::prelude::*;
+use anyhow::bail;
 use clap::Parser as ClapParser;
 use confique::toml::FormatOptions;
 use confique::Config as DeriveConfig;
 use tracing::*;
 
-use crate::config::Config;
-use crate::custom_difficulty::CustomDifficulty;
-use crate::parser::Json;
-use crate::spanned::Spanned;
-
-mod config;
-mod custom_difficulty;
-mod edit_distance;
-mod handlers;
-mod late_lints;
-mod logging;
-mod parser;
-mod spanned;
+use cdlint::cache::{self, Cache, LintResult};
+use cdlint::config::Config;
+use cdlint::diagnostic::{self, ColorChoice, EmitOptions, ErrorFormat, OutputFormat};
+use cdlint::driver::{self, Input, Lowering};
+use cdlint::game_data::{self, GameData};
+use cdlint::lint::{LintGroup, LintSelection};
+use cdlint::summary::Summary;
+use cdlint::{
+    archive, commands, crash_report, hazard_estimate, html_report, logging, plugins, time_passes,
+    Diagnostics,
+};
+use rayon::prelude::*;
 
 #[derive(Debug, ClapParser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// The path to a Custom Difficulty JSON file.
-    input: PathBuf,
-}
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The path to a Custom Difficulty JSON file to lint, or to a zip archive or a directory to lint
+    /// each JSON file inside.
+    #[arg(required = true)]
+    input: Option<PathBuf>,
+
+    /// What to print. `html` prints a standalone report page instead of the diagnostics, e.g. to
+    /// redirect into a file and share.
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Print the number of diagnostics emitted by each lint.
+    #[arg(long)]
+    show_stats: bool,
+
+    /// Print how long parsing, lowering and each lint took to stderr, e.g. to find out which lint
+    /// makes linting slow. Lint results aren't cached or reused from the cache meanwhile.
+    #[arg(long)]
+    time_passes: bool,
+
+    /// Lint every file, instead of reusing what linting reported the last time a file was linted
+    /// unchanged with the same config, and don't cache what linting reports.
+    #[arg(long)]
+    no_cache: bool,
 
-type DiagnosticReport<'a> = Report<'a, (&'a String, std::ops::Range<usize>)>;
-type Diagnostics<'a> = Vec<DiagnosticReport<'a>>;
+    /// How diagnostics are printed.
+    #[arg(long, value_enum, default_value_t, global = true)]
+    error_format: ErrorFormat,
 
-pub enum ValidationResult<'d, T> {
-    Ok(T),
-    Err(DiagnosticReport<'d>),
+    /// Whether to color diagnostics. `auto` colors when printing to a terminal, unless the
+    /// `NO_COLOR` environment variable is set.
+    #[arg(long, value_enum, default_value_t, global = true)]
+    color: ColorChoice,
+
+    /// Only use ASCII characters when drawing diagnostics, for terminals and log viewers which
+    /// can't display Unicode box-drawing characters.
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Print every diagnostic of a lint which fires many times, instead of collapsing them into
+    /// one diagnostic which points at the first few.
+    #[arg(long, global = true)]
+    no_collapse: bool,
+
+    /// Which game version's vanilla data to lint against, e.g. `season05` or `latest`. Overrides
+    /// `game_version` in the config.
+    #[arg(long, value_name = "VERSION", global = true)]
+    game_version: Option<String>,
+
+    /// Only run these lints, even if they are in a lint group which is not enabled. Can be given
+    /// multiple times or as a comma-separated list.
+    #[arg(long, value_name = "LINT", value_delimiter = ',')]
+    only: Vec<String>,
+
+    /// Don't run these lints. Can be given multiple times or as a comma-separated list.
+    #[arg(long, value_name = "LINT", value_delimiter = ',')]
+    skip: Vec<String>,
+
+    /// Fail if more than this many warnings are emitted.
+    #[arg(long, value_name = "N")]
+    max_warnings: Option<usize>,
 }
 
-pub(crate) fn dummy_sp() -> SimpleSpan {
-    SimpleSpan::new(0, 0)
+// Subcommands other than linting, which is what happens without a subcommand.
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Write a starter Custom Difficulty based on a vanilla hazard level, asking for anything
+    /// which is not given.
+    Init(commands::init::InitArgs),
+    /// Print the Custom Difficulty the way the game loads it: every member spelled out, values
+    /// given for each player count, and Enemy Descriptors flattened through their `Base` chains.
+    Resolve(commands::resolve::ResolveArgs),
+    /// Manage the vanilla game data lints check against, e.g. download the data of a game version
+    /// which is newer than this release of CDLint.
+    Data(commands::data::DataArgs),
+    /// Compare two Custom Difficulties member by member after resolving them, listing added,
+    /// removed and changed values, Enemy Descriptors and pool entries.
+    Diff(commands::diff::DiffArgs),
+    /// Layer Custom Difficulties over a base Custom Difficulty, lint the result and write it.
+    Merge(commands::merge::MergeArgs),
+    /// Simulate how the game builds the enemy pool from the common, disruptive and special enemy
+    /// pools, showing representative pools and how often each Enemy Descriptor makes it in.
+    Pools(commands::pools::PoolsArgs),
+    /// Summarize the Custom Difficulty: enemy counts and resupply costs for each player count, how
+    /// the pools are changed, how many Enemy Descriptors are defined and which members are left at
+    /// the game's values.
+    Stats(commands::stats::StatsArgs),
 }
 
-fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<ExitCode> {
     logging::setup_logging();
+    crash_report::install_panic_hook();
 
     let exe_path = std::env::current_exe()?;
 
@@ -60,101 +131,299 @@ fn main() -> anyhow::Result<()> {
         let default_config = confique::toml::template::<Config>(FormatOptions::default());
         std::fs::write(&config_path, default_config)?;
     }
-    let config = Config::builder().file(&config_path).load()?;
+    let mut config = Config::builder().file(&config_path).load()?;
     debug!(?config);
+    for group in &config.enabled_lint_groups {
+        if !LintGroup::ALL.iter().any(|g| g.name() == group) {
+            warn!("unknown lint group `{}` in `enabled_lint_groups`", group);
+        }
+    }
 
     let cli = Args::parse();
+    let game_data_dir = exe_path.parent().unwrap().join(game_data::DOWNLOAD_DIR);
+    game_data::load_downloaded(&game_data_dir);
+    plugins::load(&exe_path.parent().unwrap().join(plugins::PLUGIN_DIR));
+    if let Some(game_version) = &cli.game_version {
+        config.game_version = game_version.clone();
+    }
+    if GameData::for_version(&config.game_version).is_none() {
+        let known = game_data::available()
+            .iter()
+            .map(|game_data| game_data.version)
+            .chain([game_data::LATEST])
+            .collect::<Vec<_>>();
+        bail!(
+            "unknown game version `{}`, expected one of: {}",
+            config.game_version,
+            known.join(", ")
+        );
+    }
+    cli.color.apply();
+    diagnostic::set_emit_options(EmitOptions {
+        error_format: cli.error_format,
+        ascii: cli.ascii,
+        collapse: !cli.no_collapse,
+    });
+
+    match cli.command {
+        Some(Command::Init(args)) => commands::init::run(args).map(|()| ExitCode::SUCCESS),
+        Some(Command::Resolve(args)) => {
+            commands::resolve::run(&config, args).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Data(args)) => {
+            commands::data::run(&config, &game_data_dir, args).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Diff(args)) => commands::diff::run(&config, args).map(|()| ExitCode::SUCCESS),
+        Some(Command::Merge(args)) => {
+            commands::merge::run(&config, args).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Pools(args)) => {
+            commands::pools::run(&config, args).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Stats(args)) => {
+            commands::stats::run(&config, args).map(|()| ExitCode::SUCCESS)
+        }
+        None => check(
+            &config,
+            &exe_path.parent().unwrap().join(cache::CACHE_DIR),
+            cli,
+        ),
+    }
+}
+
+/// Lints the Custom Difficulty given by `cli.input`, failing if it couldn't be linted.
+fn check(config: &Config, cache_dir: &Path, cli: Args) -> anyhow::Result<ExitCode> {
+    let selection = LintSelection::new(cli.only.clone(), cli.skip.clone())?;
+    if cli.time_passes {
+        time_passes::enable();
+    }
+    // Reused results would leave nothing to time.
+    let cache = if cli.no_cache || cli.time_passes {
+        None
+    } else {
+        Cache::open(cache_dir.to_path_buf(), config, &selection)
+    };
+    // Required unless there is a subcommand.
+    let input_path = cli.input.as_deref().unwrap();
+    if input_path.is_dir() || archive::is_archive(input_path) {
+        return check_files(config, &selection, cache.as_ref(), &cli, input_path);
+    }
+    let input = Input::read(input_path)?;
+
+    let summary = match cli.format {
+        OutputFormat::Text => {
+            let result = match lint_file(config, &selection, cache.as_ref(), &input) {
+                Ok(result) => result,
+                Err(failure) => {
+                    let (diagnostics, error) = failure.into_parts();
+                    input.print(&diagnostics)?;
+                    if error.is_none() {
+                        Summary::new(&diagnostics).print(cli.show_stats);
+                    }
+                    if cli.time_passes {
+                        time_passes::print_report();
+                    }
+                    return match error {
+                        Some(e) => Err(e),
+                        None => Ok(ExitCode::FAILURE),
+                    };
+                }
+            };
+            let summary = Summary::new(&result.diagnostics);
+            input.print(&result.diagnostics)?;
+            summary.print(cli.show_stats);
 
-    debug!(input = ?cli.input);
+            if !result.estimates.is_empty() {
+                println!();
+                for estimate in &result.estimates {
+                    println!("note: {estimate}");
+                }
+                println!("note: hazard levels are estimated from approximate vanilla values");
+            }
+            summary
+        }
+        // The report shows the lowered Custom Difficulty, so it is never cached.
+        OutputFormat::Html => {
+            let mut loaded = driver::load(config, &selection, &input, cli.show_stats)?;
+            driver::lint(config, &selection, &input, &mut loaded)?;
 
-    let json_string = match std::fs::read_to_string(&cli.input) {
-        Ok(file) => file,
-        Err(e) => {
-            error!(path = ?cli.input, "failed to read input");
-            return Err(e)
-                .with_context(|| format!("failed to read file `{}`", cli.input.display()));
+            let diagnostics = loaded
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| !loaded.suppressions.is_suppressed(diagnostic))
+                .collect::<Vec<_>>();
+            let summary = Summary::new(diagnostics.iter().copied());
+            let estimates = hazard_estimate::estimate_hazards(&loaded.cd);
+            print!(
+                "{}",
+                html_report::render(&input, &loaded.cd, &diagnostics, &summary, &estimates)
+            );
+            summary
         }
     };
+    if cli.time_passes {
+        time_passes::print_report();
+    }
 
-    let path = cli.input.display().to_string();
-
-    let (custom_difficulty_json, errors) =
-        parser::parser().parse(&json_string).into_output_errors();
-    debug!(?errors);
-
-    errors.into_iter().for_each(|e| {
-        Report::build(ReportKind::Error, &path, e.span().start)
-            .with_message(e.to_string())
-            .with_label(
-                Label::new((&path, e.span().into_range()))
-                    .with_message(e.reason().to_string())
-                    .with_color(Color::Red),
-            )
-            .finish()
-            .print((&path, Source::from(&json_string)))
-            .unwrap()
-    });
+    check_max_warnings(&summary, cli.max_warnings)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Why a file couldn't be linted, see [`lint_file`].
+enum LintFailure<'d> {
+    /// Nothing can be lowered because the file is empty or not an object.
+    NothingToLower(Diagnostics<'d>),
+    Failed(Diagnostics<'d>, anyhow::Error),
+}
+
+impl<'d> LintFailure<'d> {
+    /// The diagnostics saying why the file couldn't be linted, and the error if lowering or
+    /// linting it failed.
+    fn into_parts(self) -> (Diagnostics<'d>, Option<anyhow::Error>) {
+        match self {
+            LintFailure::NothingToLower(diagnostics) => (diagnostics, None),
+            LintFailure::Failed(diagnostics, e) => (diagnostics, Some(e)),
+        }
+    }
+}
+
+/// Lints the file, or reuses what linting it reported last time if it is cached. Nothing is
+/// printed, so that files can be linted in parallel.
+fn lint_file<'d>(
+    config: &Config,
+    selection: &LintSelection,
+    cache: Option<&Cache>,
+    input: &'d Input,
+) -> Result<LintResult<'d>, LintFailure<'d>> {
+    if let Some(result) = cache.and_then(|cache| cache.get(input)) {
+        return Ok(result);
+    }
+
+    let mut loaded = match driver::lower(config, selection, input) {
+        Lowering::Lowered(loaded) => *loaded,
+        Lowering::NothingToLower(diagnostics) => {
+            return Err(LintFailure::NothingToLower(diagnostics))
+        }
+        Lowering::Failed(diagnostics, e) => return Err(LintFailure::Failed(diagnostics, e)),
+    };
+    let linted = driver::lint(config, selection, input, &mut loaded);
+    let suppressions = &loaded.suppressions;
+    let diagnostics = loaded
+        .diagnostics
+        .into_iter()
+        .filter(|diagnostic| !suppressions.is_suppressed(diagnostic))
+        .collect();
+    if let Err(e) = linted {
+        // Linting can fail after the lints reported their diagnostics, e.g. when writing the
+        // cyclic reference graph, so they are printed before the error like when lowering fails.
+        return Err(LintFailure::Failed(diagnostics, e));
+    }
 
-    let Some(custom_difficulty_json) = custom_difficulty_json else {
-        bail!("failed to parse Custom Difficulty JSON");
+    let result = LintResult {
+        diagnostics,
+        estimates: hazard_estimate::estimate_hazards(&loaded.cd)
+            .iter()
+            .map(|estimate| estimate.describe())
+            .collect(),
     };
+    if let Some(cache) = cache {
+        cache.insert(input, &result);
+    }
+    Ok(result)
+}
 
-    let Spanned {
-        val: Json::Object(Spanned {
-            val: top_level_members,
-            ..
-        }),
-        ..
-    } = custom_difficulty_json
-    else {
-        bail!("unexpected top level JSON kind");
+/// Lints each JSON file inside the zip archive or the directory, printing the diagnostics of each
+/// file in turn and one summary for all of them. A file which couldn't be linted doesn't stop the
+/// others from being linted, but makes the check fail.
+fn check_files(
+    config: &Config,
+    selection: &LintSelection,
+    cache: Option<&Cache>,
+    cli: &Args,
+    path: &Path,
+) -> anyhow::Result<ExitCode> {
+    if cli.format == OutputFormat::Html {
+        bail!(
+            "`--format html` is not supported for archives and directories, lint the files inside \
+             one at a time"
+        );
+    }
+    let mut inputs = if path.is_dir() {
+        Input::read_dir(path)?
+    } else {
+        archive::read_json_entries(path)?
     };
+    for input in &mut inputs {
+        input.name_reference_graph_after_path();
+    }
+
+    // The files are linted in parallel, but printed in order once all of them are linted, so that
+    // the output is the same as if they were linted one after another.
+    let results = inputs
+        .par_iter()
+        .map(|input| lint_file(config, selection, cache, input))
+        .collect::<Vec<_>>();
+
+    let mut summary = Summary::default();
+    let mut estimates = Vec::new();
+    let mut failures = 0;
+    for (input, result) in inputs.iter().zip(results) {
+        let result = match result {
+            Ok(result) => result,
+            Err(failure) => {
+                failures += 1;
+                let (diagnostics, error) = failure.into_parts();
+                input.print(&diagnostics)?;
+                summary.add(Summary::new(&diagnostics));
+                if let Some(e) = error {
+                    eprintln!(
+                        "Error: {:?}",
+                        e.context(format!("trying to lint `{}`", input.path))
+                    );
+                }
+                continue;
+            }
+        };
+        input.print(&result.diagnostics)?;
+        summary.add(Summary::new(&result.diagnostics));
+        for estimate in result.estimates {
+            estimates.push(format!("`{}`: {estimate}", input.path));
+        }
+    }
 
-    let mut diagnostics = Vec::new();
-    let mut custom_difficulty = CustomDifficulty::default();
-
-    // There are two kinds of lints:
-    // 1. Early-pass lints: these lints are performed while parsing the CD JSON into the CD struct.
-    // 2. Late-pass lints: these lints are performed on the built CD struct.
-
-    handlers::handle_top_level_members(
-        &mut diagnostics,
-        &path,
-        &json_string,
-        &mut custom_difficulty,
-        &top_level_members,
-    )
-    .context("trying to process top level members")?;
-
-    late_lints::lint_empty_cd_name(&config, &custom_difficulty, &path, &mut diagnostics);
-    late_lints::lint_undefined_enemy_descriptors(
-        &config,
-        &custom_difficulty,
-        &path,
-        &mut diagnostics,
-    );
-    late_lints::lint_min_larger_than_max(&config, &custom_difficulty, &path, &mut diagnostics);
-    late_lints::lint_unused_custom_enemy_descriptors(
-        &config,
-        &custom_difficulty,
-        &path,
-        &mut diagnostics,
-    );
-    late_lints::lint_ambiguous_enemy_pool_add_remove(
-        &config,
-        &custom_difficulty,
-        &path,
-        &mut diagnostics,
-    );
-    late_lints::lint_cyclic_enemy_descriptor_references(
-        &config,
-        &custom_difficulty,
-        &path,
-        &mut diagnostics,
-    )?;
-
-    for diagnostic in &diagnostics {
-        diagnostic.print((&path, Source::from(&json_string)))?;
+    println!("linted {} JSON files in `{}`", inputs.len(), path.display());
+    if failures > 0 {
+        println!("{failures} of them couldn't be linted");
+    }
+    summary.print(cli.show_stats);
+    if !estimates.is_empty() {
+        println!();
+        for estimate in &estimates {
+            println!("note: {estimate}");
+        }
+        println!("note: hazard levels are estimated from approximate vanilla values");
+    }
+    if cli.time_passes {
+        time_passes::print_report();
+    }
+
+    check_max_warnings(&summary, cli.max_warnings)?;
+    Ok(if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn check_max_warnings(summary: &Summary, max_warnings: Option<usize>) -> anyhow::Result<()> {
+    if let Some(max_warnings) = max_warnings {
+        if summary.warnings > max_warnings {
+            bail!(
+                "{} warnings emitted, which exceeds --max-warnings {}",
+                summary.warnings,
+                max_warnings
+            );
+        }
     }
 
     Ok(())
@@ -1,17 +1,35 @@
-//! Parser for a Custom Difficulty JSON.
+//! Parser for a Custom Difficulty JSON, operating on the tokens produced by the [lexer].
 
+use std::fmt;
+use std::ops::Range;
+
+use chumsky::error::{Error, RichReason};
+use chumsky::input::SpannedInput;
+use chumsky::label::LabelError;
 use chumsky::prelude::*;
+use chumsky::util::MaybeRef;
+use tracing::*;
 
-use crate::Spanned;
+use crate::config::Config;
+use crate::diagnostic::{
+    Applicability, Color, Fmt, Label, Report, ReportBuilder, ReportKind, Suggestion,
+};
+use crate::lexer::{self, Token};
+use crate::lint::JSON_COMMENTS;
+use crate::{DiagnosticReport, Diagnostics, Spanned};
 
 #[derive(Clone, Debug)]
 pub enum Json {
+    #[allow(dead_code)]
     Null(Spanned<()>),
     Bool(Spanned<bool>),
     Str(Spanned<String>),
     Num(Spanned<f64>),
     Array(Spanned<Vec<Spanned<Json>>>),
     Object(Spanned<Vec<(Spanned<String>, Spanned<Json>)>>),
+    /// A value which could not be parsed, recovered from so that later syntax errors are still
+    /// reported.
+    Invalid,
 }
 
 impl Json {
@@ -23,163 +41,804 @@ impl Json {
             Json::Num(_) => "number",
             Json::Array(_) => "array",
             Json::Object(_) => "object",
+            Json::Invalid => "invalid value",
+        }
+    }
+
+    /// Removes invalid values, along with the members and array elements containing them, so that
+    /// the syntax errors already reported for them don't cause further diagnostics.
+    pub fn remove_invalid(&mut self) {
+        match self {
+            Json::Array(a) => {
+                a.val.retain(|elem| !matches!(elem.val, Json::Invalid));
+                a.val.iter_mut().for_each(|elem| elem.val.remove_invalid());
+            }
+            Json::Object(obj) => {
+                obj.val.retain(|(_, val)| !matches!(val.val, Json::Invalid));
+                obj.val
+                    .iter_mut()
+                    .for_each(|(_, val)| val.val.remove_invalid());
+            }
+            _ => {}
+        }
+    }
+
+    /// The value without spans, e.g. for writing it back out. Integral numbers become integers, and
+    /// of duplicate members only the first is kept, as it is the one which is lowered.
+    pub fn to_value(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            Json::Null(_) | Json::Invalid => Value::Null,
+            Json::Bool(b) => Value::Bool(b.val),
+            Json::Str(s) => Value::String(s.val.clone()),
+            Json::Num(n) if n.val.fract() == 0.0 && n.val.abs() < 2f64.powi(53) => {
+                Value::from(n.val as i64)
+            }
+            Json::Num(n) => Value::from(n.val),
+            Json::Array(a) => a.val.iter().map(|elem| elem.val.to_value()).collect(),
+            Json::Object(obj) => {
+                let mut members = serde_json::Map::new();
+                for (name, val) in &obj.val {
+                    members
+                        .entry(name.val.clone())
+                        .or_insert_with(|| val.val.to_value());
+                }
+                Value::Object(members)
+            }
+        }
+    }
+}
+
+/// A syntax mistake the parser recovered from, which gets a more specific diagnostic than a generic
+/// "found X expected Y" parse error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyntaxIssue {
+    /// Object members which are not separated by a `,`. `next` is the offset of the second member.
+    MissingComma { next: usize },
+    /// A `,` after the last element of an array or the last member of an object.
+    TrailingComma { span: Range<usize> },
+    /// A string delimited by `'` instead of `"`.
+    SingleQuotedString { span: Range<usize> },
+    /// An object key which is not a string, e.g. `{ Name: "x" }`.
+    UnquotedKey { span: Range<usize> },
+    /// A `//` or `/* */` comment, which JSON does not allow.
+    Comment { span: Range<usize> },
+    /// A literal with the wrong case or from another language, e.g. `True` or `None`.
+    /// `replacement` is the JSON literal it was parsed as.
+    WrongCaseLiteral {
+        span: Range<usize>,
+        replacement: &'static str,
+    },
+    /// Control characters such as line breaks inside a string, which must be escaped.
+    ControlCharacters { spans: Vec<Range<usize>> },
+    /// An escape sequence which JSON does not define, e.g. `\q`.
+    UnknownEscape { span: Range<usize> },
+    /// A number with a leading `+`, leading zeros or in hexadecimal, e.g. `+1.5`, `010` or `0x10`.
+    /// `replacement` is the same number written as JSON.
+    NonJsonNumber {
+        span: Range<usize>,
+        replacement: String,
+    },
+}
+
+impl SyntaxIssue {
+    /// The offset the syntax issue is reported at.
+    fn offset(&self) -> usize {
+        match self {
+            SyntaxIssue::MissingComma { next } => *next,
+            SyntaxIssue::ControlCharacters { spans } => spans[0].start,
+            SyntaxIssue::TrailingComma { span }
+            | SyntaxIssue::SingleQuotedString { span }
+            | SyntaxIssue::UnquotedKey { span }
+            | SyntaxIssue::Comment { span }
+            | SyntaxIssue::WrongCaseLiteral { span, .. }
+            | SyntaxIssue::UnknownEscape { span }
+            | SyntaxIssue::NonJsonNumber { span, .. } => span.start,
+        }
+    }
+
+    /// The lint reporting this syntax issue, if it can be suppressed.
+    pub fn lint(&self) -> Option<&'static str> {
+        match self {
+            SyntaxIssue::Comment { .. } => Some(JSON_COMMENTS),
+            SyntaxIssue::MissingComma { .. }
+            | SyntaxIssue::TrailingComma { .. }
+            | SyntaxIssue::SingleQuotedString { .. }
+            | SyntaxIssue::UnquotedKey { .. }
+            | SyntaxIssue::WrongCaseLiteral { .. }
+            | SyntaxIssue::ControlCharacters { .. }
+            | SyntaxIssue::UnknownEscape { .. }
+            | SyntaxIssue::NonJsonNumber { .. } => None,
         }
     }
+
+    pub fn report<'d>(&self, config: &Config, path: &'d String, src: &str) -> DiagnosticReport<'d> {
+        match self {
+            SyntaxIssue::MissingComma { next } => {
+                // The `,` belongs right after the previous member, not before the next one.
+                let at = src[..*next].trim_end().len();
+                Report::build(ReportKind::Error, path, at)
+                    .with_message("missing `,` between object members")
+                    .with_label(
+                        Label::new((path, at..at))
+                            .with_color(Color::Red)
+                            .with_message("add a `,` here"),
+                    )
+                    .with_suggestion(Suggestion::insert(
+                        at,
+                        ",",
+                        Applicability::MachineApplicable,
+                    ))
+                    .finish()
+            }
+            SyntaxIssue::TrailingComma { span } => {
+                Report::build(ReportKind::Warning, path, span.start)
+                    .with_message("trailing comma")
+                    .with_label(
+                        Label::new((path, span.clone()))
+                            .with_color(Color::Yellow)
+                            .with_message("remove this `,`"),
+                    )
+                    .with_note("JSON does not allow a `,` after the last element or member")
+                    .with_suggestion(Suggestion::remove(
+                        span.clone(),
+                        "the trailing `,`",
+                        Applicability::MachineApplicable,
+                    ))
+                    .finish()
+            }
+            SyntaxIssue::SingleQuotedString { span } => {
+                let contents = &src[span.start + 1..span.end - 1];
+                Report::build(ReportKind::Error, path, span.start)
+                    .with_message("strings must be delimited by double quotes")
+                    .with_label(
+                        Label::new((path, span.clone()))
+                            .with_color(Color::Red)
+                            .with_message("this string uses single quotes"),
+                    )
+                    .with_suggestion(Suggestion::new(
+                        span.clone(),
+                        format!("\"{}\"", contents.replace('"', "\\\"")),
+                        // Escapes such as `\'` may not be valid in a double quoted string.
+                        if contents.contains('\\') {
+                            Applicability::MaybeIncorrect
+                        } else {
+                            Applicability::MachineApplicable
+                        },
+                    ))
+                    .finish()
+            }
+            SyntaxIssue::UnquotedKey { span } => Report::build(ReportKind::Error, path, span.start)
+                .with_message("object keys must be strings")
+                .with_label(
+                    Label::new((path, span.clone()))
+                        .with_color(Color::Red)
+                        .with_message("this key is not quoted"),
+                )
+                .with_suggestion(Suggestion::new(
+                    span.clone(),
+                    format!("\"{}\"", &src[span.clone()]),
+                    Applicability::MachineApplicable,
+                ))
+                .finish(),
+            SyntaxIssue::Comment { span } => {
+                let (kind, color) = if config.deny_json_comments {
+                    (ReportKind::Error, Color::Red)
+                } else {
+                    (ReportKind::Warning, Color::Yellow)
+                };
+                Report::build(kind, path, span.start)
+                    .with_message("comments are not allowed in JSON")
+                    .with_label(
+                        Label::new((path, span.clone()))
+                            .with_color(color)
+                            .with_message(
+                                "the game may fail to load a Custom Difficulty with this comment",
+                            ),
+                    )
+                    .with_suggestion(Suggestion::remove(
+                        span.clone(),
+                        "the comment",
+                        Applicability::MachineApplicable,
+                    ))
+                    .finish()
+            }
+            SyntaxIssue::WrongCaseLiteral { span, replacement } => {
+                let literal = &src[span.clone()];
+                let label = if literal.eq_ignore_ascii_case(replacement) {
+                    "JSON literals are lowercase".to_string()
+                } else {
+                    format!("JSON uses `{replacement}` instead")
+                };
+                Report::build(ReportKind::Error, path, span.start)
+                    .with_message(format!("`{literal}` is not a JSON literal"))
+                    .with_label(
+                        Label::new((path, span.clone()))
+                            .with_color(Color::Red)
+                            .with_message(label),
+                    )
+                    .with_suggestion(Suggestion::new(
+                        span.clone(),
+                        replacement,
+                        Applicability::MachineApplicable,
+                    ))
+                    .finish()
+            }
+            SyntaxIssue::ControlCharacters { spans } => {
+                let labels = spans.iter().map(|span| {
+                    let escaped = match &src[span.clone()] {
+                        "\r\n" => "\\r\\n".to_string(),
+                        "\n" => "\\n".to_string(),
+                        "\r" => "\\r".to_string(),
+                        "\t" => "\\t".to_string(),
+                        c => format!("\\u{:04X}", c.chars().next().unwrap() as u32),
+                    };
+                    Label::new((path, span.clone()))
+                        .with_color(Color::Yellow)
+                        .with_message(format!("escape this as `{escaped}`"))
+                });
+                Report::build(ReportKind::Warning, path, spans[0].start)
+                    .with_message(if spans.len() == 1 {
+                        "unescaped control character in string"
+                    } else {
+                        "unescaped control characters in string"
+                    })
+                    .with_labels(labels)
+                    .with_note(
+                        "JSON does not allow control characters such as line breaks in strings",
+                    )
+                    .finish()
+            }
+            SyntaxIssue::UnknownEscape { span } => {
+                let escape = &src[span.clone()];
+                Report::build(ReportKind::Error, path, span.start)
+                    .with_message(format!("unknown escape sequence `{escape}`"))
+                    .with_label(
+                        Label::new((path, span.clone()))
+                            .with_color(Color::Red)
+                            .with_message("unknown escape sequence"),
+                    )
+                    .with_help(format!(
+                        "to include a literal {} in the string, escape it as {}",
+                        "\\".fg(Color::Blue),
+                        "\\\\".fg(Color::Blue)
+                    ))
+                    .finish()
+            }
+            SyntaxIssue::NonJsonNumber { span, replacement } => {
+                let number = &src[span.clone()];
+                let (message, label) = if number.contains(['x', 'X']) {
+                    (
+                        "hexadecimal numbers are not allowed in JSON",
+                        "this number is hexadecimal",
+                    )
+                } else if number.starts_with('+') {
+                    (
+                        "JSON numbers can't start with `+`",
+                        "this number has a leading `+`",
+                    )
+                } else {
+                    (
+                        "leading zeros are not allowed in JSON numbers",
+                        "this number has leading zeros",
+                    )
+                };
+                Report::build(ReportKind::Warning, path, span.start)
+                    .with_message(message)
+                    .with_label(
+                        Label::new((path, span.clone()))
+                            .with_color(Color::Yellow)
+                            .with_message(label),
+                    )
+                    .with_suggestion(Suggestion::new(
+                        span.clone(),
+                        replacement,
+                        Applicability::MachineApplicable,
+                    ))
+                    .with_note(
+                        "JSON numbers are decimal, with an optional `-` sign and no leading zeros, \
+                         e.g. `-1.5e3`",
+                    )
+                    .finish()
+            }
+        }
+    }
+}
+
+/// The result of lexing and parsing a Custom Difficulty JSON.
+pub struct Parsed<'d> {
+    /// `None` if nothing could be parsed.
+    pub json: Option<Spanned<Json>>,
+    pub errors: Diagnostics<'d>,
+    /// Syntax mistakes which were recovered from, which are reported separately from `errors` so
+    /// that the ones belonging to a lint can be suppressed.
+    pub issues: Vec<SyntaxIssue>,
+}
+
+/// Lexes and parses the input. Both passes recover from syntax errors, so that every syntax error
+/// in a file is reported in one run and the rest of the file can still be linted.
+pub fn parse<'d>(config: &Config, path: &'d String, src: &str) -> Parsed<'d> {
+    let mut issues = Vec::new();
+    let (tokens, lex_errors) = lexer::lexer()
+        .parse_with_state(src, &mut issues)
+        .into_output_errors();
+    let tokens = tokens.unwrap_or_default();
+    debug!(?lex_errors);
+
+    if let Some(report) = nesting_too_deep_report(config, path, &tokens) {
+        return Parsed {
+            json: None,
+            errors: vec![report],
+            issues: Vec::new(),
+        };
+    }
+
+    let eoi = SimpleSpan::new(src.len(), src.len());
+    let (json, parse_errors) = parser()
+        .parse_with_state(tokens.as_slice().spanned(eoi), &mut issues)
+        .into_output_errors();
+    debug!(?parse_errors);
+    // The lexer records its issues before the parser records any, so sort them to report them in
+    // the order they appear in the file.
+    issues.sort_by_key(SyntaxIssue::offset);
+
+    let delimiters = match_delimiters(src, &tokens);
+    let mut errors = Vec::new();
+    let mut at_end_of_input = false;
+    for (end_of_input, report) in lex_errors
+        .iter()
+        .map(|e| (is_end_of_input(e), parse_error_report(e, path, &delimiters)))
+        .chain(
+            parse_errors
+                .iter()
+                .map(|e| (is_end_of_input(e), parse_error_report(e, path, &delimiters))),
+        )
+    {
+        // Each level of nesting which is not closed runs out of input, but the first error about
+        // it already points at every unclosed delimiter.
+        if end_of_input && std::mem::replace(&mut at_end_of_input, true) {
+            continue;
+        }
+        errors.push(report);
+    }
+    // The parser does not always run out of input when a delimiter is not closed, since it
+    // recovers by skipping ahead to the next delimiter. If none of the errors point at the end of
+    // input, unclosed delimiters get their own error.
+    if !errors.is_empty() && !at_end_of_input {
+        errors.extend(unclosed_delimiter_report(path, &delimiters));
+    }
+
+    Parsed {
+        json,
+        errors,
+        issues,
+    }
+}
+
+/// Whether the error is caused by running out of input, as opposed to a custom error which doesn't
+/// have a found token either.
+fn is_end_of_input<T>(e: &Rich<'_, T>) -> bool {
+    matches!(e.reason(), RichReason::ExpectedFound { found: None, .. })
+}
+
+fn parse_error_report<'d, T: fmt::Display>(
+    e: &Rich<'_, T>,
+    path: &'d String,
+    delimiters: &Delimiters,
+) -> DiagnosticReport<'d> {
+    let span = ordered_span(*e.span());
+    let mut report = Report::build(ReportKind::Error, path, span.start)
+        .with_message(e.to_string())
+        .with_label(
+            Label::new((path, span.into_range()))
+                .with_message(e.reason().to_string())
+                .with_color(Color::Red),
+        );
+
+    // Running out of input is usually caused by a missing closing delimiter, so point at the
+    // delimiters which were never closed.
+    if is_end_of_input(e) {
+        add_unclosed_delimiter_labels(&mut report, path, delimiters);
+    }
+
+    report.finish()
+}
+
+fn unclosed_delimiter_report<'d>(
+    path: &'d String,
+    delimiters: &Delimiters,
+) -> Option<DiagnosticReport<'d>> {
+    let first = *delimiters.unclosed.first()?;
+    let mut report = Report::build(ReportKind::Error, path, first)
+        .with_message("this file contains an unclosed delimiter");
+    add_unclosed_delimiter_labels(&mut report, path, delimiters);
+    Some(report.finish())
+}
+
+fn add_unclosed_delimiter_labels<'d>(
+    report: &mut ReportBuilder<'d>,
+    path: &'d String,
+    delimiters: &Delimiters,
+) {
+    if delimiters.unclosed.is_empty() {
+        return;
+    }
+
+    for open in &delimiters.unclosed {
+        report.add_label(
+            Label::new((path, *open..*open + 1))
+                .with_color(Color::Yellow)
+                .with_message("unclosed delimiter"),
+        );
+    }
+    for (open, close) in &delimiters.misindented {
+        report.add_label(
+            Label::new((path, *open..*open + 1))
+                .with_color(Color::Cyan)
+                .with_message("this delimiter might not be properly closed..."),
+        );
+        report.add_label(
+            Label::new((path, *close..*close + 1))
+                .with_color(Color::Cyan)
+                .with_message("...as it matches this but it has different indentation"),
+        );
+    }
+}
+
+#[derive(Debug, Default)]
+struct Delimiters {
+    /// Offsets of `{` and `[` which are never closed.
+    unclosed: Vec<usize>,
+    /// Offsets of delimiter pairs whose closing delimiter starts a line with a different
+    /// indentation than the line of the opening delimiter, which hints at where a closing delimiter
+    /// is actually missing.
+    misindented: Vec<(usize, usize)>,
+}
+
+/// The offsets of `{`, `}`, `[` and `]` tokens.
+fn delimiters<'t>(
+    tokens: &'t [(Token<'_>, SimpleSpan)],
+) -> impl Iterator<Item = (usize, char)> + 't {
+    tokens.iter().filter_map(|(token, span)| match token {
+        Token::Ctrl(c @ ('{' | '}' | '[' | ']')) => Some((span.start, *c)),
+        _ => None,
+    })
 }
 
-/// Taken from: <https://github.com/zesterer/chumsky/blob/main/examples/json.rs>.
-pub fn parser<'a>() -> impl Parser<'a, &'a str, Spanned<Json>, extra::Err<Rich<'a, char>>> {
+/// Matches `{}` and `[]` tokens.
+fn match_delimiters(src: &str, tokens: &[(Token<'_>, SimpleSpan)]) -> Delimiters {
+    let indentation = |offset: usize| {
+        let line_start = src[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = &src[line_start..];
+        let indent = line.len() - line.trim_start().len();
+        (line_start, indent)
+    };
+
+    let mut delimiters = Delimiters::default();
+    let mut open = Vec::new();
+    for (i, c) in self::delimiters(tokens) {
+        if matches!(c, '{' | '[') {
+            open.push(i);
+            continue;
+        }
+        let Some(o) = open.pop() else {
+            continue;
+        };
+        let (line_start, indent) = indentation(i);
+        // Only closing delimiters on their own line are expected to line up.
+        if line_start + indent == i && indentation(o).1 != indent {
+            delimiters.misindented.push((o, i));
+        }
+    }
+    delimiters.unclosed = open;
+    delimiters
+}
+
+/// Empty input would otherwise be reported as a generic parse error.
+pub fn empty_input_report<'d>(path: &'d String, src: &str) -> Option<DiagnosticReport<'d>> {
+    if !src.trim().is_empty() {
+        return None;
+    }
+
+    let report = Report::build(ReportKind::Error, path, 0)
+        .with_message(if src.is_empty() {
+            "file is empty"
+        } else {
+            "file only contains whitespace"
+        })
+        .with_label(
+            Label::new((path, 0..0))
+                .with_color(Color::Red)
+                .with_message("expected a Custom Difficulty object"),
+        )
+        .with_help(format!(
+            "a Custom Difficulty is an object of members, e.g. {}; {} writes a starter one",
+            "{ \"Name\": \"My Custom Difficulty\", ... }".fg(Color::Blue),
+            "cdlint init".fg(Color::Blue)
+        ))
+        .finish();
+    Some(report)
+}
+
+/// The parser is recursive, so deeply nested arrays and objects could overflow the stack. This
+/// checks the nesting depth before parsing.
+fn nesting_too_deep_report<'d>(
+    config: &Config,
+    path: &'d String,
+    tokens: &[(Token<'_>, SimpleSpan)],
+) -> Option<DiagnosticReport<'d>> {
+    let mut depth = 0usize;
+    for (i, c) in delimiters(tokens) {
+        if matches!(c, '}' | ']') {
+            depth = depth.saturating_sub(1);
+            continue;
+        }
+        depth += 1;
+        if depth > config.max_json_nesting_depth {
+            let report = Report::build(ReportKind::Error, path, i)
+                .with_message("JSON nesting too deep")
+                .with_label(
+                    Label::new((path, i..i + 1))
+                        .with_color(Color::Red)
+                        .with_message(format!(
+                            "this exceeds the maximum nesting depth of {}",
+                            config.max_json_nesting_depth
+                        )),
+                )
+                .with_note(format!(
+                    "the maximum nesting depth can be changed with \"{}\" in the config",
+                    "max_json_nesting_depth".fg(Color::Blue)
+                ))
+                .finish();
+            return Some(report);
+        }
+    }
+    None
+}
+
+type ParserInput<'t, 'a> = SpannedInput<Token<'a>, SimpleSpan, &'t [(Token<'a>, SimpleSpan)]>;
+
+/// Syntax issues are collected in the parser state rather than emitted as errors, because the
+/// parser accepts the input as if the mistake wasn't there.
+type ParserExtra<'t, 'a> = extra::Full<Rich<'t, Token<'a>>, Vec<SyntaxIssue>, ()>;
+
+/// Records the syntax issue, unless it was already recorded by a branch the parser backtracked out
+/// of.
+pub fn record_issue(issues: &mut Vec<SyntaxIssue>, issue: SyntaxIssue) {
+    if !issues.contains(&issue) {
+        issues.push(issue);
+    }
+}
+
+/// The span chumsky computes for an empty range of tokens, e.g. between a pair of delimiters or at
+/// the end of input, is reversed: it goes from the start of the next token to the end of the
+/// previous one. This puts it back in order, so that it covers the gap between the tokens.
+fn ordered_span(span: SimpleSpan) -> SimpleSpan {
+    SimpleSpan::new(span.start.min(span.end), span.start.max(span.end))
+}
+
+/// `span` extended to cover the spans inside it. Without a closing delimiter, the contents of an
+/// array or object can end past its last token, at the end of input.
+fn covering_span(span: SimpleSpan, inner: impl IntoIterator<Item = SimpleSpan>) -> SimpleSpan {
+    inner.into_iter().fold(span, |span, inner| {
+        SimpleSpan::new(span.start.min(inner.start), span.end.max(inner.end))
+    })
+}
+
+/// Accepts a token for which `f` returns `Some`, which is described as `label` in errors.
+///
+/// Unlike `select!`, the error is reported at the mismatched token rather than at the token after
+/// it, so that it is merged with the errors of the other alternatives tried at the same token.
+/// Since [`Parser::labelled`] only relabels errors reported at the token after, the label is part
+/// of the error instead.
+fn token<'t, 'a: 't, O>(
+    label: Option<&'static str>,
+    f: impl Fn(Token<'a>) -> Option<O> + Clone,
+) -> impl Parser<'t, ParserInput<'t, 'a>, O, ParserExtra<'t, 'a>> + Clone {
+    custom(move |inp| {
+        let before = inp.offset();
+        let found = inp.next();
+        if let Some(out) = found.clone().and_then(&f) {
+            return Ok(out);
+        }
+        let mut error = <Rich<_> as Error<ParserInput>>::expected_found(
+            [],
+            found.map(MaybeRef::Val),
+            inp.span_since(before),
+        );
+        if let Some(label) = label {
+            LabelError::<ParserInput, _>::label_with(&mut error, label);
+        }
+        Err(error)
+    })
+}
+
+/// Accepts a `,` directly before the `close` delimiter, recording it as a trailing comma.
+fn trailing_comma<'t, 'a: 't>(
+    close: char,
+) -> impl Parser<'t, ParserInput<'t, 'a>, (), ParserExtra<'t, 'a>> + Clone {
+    just(Token::Ctrl(','))
+        .map_with(|_, e| -> SimpleSpan { e.span() })
+        .then_ignore(just(Token::Ctrl(close)).rewind())
+        // Only recorded once the closing delimiter is found, since the parser state is not reset
+        // when backtracking.
+        .validate(|span, e, _| {
+            record_issue(
+                e.state(),
+                SyntaxIssue::TrailingComma {
+                    span: span.into_range(),
+                },
+            );
+        })
+}
+
+/// Closes an array or object. A missing closing delimiter is recovered from, and reported by
+/// [`unclosed_delimiter_report`] and [`parse_error_report`].
+fn closing_delimiter<'t, 'a: 't>(
+    close: char,
+) -> impl Parser<'t, ParserInput<'t, 'a>, (), ParserExtra<'t, 'a>> + Clone {
+    just(Token::Ctrl(close))
+        .ignored()
+        .recover_with(via_parser(end()))
+        .recover_with(skip_then_retry_until(any().ignored(), end()))
+}
+
+/// The JSON literal an identifier is likely meant to be, e.g. `true` for `True` or `null` for
+/// Python's `None`.
+fn json_literal_for(ident: &str) -> Option<&'static str> {
+    match ident.to_ascii_lowercase().as_str() {
+        "true" => Some("true"),
+        "false" => Some("false"),
+        "null" | "none" | "nil" => Some("null"),
+        _ => None,
+    }
+}
+
+/// Adapted from: <https://github.com/zesterer/chumsky/blob/main/examples/json.rs>, but operating on
+/// the tokens produced by the [lexer][lexer::lexer].
+///
+/// The parser recovers from syntax errors by skipping to the next delimiter, so that every syntax
+/// error in a file is reported in one run and the rest of the file can still be linted. Values
+/// which could not be parsed are [`Json::Invalid`].
+fn parser<'t, 'a: 't>() -> impl Parser<'t, ParserInput<'t, 'a>, Spanned<Json>, ParserExtra<'t, 'a>>
+{
     recursive(|value| {
-        let digits = text::digits(10).to_slice();
-
-        let frac = just('.').then(digits);
-
-        let exp = just('e')
-            .or(just('E'))
-            .then(one_of("+-").or_not())
-            .then(digits)
-            .labelled("exponent");
-
-        let number = just('-')
-            .or_not()
-            .then(text::int(10))
-            .then(frac.or_not())
-            .then(exp.or_not())
-            .to_slice()
-            .map_with(|s: &str, e| {
-                let n: f64 = s.parse().unwrap();
-                Spanned {
-                    span: e.span(),
-                    val: n,
-                }
-            })
-            .boxed()
-            .labelled("number");
-
-        let escape = just('\\')
-            .then(choice((
-                just('\\'),
-                just('/'),
-                just('"'),
-                just('b').to('\x08'),
-                just('f').to('\x0C'),
-                just('n').to('\n'),
-                just('r').to('\r'),
-                just('t').to('\t'),
-                just('u').ignore_then(text::digits(16).exactly(4).to_slice().validate(
-                    |digits, e, emitter| {
-                        char::from_u32(u32::from_str_radix(digits, 16).unwrap()).unwrap_or_else(
-                            || {
-                                emitter.emit(Rich::custom(e.span(), "invalid unicode character"));
-                                '\u{FFFD}' // unicode replacement character
-                            },
-                        )
-                    },
-                )),
-            )))
-            .ignored()
-            .boxed()
-            .labelled("escape character");
-
-        let string = none_of("\\\"")
-            .ignored()
-            .or(escape)
-            .repeated()
-            .to_slice()
-            .map(ToString::to_string)
-            .delimited_by(just('"'), just('"'))
-            .map_with(|val, e| Spanned {
-                span: e.span(),
-                val,
-            })
-            .boxed()
-            .labelled("string");
+        let string = token(Some("string"), |t| match t {
+            Token::Str(s) => Some(s.to_string()),
+            _ => None,
+        })
+        .map_with(|val, e| Spanned::new(val, e.span()));
+
+        let number = token(Some("number"), |t| match t {
+            Token::Num(n) => Some(n),
+            _ => None,
+        })
+        .map_with(|val, e| Spanned::new(val, e.span()));
+
+        let literal = |name: &'static str, val: fn(SimpleSpan) -> Json| {
+            token(Some(name), move |t| (t == Token::Ident(name)).then_some(()))
+                .map_with(move |_, e| Spanned::new(val(e.span()), e.span()))
+        };
 
         let array = value
             .clone()
-            .separated_by(just(',').padded())
-            .collect()
-            .map_with(|val, e| Spanned {
-                val,
-                span: e.span(),
+            .separated_by(just(Token::Ctrl(',')).recover_with(skip_then_retry_until(
+                any().ignored(),
+                one_of([Token::Ctrl(','), Token::Ctrl(']')]).ignored(),
+            )))
+            .collect::<Vec<Spanned<Json>>>()
+            .map_with(|val, e| {
+                let span = covering_span(ordered_span(e.span()), val.last().map(|elem| elem.span));
+                Spanned::new(val, span)
             })
-            .padded()
-            .delimited_by(just('['), just(']'))
+            .then_ignore(trailing_comma(']').or_not())
+            .delimited_by(just(Token::Ctrl('[')), closing_delimiter(']'))
             .boxed()
             .labelled("array");
 
+        let unquoted_key = token(Some("string"), |t| match t {
+            Token::Ident(key) => Some(key),
+            _ => None,
+        })
+        .validate(|key, e, _| {
+            let span: SimpleSpan = e.span();
+            record_issue(
+                e.state(),
+                SyntaxIssue::UnquotedKey {
+                    span: span.into_range(),
+                },
+            );
+            Spanned::new(key.to_string(), span)
+        });
         let member = string
             .clone()
-            .then_ignore(just(':').padded())
+            .or(unquoted_key)
+            .then_ignore(just(Token::Ctrl(':')))
             .then(value)
             .labelled("object member");
+        // A member directly following another one without a `,` is parsed as if the `,` was there.
+        let missing_comma = token(None, |t| matches!(t, Token::Str(_)).then_some(()))
+            .map_with(|_, e| -> SimpleSpan { e.span() })
+            .rewind()
+            .validate(|span, e, _| {
+                record_issue(e.state(), SyntaxIssue::MissingComma { next: span.start });
+            });
+        let member_separator = just(Token::Ctrl(',')).ignored().or(missing_comma);
         let object = member
             .clone()
-            .separated_by(just(',').padded())
-            .collect()
-            .map_with(|val, e| Spanned {
-                val,
-                span: e.span(),
+            .separated_by(member_separator.recover_with(skip_then_retry_until(
+                any().ignored(),
+                one_of([Token::Ctrl(','), Token::Ctrl('}')]).ignored(),
+            )))
+            .collect::<Vec<_>>()
+            .map_with(|val, e| {
+                let span =
+                    covering_span(ordered_span(e.span()), val.last().map(|(_, val)| val.span));
+                Spanned::new(val, span)
             })
-            .padded()
-            .delimited_by(just('{'), just('}'))
+            .then_ignore(trailing_comma('}').or_not())
+            .delimited_by(just(Token::Ctrl('{')), closing_delimiter('}'))
             .boxed()
             .labelled("object");
 
         choice((
-            just("null")
-                .map_with(|_, e| Spanned {
-                    span: e.span(),
-                    val: Json::Null(Spanned {
-                        span: e.span(),
-                        val: (),
-                    }),
-                })
-                .labelled("null"),
-            just("true")
-                .map_with(|_, e| Spanned {
-                    span: e.span(),
-                    val: Json::Bool(Spanned {
-                        val: true,
-                        span: e.span(),
-                    }),
-                })
-                .labelled("true"),
-            just("false")
-                .map_with(|_, e| Spanned {
-                    span: e.span(),
-                    val: Json::Bool(Spanned {
-                        val: false,
-                        span: e.span(),
-                    }),
-                })
-                .labelled("false"),
-            number
-                .map_with(|val, e| Spanned {
-                    span: e.span(),
-                    val: Json::Num(val),
-                })
-                .labelled("number"),
-            string
-                .map_with(|val, e| Spanned {
-                    span: e.span(),
-                    val: Json::Str(val),
-                })
-                .labelled("string"),
+            literal("null", |span| Json::Null(Spanned::new((), span))),
+            literal("true", |span| Json::Bool(Spanned::new(true, span))),
+            literal("false", |span| Json::Bool(Spanned::new(false, span))),
+            number.map_with(|val, e| Spanned::new(Json::Num(val), e.span())),
+            string.map_with(|val, e| Spanned::new(Json::Str(val), e.span())),
             array
-                .map_with(|val, e| Spanned {
-                    span: e.span(),
-                    val: Json::Array(val),
+                .map_with(|val, e| {
+                    let span = covering_span(e.span(), [val.span]);
+                    Spanned::new(Json::Array(val), span)
                 })
                 .labelled("array"),
             object
-                .map_with(|val, e| Spanned {
-                    span: e.span(),
-                    val: Json::Object(val),
+                .map_with(|val, e| {
+                    let span = covering_span(e.span(), [val.span]);
+                    Spanned::new(Json::Object(val), span)
                 })
                 .labelled("object"),
+            // Checked last, so that it only applies to literals which aren't valid JSON.
+            token(None, |t| match t {
+                Token::Ident(ident) if json_literal_for(ident).is_some() => Some(ident),
+                _ => None,
+            })
+            .validate(|ident, e, _| {
+                let span: SimpleSpan = e.span();
+                let replacement = json_literal_for(ident).unwrap();
+                record_issue(
+                    e.state(),
+                    SyntaxIssue::WrongCaseLiteral {
+                        span: span.into_range(),
+                        replacement,
+                    },
+                );
+                let val = match replacement {
+                    "true" => Json::Bool(Spanned::new(true, span)),
+                    "false" => Json::Bool(Spanned::new(false, span)),
+                    _ => Json::Null(Spanned::new((), span)),
+                };
+                Spanned::new(val, span)
+            }),
+        ))
+        .recover_with(via_parser(nested_delimiters(
+            Token::Ctrl('{'),
+            Token::Ctrl('}'),
+            [(Token::Ctrl('['), Token::Ctrl(']'))],
+            |span| Spanned::new(Json::Invalid, span),
+        )))
+        .recover_with(via_parser(nested_delimiters(
+            Token::Ctrl('['),
+            Token::Ctrl(']'),
+            [(Token::Ctrl('{'), Token::Ctrl('}'))],
+            |span| Spanned::new(Json::Invalid, span),
+        )))
+        .recover_with(skip_then_retry_until(
+            any().ignored(),
+            one_of([Token::Ctrl(','), Token::Ctrl(']'), Token::Ctrl('}')]).ignored(),
         ))
-        .padded()
     })
     .labelled("Custom Difficulty JSON")
 }
@@ -0,0 +1,219 @@
+//! Lints written by users, loaded from the [`PLUGIN_DIR`] next to the executable, so that house
+//! rules can be checked without forking CDLint.
+//!
+//! Plugins are either Rhai scripts (`.rhai`, see [`script`]) or WebAssembly modules (`.wasm`, see
+//! [`wasm`]). A plugin is given the resolved Custom Difficulty (see `cdlint resolve`) and a source map from
+//! member paths to spans, and returns diagnostics which are reported like those of any other lint,
+//! under the name `plugin::<file stem>`.
+
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::*;
+
+use crate::config::Config;
+use crate::crash_report;
+use crate::diagnostic::{Color, Label, Report, ReportKind};
+use crate::lint::{LintGroup, LintSelection};
+use crate::parser::Json;
+use crate::spanned::Spanned;
+use crate::time_passes::{time, Pass};
+use crate::Diagnostics;
+
+mod script;
+mod wasm;
+
+/// The directory next to the executable which plugins are loaded from.
+pub const PLUGIN_DIR: &str = "plugins";
+
+static PLUGINS: OnceLock<Vec<Box<dyn LintPlugin>>> = OnceLock::new();
+
+/// A lint provided by a plugin.
+pub trait LintPlugin: Send + Sync {
+    /// The lint name diagnostics are reported under, e.g. `plugin::house_rules`.
+    fn name(&self) -> &'static str;
+
+    fn check(&self, input: &PluginInput) -> anyhow::Result<Vec<PluginDiagnostic>>;
+}
+
+/// What a plugin is given to check.
+pub struct PluginInput {
+    /// The Custom Difficulty as the game loads it, as printed by `cdlint resolve`.
+    pub cd: Value,
+    /// The span of each member and array element as written, keyed by its JSON pointer, e.g.
+    /// `/EnemyDescriptors/ED_Spider_Grunt/Base` or `/CommonEnemies/add/0`. Members span from their
+    /// name to the end of their value.
+    pub source_map: IndexMap<String, Range<usize>>,
+}
+
+/// A diagnostic emitted by a plugin.
+#[derive(Debug)]
+pub struct PluginDiagnostic {
+    pub level: PluginLevel,
+    pub message: String,
+    /// Where in the source the diagnostic is about, if anywhere in particular.
+    pub span: Option<Range<usize>>,
+    pub help: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginLevel {
+    Error,
+    Warning,
+    Advice,
+}
+
+/// Loads the plugins in `dir`. Plugins which can't be loaded are skipped with a warning, so that a
+/// broken plugin doesn't stop linting.
+pub fn load(dir: &Path) {
+    let mut plugins = Vec::<Box<dyn LintPlugin>>::new();
+    let mut paths = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+    for path in paths {
+        let loaded: anyhow::Result<Box<dyn LintPlugin>> =
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some("rhai") => script::ScriptPlugin::load(&path).map(|p| Box::new(p) as _),
+                Some("wasm") => wasm::WasmPlugin::load(&path).map(|p| Box::new(p) as _),
+                _ => continue,
+            };
+        match loaded {
+            Ok(plugin) => {
+                debug!(?path, name = plugin.name(), "loaded plugin");
+                plugins.push(plugin);
+            }
+            Err(e) => warn!("skipping plugin `{}`: {:#}", path.display(), e),
+        }
+    }
+    let _ = PLUGINS.set(plugins);
+}
+
+/// The lint names of the loaded plugins.
+pub fn names() -> impl Iterator<Item = &'static str> {
+    loaded().iter().map(|plugin| plugin.name())
+}
+
+fn loaded() -> &'static [Box<dyn LintPlugin>] {
+    PLUGINS.get().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// The lint name of the plugin loaded from `path`.
+fn plugin_name(path: &Path) -> &'static str {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    format!("plugin::{stem}").leak()
+}
+
+/// Runs the loaded plugins which are selected like any other lint in the default group. Plugins
+/// which fail are skipped with a warning.
+pub fn run_plugins<'d>(
+    config: &Config,
+    selection: &LintSelection,
+    input: impl FnOnce() -> PluginInput,
+    path: &'d String,
+    src: &str,
+    diag: &mut Diagnostics<'d>,
+) {
+    let plugins = loaded()
+        .iter()
+        .filter(|plugin| selection.is_enabled(plugin.name(), LintGroup::Default, config))
+        .collect::<Vec<_>>();
+    if plugins.is_empty() {
+        return;
+    }
+
+    let input = input();
+    for plugin in plugins {
+        debug!(plugin = plugin.name(), "running plugin");
+        let _lint = crash_report::enter_lint(plugin.name());
+        let diagnostics = match time(Pass::Lint(plugin.name()), || plugin.check(&input)) {
+            Ok(diagnostics) => diagnostics,
+            Err(e) => {
+                warn!("plugin `{}` failed: {:#}", plugin.name(), e);
+                continue;
+            }
+        };
+        for mut diagnostic in diagnostics {
+            // Spans come from the plugin, so they may not be in the source at all.
+            if diagnostic.span.as_ref().is_some_and(|span| {
+                span.end > src.len()
+                    || !src.is_char_boundary(span.start)
+                    || !src.is_char_boundary(span.end)
+            }) {
+                diagnostic.span = None;
+            }
+            let mut report = diagnostic.report(plugin.name(), path);
+            report.lint = Some(plugin.name());
+            diag.push(report);
+        }
+    }
+}
+
+impl PluginDiagnostic {
+    fn report<'d>(&self, plugin: &str, path: &'d String) -> Report<'d> {
+        let (kind, color) = match self.level {
+            PluginLevel::Error => (ReportKind::Error, Color::Red),
+            PluginLevel::Warning => (ReportKind::Warning, Color::Yellow),
+            PluginLevel::Advice => (ReportKind::Advice, Color::Cyan),
+        };
+        let mut report = Report::build(kind, path, self.span.as_ref().map_or(0, |span| span.start))
+            .with_message(&self.message);
+        if let Some(span) = &self.span {
+            report.add_label(
+                Label::new((path, span.clone()))
+                    .with_color(color)
+                    .with_message(format!("reported by `{plugin}`")),
+            );
+        }
+        if let Some(help) = &self.help {
+            report.set_help(help);
+        }
+        report.finish()
+    }
+}
+
+/// The source map of the top-level members, see [`PluginInput::source_map`].
+pub fn source_map(
+    top_level_members: &[(Spanned<String>, Spanned<Json>)],
+) -> IndexMap<String, Range<usize>> {
+    fn walk(map: &mut IndexMap<String, Range<usize>>, pointer: &str, val: &Spanned<Json>) {
+        match &val.val {
+            Json::Object(members) => {
+                for (name, val) in &members.val {
+                    let pointer = format!("{pointer}/{}", escape_pointer(&name.val));
+                    map.insert(pointer.clone(), name.span.start..val.span.end);
+                    walk(map, &pointer, val);
+                }
+            }
+            Json::Array(vals) => {
+                for (i, val) in vals.val.iter().enumerate() {
+                    let pointer = format!("{pointer}/{i}");
+                    map.insert(pointer.clone(), val.span.into_range());
+                    walk(map, &pointer, val);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut map = IndexMap::new();
+    for (name, val) in top_level_members {
+        let pointer = format!("/{}", escape_pointer(&name.val));
+        map.insert(pointer.clone(), name.span.start..val.span.end);
+        walk(&mut map, &pointer, val);
+    }
+    map
+}
+
+/// Escapes a member name for use in a JSON pointer (RFC 6901).
+fn escape_pointer(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
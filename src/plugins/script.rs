@@ -0,0 +1,162 @@
+//! Plugins written as [Rhai](https://rhai.rs) scripts.
+//!
+//! A script defines `fn lint(cd)`, where `cd` is the resolved Custom Difficulty as a map, and
+//! reports diagnostics by calling these functions:
+//!
+//! - `error(message)`, `warning(message)` and `advice(message)`, which report a diagnostic about
+//!   the Custom Difficulty as a whole;
+//! - `error(message, span)`, `warning(message, span)` and `advice(message, span)`, which report a
+//!   diagnostic about `span`, and also take a help message as a third argument;
+//! - `span(pointer)`, which returns the span of a member or array element as written, given its
+//!   JSON pointer, e.g. `span("/EnemyDescriptors/ED_Spider_Grunt/Base")`, or `()` if it isn't
+//!   written.
+//!
+//! ```rhai
+//! fn lint(cd) {
+//!     if cd.ResupplyCost[0] < 40.0 {
+//!         warning("resupplies are too cheap for our group", span("/ResupplyCost"));
+//!     }
+//! }
+//! ```
+//!
+//! Scripts can't access the file system, and are stopped if they run for too long.
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::path::Path;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Context};
+use indexmap::IndexMap;
+use rhai::{Dynamic, Engine, ImmutableString, Scope, AST};
+
+use super::{LintPlugin, PluginDiagnostic, PluginInput, PluginLevel};
+
+/// How many operations a script may run for each Custom Difficulty, so that an accidental
+/// infinite loop doesn't hang CDLint.
+const MAX_OPERATIONS: u64 = 10_000_000;
+/// How deeply expressions may be nested, in and outside of functions. Rhai's defaults are lower
+/// in debug builds, which rejects ordinary scripts.
+const MAX_EXPR_DEPTH: usize = 64;
+
+pub struct ScriptPlugin {
+    name: &'static str,
+    /// The script is compiled for each Custom Difficulty, since compiled scripts can't be shared
+    /// between threads.
+    script: String,
+}
+
+impl ScriptPlugin {
+    /// Reads and compiles the script, so that syntax errors are reported up front.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let script = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+        let plugin = Self {
+            name: super::plugin_name(path),
+            script,
+        };
+        let ast = plugin.compile(&engine())?;
+        if !ast
+            .iter_functions()
+            .any(|f| f.name == "lint" && f.params.len() == 1)
+        {
+            anyhow::bail!("the script does not define `fn lint(cd)`");
+        }
+        Ok(plugin)
+    }
+
+    fn compile(&self, engine: &Engine) -> anyhow::Result<AST> {
+        engine
+            .compile(&self.script)
+            .map_err(|e| anyhow!("{e}"))
+            .context("failed to compile script")
+    }
+}
+
+impl LintPlugin for ScriptPlugin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn check(&self, input: &PluginInput) -> anyhow::Result<Vec<PluginDiagnostic>> {
+        let diagnostics = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = engine();
+        register_api(&mut engine, &diagnostics, &input.source_map);
+
+        let ast = self.compile(&engine)?;
+        let cd = rhai::serde::to_dynamic(&input.cd).map_err(|e| anyhow!("{e}"))?;
+        // Whatever `lint` returns is ignored, only the diagnostics it reports matter.
+        let _: Dynamic = engine
+            .call_fn(&mut Scope::new(), &ast, "lint", (cd,))
+            .map_err(|e| anyhow!("{e}"))?;
+
+        Ok(diagnostics.take())
+    }
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH);
+    engine
+}
+
+fn register_api(
+    engine: &mut Engine,
+    diagnostics: &Rc<RefCell<Vec<PluginDiagnostic>>>,
+    source_map: &IndexMap<String, Range<usize>>,
+) {
+    for (name, level) in [
+        ("error", PluginLevel::Error),
+        ("warning", PluginLevel::Warning),
+        ("advice", PluginLevel::Advice),
+    ] {
+        let push = {
+            let diagnostics = Rc::clone(diagnostics);
+            move |message: ImmutableString, span: Dynamic, help: Option<ImmutableString>| {
+                diagnostics.borrow_mut().push(PluginDiagnostic {
+                    level,
+                    message: message.to_string(),
+                    span: to_span(span),
+                    help: help.map(|help| help.to_string()),
+                });
+            }
+        };
+        let push_ = push.clone();
+        engine.register_fn(name, move |message: ImmutableString| {
+            push_(message, Dynamic::UNIT, None)
+        });
+        let push_ = push.clone();
+        engine.register_fn(name, move |message: ImmutableString, span: Dynamic| {
+            push_(message, span, None)
+        });
+        engine.register_fn(
+            name,
+            move |message: ImmutableString, span: Dynamic, help: ImmutableString| {
+                push(message, span, Some(help))
+            },
+        );
+    }
+
+    let source_map = source_map.clone();
+    engine.register_fn("span", move |pointer: ImmutableString| -> Dynamic {
+        match source_map.get(pointer.as_str()) {
+            Some(span) => Dynamic::from_array(vec![
+                Dynamic::from_int(span.start as rhai::INT),
+                Dynamic::from_int(span.end as rhai::INT),
+            ]),
+            None => Dynamic::UNIT,
+        }
+    });
+}
+
+/// A span as returned by `span`, i.e. `[start, end]`, or `None` for anything else such as `()`.
+fn to_span(span: Dynamic) -> Option<Range<usize>> {
+    let span = span.try_cast::<rhai::Array>()?;
+    let [start, end] = span.as_slice() else {
+        return None;
+    };
+    let start = usize::try_from(start.as_int().ok()?).ok()?;
+    let end = usize::try_from(end.as_int().ok()?).ok()?;
+    (start <= end).then_some(start..end)
+}
@@ -0,0 +1,142 @@
+//! Plugins compiled to WebAssembly, e.g. from Rust with `--target wasm32-unknown-unknown`.
+//!
+//! A module exports its `memory` and two functions:
+//!
+//! - `alloc(len: i32) -> i32`, which returns a pointer to `len` bytes the input can be written to;
+//! - `lint(ptr: i32, len: i32) -> i64`, which is given the input written by CDLint and returns the
+//!   output as `(ptr << 32) | len`.
+//!
+//! The input is a JSON object with the resolved Custom Difficulty as `cd` and the source map as
+//! `source_map`, which maps JSON pointers to `[start, end]` spans. The output is a JSON array of
+//! diagnostics:
+//!
+//! ```json
+//! [{ "level": "warning", "message": "...", "span": [12, 30], "help": "..." }]
+//! ```
+//!
+//! where `level` is `error`, `warning` or `advice`, and `span` and `help` may be left out.
+//!
+//! Modules can't import anything, so they have no access to the file system or anything else
+//! outside of their own memory. They are stopped if they run for too long or use too much memory.
+
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context};
+use serde::Deserialize;
+use serde_json::json;
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::{LintPlugin, PluginDiagnostic, PluginInput, PluginLevel};
+
+/// How much fuel a module may use for each Custom Difficulty, roughly one unit per instruction, so
+/// that an accidental infinite loop doesn't hang CDLint.
+const MAX_FUEL: u64 = 100_000_000;
+/// How many bytes of memory a module may use.
+const MAX_MEMORY: usize = 256 * 1024 * 1024;
+
+pub struct WasmPlugin {
+    name: &'static str,
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmPlugin {
+    /// Reads and compiles the module, checking that it has the exports plugins need.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let wasm =
+            std::fs::read(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm).context("failed to compile module")?;
+
+        if let Some(import) = module.imports().next() {
+            bail!(
+                "the module imports `{}::{}`, but plugins can't import anything",
+                import.module(),
+                import.name()
+            );
+        }
+        for export in ["memory", "alloc", "lint"] {
+            if module.get_export(export).is_none() {
+                bail!("the module does not export `{export}`");
+            }
+        }
+
+        Ok(Self {
+            name: super::plugin_name(path),
+            engine,
+            module,
+        })
+    }
+}
+
+impl LintPlugin for WasmPlugin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn check(&self, input: &PluginInput) -> anyhow::Result<Vec<PluginDiagnostic>> {
+        let source_map = input
+            .source_map
+            .iter()
+            .map(|(pointer, span)| (pointer.clone(), json!([span.start, span.end])))
+            .collect::<serde_json::Map<_, _>>();
+        let input = serde_json::to_vec(&json!({
+            "cd": input.cd,
+            "source_map": source_map,
+        }))?;
+
+        let limits = StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(MAX_FUEL)?;
+        let instance = Linker::new(&self.engine).instantiate_and_start(&mut store, &self.module)?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .context("the module does not export `memory`")?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc")?;
+        let lint = instance.get_typed_func::<(i32, i32), i64>(&store, "lint")?;
+
+        let len = i32::try_from(input.len()).context("the input is too large")?;
+        let ptr = alloc.call(&mut store, len).context("`alloc` failed")?;
+        memory
+            .write(&mut store, ptr as u32 as usize, &input)
+            .map_err(|e| anyhow!("failed to write the input: {e}"))?;
+        let output = lint.call(&mut store, (ptr, len)).context("`lint` failed")? as u64;
+
+        let (ptr, len) = ((output >> 32) as usize, output as u32 as usize);
+        if len > MAX_MEMORY {
+            bail!("the output is larger than the module's memory can be");
+        }
+        let mut output = vec![0; len];
+        memory
+            .read(&store, ptr, &mut output)
+            .map_err(|e| anyhow!("failed to read the output: {e}"))?;
+        let diagnostics = serde_json::from_slice::<Vec<WasmDiagnostic>>(&output)
+            .context("invalid output")?
+            .into_iter()
+            .map(|diagnostic| PluginDiagnostic {
+                level: diagnostic.level,
+                message: diagnostic.message,
+                span: diagnostic
+                    .span
+                    .filter(|[start, end]| start <= end)
+                    .map(|[start, end]| start..end),
+                help: diagnostic.help,
+            })
+            .collect();
+        Ok(diagnostics)
+    }
+}
+
+/// A diagnostic as returned by a module.
+#[derive(Debug, Deserialize)]
+struct WasmDiagnostic {
+    level: PluginLevel,
+    message: String,
+    #[serde(default)]
+    span: Option<[usize; 2]>,
+    #[serde(default)]
+    help: Option<String>,
+}
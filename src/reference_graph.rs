@@ -0,0 +1,348 @@
+//! The graph of "based-on" relationships between Enemy Descriptors, written by the
+//! `cyclic_enemy_descriptor_references` lint if `generate_cyclic_reference_graph` is enabled.
+//!
+//! The graph is written both in graphviz's dot format and as an SVG, which is laid out here so that
+//! graphviz does not need to be installed to look at it.
+
+use std::fmt::Write as _;
+
+use indexmap::IndexSet;
+
+use crate::custom_difficulty::CustomDifficulty;
+
+/// The Enemy Descriptors defined in the Custom Difficulty, and the Enemy Descriptors they are based
+/// on, directly or indirectly.
+#[derive(Debug)]
+pub struct ReferenceGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug)]
+pub struct Node {
+    pub name: String,
+    /// Whether the Enemy Descriptor is defined in the Custom Difficulty, as opposed to a vanilla
+    /// (or modded) Enemy Descriptor which is only used as a `Base`.
+    pub defined: bool,
+}
+
+/// An Enemy Descriptor `from` with `Base` set to `to`, both indices into the nodes.
+#[derive(Debug)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    /// Whether the edge is part of a cycle (of more than one Enemy Descriptor).
+    pub in_cycle: bool,
+}
+
+const NODE_HEIGHT: f64 = 28.0;
+const CHAR_WIDTH: f64 = 7.2;
+const NODE_PADDING: f64 = 16.0;
+const NODE_GAP: f64 = 24.0;
+const LAYER_GAP: f64 = 72.0;
+/// Leaves room for loops and edges arcing above or below a layer.
+const MARGIN: f64 = 40.0;
+
+impl ReferenceGraph {
+    /// The graph of the Enemy Descriptors defined in the Custom Difficulty and the Enemy
+    /// Descriptors they are based on (directly or indirectly). Every other vanilla Enemy Descriptor
+    /// would be an isolated node, so they are left out.
+    pub fn from_custom_difficulty(cd: &CustomDifficulty) -> Self {
+        let mut nodes = IndexSet::new();
+        for name in cd.enemy_descriptors.keys() {
+            let mut current = name.as_str();
+            while nodes.insert(current) {
+                match cd.enemy_descriptor(current) {
+                    Some(ed) if ed.base.is_specified() => current = &ed.base.val,
+                    _ => break,
+                }
+            }
+        }
+
+        // An edge is part of a cycle if its Enemy Descriptor can be reached from its base.
+        let reaches = |from: &str, to: &str| {
+            let mut current = from;
+            for _ in 0..nodes.len() {
+                if current == to {
+                    return true;
+                }
+                match cd.enemy_descriptor(current) {
+                    Some(ed) if ed.base.is_specified() => current = &ed.base.val,
+                    _ => return false,
+                }
+            }
+            false
+        };
+        let edges = cd
+            .enemy_descriptors
+            .iter()
+            .filter(|(_, ed)| ed.base.is_specified())
+            .map(|(name, ed)| {
+                let (name, base) = (name.as_str(), ed.base.as_str());
+                (name, base, name != base && reaches(base, name))
+            })
+            .collect::<Vec<_>>();
+
+        Self::new(
+            nodes
+                .iter()
+                .map(|&name| (name, cd.enemy_descriptor(name).is_some())),
+            edges,
+        )
+    }
+
+    /// `nodes` are the names of the Enemy Descriptors and whether they are defined, `edges` the
+    /// names of Enemy Descriptors and their bases and whether the edge is part of a cycle. Edges
+    /// between Enemy Descriptors which are not nodes are left out.
+    pub fn new<'a>(
+        nodes: impl IntoIterator<Item = (&'a str, bool)>,
+        edges: impl IntoIterator<Item = (&'a str, &'a str, bool)>,
+    ) -> Self {
+        let mut names = IndexSet::new();
+        let nodes = nodes
+            .into_iter()
+            .map(|(name, defined)| {
+                names.insert(name);
+                Node {
+                    name: name.to_string(),
+                    defined,
+                }
+            })
+            .collect();
+        let edges = edges
+            .into_iter()
+            .filter_map(|(from, to, in_cycle)| {
+                Some(Edge {
+                    from: names.get_index_of(from)?,
+                    to: names.get_index_of(to)?,
+                    in_cycle,
+                })
+            })
+            .collect();
+        ReferenceGraph { nodes, edges }
+    }
+
+    /// Enemy Descriptors which are not defined in the Custom Difficulty are grayed out, and edges
+    /// which are part of a cycle are red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n    node [shape = box];\n");
+        for node in &self.nodes {
+            let style = if node.defined {
+                ""
+            } else {
+                ", color = gray, fontcolor = gray"
+            };
+            let _ = writeln!(dot, "    {:?} [label = {:?}{style}];", node.name, node.name);
+        }
+        for edge in &self.edges {
+            let style = if edge.in_cycle {
+                ", color = red, fontcolor = red"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "    {:?} -> {:?} [label = \"Base\"{style}];",
+                self.nodes[edge.from].name, self.nodes[edge.to].name
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The graph laid out in layers, with Enemy Descriptors above the Enemy Descriptors based on
+    /// them. Styled like [`ReferenceGraph::to_dot`].
+    pub fn to_svg(&self) -> String {
+        let layers = self.layers();
+
+        // Lay out each layer left to right, centered on the widest layer.
+        let width_of =
+            |node: usize| self.nodes[node].name.chars().count() as f64 * CHAR_WIDTH + NODE_PADDING;
+        let layer_widths = layers
+            .iter()
+            .map(|layer| {
+                layer.iter().map(|&node| width_of(node)).sum::<f64>()
+                    + NODE_GAP * layer.len().saturating_sub(1) as f64
+            })
+            .collect::<Vec<_>>();
+        let total_width = layer_widths.iter().copied().fold(0.0, f64::max) + 2.0 * MARGIN;
+        let total_height =
+            layers.len() as f64 * (NODE_HEIGHT + LAYER_GAP) - LAYER_GAP + 2.0 * MARGIN;
+
+        // The top left corner and width of each node.
+        let mut positions = vec![(0.0, 0.0, 0.0); self.nodes.len()];
+        for (i, layer) in layers.iter().enumerate() {
+            let mut x = (total_width - layer_widths[i]) / 2.0;
+            let y = MARGIN + i as f64 * (NODE_HEIGHT + LAYER_GAP);
+            for &node in layer {
+                positions[node] = (x, y, width_of(node));
+                x += width_of(node) + NODE_GAP;
+            }
+        }
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{total_width:.0}" height="{total_height:.0}" font-family="monospace" font-size="12">"#
+        );
+        for (id, color) in [("arrow", "black"), ("arrow-cycle", "red")] {
+            let _ = writeln!(
+                svg,
+                r#"  <defs><marker id="{id}" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="{color}"/></marker></defs>"#
+            );
+        }
+
+        for edge in &self.edges {
+            let (color, marker) = if edge.in_cycle {
+                ("red", "arrow-cycle")
+            } else {
+                ("black", "arrow")
+            };
+            let (fx, fy, fw) = positions[edge.from];
+            let (tx, ty, tw) = positions[edge.to];
+            let (fcx, tcx) = (fx + fw / 2.0, tx + tw / 2.0);
+            let (path, label_x, label_y) = if edge.from == edge.to {
+                // A loop above the node.
+                (
+                    format!(
+                        "M {:.1} {fy:.1} C {:.1} {:.1}, {:.1} {:.1}, {:.1} {fy:.1}",
+                        fcx - 10.0,
+                        fcx - 30.0,
+                        fy - 30.0,
+                        fcx + 30.0,
+                        fy - 30.0,
+                        fcx + 10.0
+                    ),
+                    fcx + 16.0,
+                    fy - 20.0,
+                )
+            } else if fy > ty {
+                // The usual case: from the top of the node up to the bottom of its base.
+                let y2 = ty + NODE_HEIGHT;
+                (
+                    format!("M {fcx:.1} {fy:.1} L {tcx:.1} {y2:.1}"),
+                    (fcx + tcx) / 2.0,
+                    (fy + y2) / 2.0,
+                )
+            } else if fy < ty {
+                // Only edges of cycles go down. Curve them, so they don't overlap an edge going up
+                // between the same nodes.
+                let y1 = fy + NODE_HEIGHT;
+                let (cx, cy) = ((fcx + tcx) / 2.0 + LAYER_GAP / 2.0, (y1 + ty) / 2.0);
+                (
+                    format!("M {fcx:.1} {y1:.1} Q {cx:.1} {cy:.1}, {tcx:.1} {ty:.1}"),
+                    cx - 8.0,
+                    cy,
+                )
+            } else {
+                // Edges within a layer arc above it going right, and below it going left.
+                let (y, bulge) = if fcx < tcx {
+                    (fy, fy - LAYER_GAP / 2.0)
+                } else {
+                    (fy + NODE_HEIGHT, fy + NODE_HEIGHT + LAYER_GAP / 2.0)
+                };
+                (
+                    format!(
+                        "M {fcx:.1} {y:.1} Q {:.1} {bulge:.1}, {tcx:.1} {y:.1}",
+                        (fcx + tcx) / 2.0
+                    ),
+                    (fcx + tcx) / 2.0,
+                    (y + bulge) / 2.0,
+                )
+            };
+            let _ = writeln!(
+                svg,
+                r#"  <path d="{path}" fill="none" stroke="{color}" marker-end="url(#{marker})"/>"#
+            );
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{label_x:.1}" y="{label_y:.1}" fill="{color}" font-size="10">Base</text>"#
+            );
+        }
+
+        for (node, &(x, y, w)) in self.nodes.iter().zip(&positions) {
+            let color = if node.defined { "black" } else { "gray" };
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{x:.1}" y="{y:.1}" width="{w:.1}" height="{NODE_HEIGHT}" fill="white" stroke="{color}"/>"#
+            );
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{:.1}" y="{:.1}" fill="{color}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                x + w / 2.0,
+                y + NODE_HEIGHT / 2.0,
+                escape_xml(&node.name)
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Assigns each node to a layer, where layer 0 is at the top. Each Enemy Descriptor is placed
+    /// in the layer below its base, ignoring edges of cycles (without which the graph is acyclic).
+    /// Within a layer, nodes are ordered by the average position of their bases.
+    fn layers(&self) -> Vec<Vec<usize>> {
+        let mut layer = vec![0; self.nodes.len()];
+        // The longest path is at most as long as there are nodes, so this converges.
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+            for edge in self
+                .edges
+                .iter()
+                .filter(|edge| !edge.in_cycle && edge.from != edge.to)
+            {
+                if layer[edge.from] < layer[edge.to] + 1 {
+                    layer[edge.from] = layer[edge.to] + 1;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let layer_count = layer.iter().max().map_or(0, |max| max + 1);
+        let mut layers = vec![Vec::new(); layer_count];
+        for (node, &i) in layer.iter().enumerate() {
+            layers[i].push(node);
+        }
+
+        let mut order = vec![0.0; self.nodes.len()];
+        for (i, layer) in layers.iter_mut().enumerate() {
+            if i > 0 {
+                let bases = |node: usize| {
+                    self.edges
+                        .iter()
+                        .filter(|edge| edge.from == node && !edge.in_cycle && edge.from != edge.to)
+                        .map(|edge| order[edge.to])
+                        .collect::<Vec<f64>>()
+                };
+                let mut keyed = layer
+                    .iter()
+                    .map(|&node| {
+                        let bases = bases(node);
+                        let key = if bases.is_empty() {
+                            f64::MAX
+                        } else {
+                            bases.iter().sum::<f64>() / bases.len() as f64
+                        };
+                        (key, node)
+                    })
+                    .collect::<Vec<_>>();
+                keyed.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+                *layer = keyed.into_iter().map(|(_, node)| node).collect();
+            }
+            for (position, &node) in layer.iter().enumerate() {
+                order[node] = position as f64;
+            }
+        }
+        layers
+    }
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,126 @@
+//! Resolution of Enemy Descriptors through their `Base` chains, i.e. the values the game ends up
+//! with after copying the values of each `Base`.
+
+use indexmap::IndexMap;
+
+use crate::custom_difficulty::{CustomDifficulty, EnemyDescriptor};
+use crate::spanned::Spanned;
+
+/// A field value of a resolved Enemy Descriptor. The span of `val` is where it was specified.
+#[derive(Debug, PartialEq)]
+pub struct Resolved<'a, T> {
+    pub val: &'a Spanned<T>,
+    /// The Enemy Descriptor in the `Base` chain which specified the value.
+    pub from: &'a str,
+}
+
+impl<T> Clone for Resolved<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Resolved<'_, T> {}
+
+/// An Enemy Descriptor with every field resolved through its `Base` chain.
+///
+/// A field is `None` if no Enemy Descriptor of the chain which is defined in this Custom Difficulty
+/// specifies it. Its value then comes from [`inherits_from`][Self::inherits_from], or is the
+/// game's default.
+#[derive(Debug)]
+pub struct ResolvedEnemyDescriptor<'a> {
+    pub name: &'a str,
+    /// The Enemy Descriptors visited when following `Base`, starting with this one.
+    pub chain: Vec<&'a str>,
+    /// The Enemy Descriptor ending the chain if it is not defined in this Custom Difficulty, e.g. a
+    /// vanilla Enemy Descriptor.
+    pub inherits_from: Option<&'a str>,
+    pub spawn_spread: Option<Resolved<'a, f64>>,
+    pub ideal_spawn_size: Option<Resolved<'a, usize>>,
+    pub can_be_used_for_constant_pressure: Option<Resolved<'a, bool>>,
+    pub can_be_used_in_encounters: Option<Resolved<'a, bool>>,
+    pub difficulty_rating: Option<Resolved<'a, f64>>,
+    pub min_spawn_count: Option<Resolved<'a, usize>>,
+    pub max_spawn_count: Option<Resolved<'a, usize>>,
+    pub rarity: Option<Resolved<'a, f64>>,
+    pub spawn_amount_modifier: Option<Resolved<'a, f64>>,
+    pub elite: Option<Resolved<'a, bool>>,
+    pub scale: Option<Resolved<'a, f64>>,
+    pub time_dilation: Option<Resolved<'a, f64>>,
+    /// Pawn stats are inherited one by one, so an Enemy Descriptor can override some pawn stats of
+    /// its `Base` and keep the others.
+    pub pawn_stats: IndexMap<&'a str, Resolved<'a, f64>>,
+}
+
+impl CustomDifficulty {
+    /// Resolves the given Enemy Descriptor through its `Base` chain. Returns `None` if the chain
+    /// is cyclic.
+    pub fn resolve_enemy_descriptor<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> Option<ResolvedEnemyDescriptor<'a>> {
+        let chain = self.base_chain(name)?;
+        let defined = chain
+            .iter()
+            .filter_map(|name| Some((*name, &self.enemy_descriptor(name)?.val)))
+            .collect::<Vec<_>>();
+        let inherits_from = chain
+            .last()
+            .copied()
+            .filter(|root| self.enemy_descriptor(root).is_none());
+
+        let mut pawn_stats = IndexMap::new();
+        for &(from, ed) in &defined {
+            for (stat, val) in &ed.pawn_stats.val.0 {
+                pawn_stats
+                    .entry(stat.as_str())
+                    .or_insert(Resolved { val, from });
+            }
+        }
+
+        Some(ResolvedEnemyDescriptor {
+            name,
+            inherits_from,
+            spawn_spread: resolve_field(&defined, |ed| &ed.spawn_spread),
+            ideal_spawn_size: resolve_field(&defined, |ed| &ed.ideal_spawn_size),
+            can_be_used_for_constant_pressure: resolve_field(&defined, |ed| {
+                &ed.can_be_used_for_constant_pressure
+            }),
+            can_be_used_in_encounters: resolve_field(&defined, |ed| &ed.can_be_used_in_encounters),
+            difficulty_rating: resolve_field(&defined, |ed| &ed.difficulty_rating),
+            min_spawn_count: resolve_field(&defined, |ed| &ed.min_spawn_count),
+            max_spawn_count: resolve_field(&defined, |ed| &ed.max_spawn_count),
+            rarity: resolve_field(&defined, |ed| &ed.rarity),
+            spawn_amount_modifier: resolve_field(&defined, |ed| &ed.spawn_amount_modifier),
+            elite: resolve_field(&defined, |ed| &ed.elite),
+            scale: resolve_field(&defined, |ed| &ed.scale),
+            time_dilation: resolve_field(&defined, |ed| &ed.time_dilation),
+            pawn_stats,
+            chain,
+        })
+    }
+
+    /// Resolves every Enemy Descriptor defined in this Custom Difficulty, in the order they are
+    /// defined. Enemy Descriptors with a cyclic `Base` chain are skipped.
+    pub fn resolve_enemy_descriptors(&self) -> IndexMap<&str, ResolvedEnemyDescriptor<'_>> {
+        self.enemy_descriptors
+            .val
+            .keys()
+            .filter_map(|name| Some((name.as_str(), self.resolve_enemy_descriptor(name)?)))
+            .collect()
+    }
+}
+
+/// The value of the nearest Enemy Descriptor in the chain which specifies the field.
+fn resolve_field<'a, T>(
+    defined: &[(&'a str, &'a EnemyDescriptor)],
+    field: impl Fn(&'a EnemyDescriptor) -> &'a Spanned<T>,
+) -> Option<Resolved<'a, T>> {
+    defined
+        .iter()
+        .map(|&(from, ed)| Resolved {
+            val: field(ed),
+            from,
+        })
+        .find(|resolved| resolved.val.is_specified())
+}
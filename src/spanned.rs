@@ -1,3 +1,5 @@
+use std::ops::{Deref, Range};
+
 use chumsky::span::SimpleSpan;
 use serde::Deserialize;
 
@@ -19,3 +21,57 @@ impl<T: Ord> Ord for Spanned<T> {
         self.val.cmp(&other.val)
     }
 }
+
+/// The span of a value which does not appear in the Custom Difficulty JSON, e.g. a member which is
+/// absent and was defaulted. Diagnostics never point at it, since there is nothing to point at.
+pub fn synthetic_sp() -> SimpleSpan {
+    SimpleSpan::new(usize::MAX, usize::MAX)
+}
+
+/// Whether a diagnostic span is [`synthetic_sp`].
+pub fn is_synthetic(span: &Range<usize>) -> bool {
+    *span == synthetic_sp().into_range()
+}
+
+impl<T> Spanned<T> {
+    pub fn new(val: T, span: SimpleSpan) -> Self {
+        Self { span, val }
+    }
+
+    /// A value which does not appear in the Custom Difficulty JSON.
+    pub fn synthetic(val: T) -> Self {
+        Self {
+            span: synthetic_sp(),
+            val,
+        }
+    }
+
+    /// Whether the value was specified in the Custom Difficulty JSON, as opposed to being
+    /// defaulted because the member was absent.
+    pub fn is_specified(&self) -> bool {
+        self.span != synthetic_sp()
+    }
+
+    /// Transforms the value, keeping its span.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned::new(f(self.val), self.span)
+    }
+
+    pub fn as_ref(&self) -> Spanned<&T> {
+        Spanned::new(&self.val, self.span)
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.val
+    }
+}
+
+impl<T: Default> Default for Spanned<T> {
+    fn default() -> Self {
+        Self::synthetic(Default::default())
+    }
+}
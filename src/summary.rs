@@ -0,0 +1,95 @@
+//! The summary printed after all diagnostics have been emitted.
+
+use indexmap::IndexMap;
+
+use crate::diagnostic::{Applicability, ReportKind};
+use crate::DiagnosticReport;
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub advice: usize,
+    /// The number of diagnostics with a fix which can be applied without review.
+    pub fixable: usize,
+    /// The number of diagnostics emitted by each lint, in the order the lints first emitted one.
+    pub per_lint: IndexMap<&'static str, usize>,
+}
+
+impl Summary {
+    pub fn new<'a, 'd: 'a>(
+        diagnostics: impl IntoIterator<Item = &'a DiagnosticReport<'d>>,
+    ) -> Self {
+        let mut summary = Summary::default();
+        for diagnostic in diagnostics {
+            match diagnostic.kind {
+                ReportKind::Error => summary.errors += 1,
+                ReportKind::Warning => summary.warnings += 1,
+                _ => summary.advice += 1,
+            }
+            if diagnostic
+                .suggestions
+                .iter()
+                .any(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+            {
+                summary.fixable += 1;
+            }
+            if let Some(lint) = diagnostic.lint {
+                *summary.per_lint.entry(lint).or_default() += 1;
+            }
+        }
+        summary
+    }
+
+    /// Adds the counts of another summary, e.g. of another file.
+    pub fn add(&mut self, other: Summary) {
+        self.errors += other.errors;
+        self.warnings += other.warnings;
+        self.advice += other.advice;
+        self.fixable += other.fixable;
+        for (lint, count) in other.per_lint {
+            *self.per_lint.entry(lint).or_default() += count;
+        }
+    }
+
+    /// E.g. `1 error, 2 warnings emitted (1 fixable)`. Advice is only counted if there is any, and
+    /// fixable diagnostics if any can be fixed.
+    pub fn counts(&self) -> String {
+        let mut counts = vec![
+            plural(self.errors, "error", "errors"),
+            plural(self.warnings, "warning", "warnings"),
+        ];
+        if self.advice > 0 {
+            counts.push(plural(self.advice, "advice", "advice"));
+        }
+        let mut counts = format!("{} emitted", counts.join(", "));
+        if self.fixable > 0 {
+            counts.push_str(&format!(" ({} fixable)", self.fixable));
+        }
+        counts
+    }
+
+    /// Prints the [counts][Self::counts], followed by the number of diagnostics per lint if
+    /// `show_stats` is set.
+    pub fn print(&self, show_stats: bool) {
+        println!("{}", self.counts());
+
+        if show_stats && !self.per_lint.is_empty() {
+            let mut per_lint = self.per_lint.iter().collect::<Vec<_>>();
+            per_lint.sort_by(|(_, a), (_, b)| b.cmp(a));
+            let width = per_lint.iter().map(|(lint, _)| lint.len()).max().unwrap();
+            println!();
+            for (lint, count) in per_lint {
+                println!("{lint:<width$}  {count}");
+            }
+        }
+    }
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("{count} {singular}")
+    } else {
+        format!("{count} {plural}")
+    }
+}
@@ -0,0 +1,175 @@
+//! Inline lint suppression via the reserved `__cdlint` member, e.g.
+//! `"__cdlint": { "allow": ["unused-custom-enemy-descriptors"] }`. At the top level it allows the
+//! lints for the whole Custom Difficulty; within an Enemy Descriptor definition it only allows
+//! diagnostics about that Enemy Descriptor definition.
+
+use std::ops::Range;
+
+use crate::diagnostic::{
+    string_literal, Applicability, Color, Fmt, Label, Report, ReportKind, Suggestion,
+};
+use crate::lint::lint_names;
+use crate::parser::Json;
+use crate::spanned::Spanned;
+use crate::{edit_distance, DiagnosticReport, Diagnostics};
+
+/// The reserved member name. It is not a Custom Difficulty attribute, so it is removed before the
+/// Custom Difficulty is built.
+pub const SUPPRESSION_MEMBER: &str = "__cdlint";
+
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    /// Lints allowed for the whole Custom Difficulty.
+    global: Vec<Spanned<String>>,
+    /// Lints allowed within the span of an Enemy Descriptor definition.
+    scoped: Vec<(Range<usize>, Vec<Spanned<String>>)>,
+}
+
+impl Suppressions {
+    /// Whether the diagnostic was produced by a lint which is allowed where the diagnostic points
+    /// to.
+    pub fn is_suppressed(&self, report: &DiagnosticReport<'_>) -> bool {
+        let Some(lint) = report.lint else {
+            return false;
+        };
+
+        if self.global.iter().any(|allowed| allowed.val == lint) {
+            return true;
+        }
+
+        let span = report.primary_span();
+        self.scoped.iter().any(|(scope, allowed)| {
+            scope.start <= span.start
+                && span.end <= scope.end
+                && allowed.iter().any(|allowed| allowed.val == lint)
+        })
+    }
+
+    /// Warns about allowed lints which don't exist, since those don't suppress anything.
+    pub fn check_lint_names<'d>(&self, path: &'d String, diag: &mut Diagnostics<'d>) {
+        let known = lint_names();
+        let allowed = self
+            .global
+            .iter()
+            .chain(self.scoped.iter().flat_map(|(_, allowed)| allowed));
+
+        for name in allowed {
+            if known.contains(&name.as_str()) {
+                continue;
+            }
+
+            let mut report = Report::build(ReportKind::Warning, path, name.span.start)
+                .with_message(format!(
+                    "unknown lint \"{}\"",
+                    name.as_str().fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, name.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message("allowing this lint has no effect"),
+                );
+            if let Some(suggestion) = edit_distance::suggest_similar(&known, &name.val) {
+                report.add_suggestion(Suggestion::new(
+                    name.span.into_range(),
+                    string_literal(suggestion),
+                    Applicability::MaybeIncorrect,
+                ));
+            }
+            diag.push(report.finish());
+        }
+    }
+}
+
+/// Removes `__cdlint` members from the top level and from Enemy Descriptor definitions, and
+/// collects the lints they allow.
+pub fn strip_suppressions<'d>(
+    top_level_members: &mut Vec<(Spanned<String>, Spanned<Json>)>,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) -> Suppressions {
+    let mut suppressions = Suppressions::default();
+
+    top_level_members.retain(|(name, val)| {
+        if name.val != SUPPRESSION_MEMBER {
+            return true;
+        }
+        suppressions.global.extend(allowed_lints(val, path, diag));
+        false
+    });
+
+    for (name, val) in top_level_members.iter_mut() {
+        if name.val != "EnemyDescriptors" {
+            continue;
+        }
+        let Json::Object(eds) = &mut val.val else {
+            continue;
+        };
+        for (ed_name, ed) in &mut eds.val {
+            // Include the name, since diagnostics about an Enemy Descriptor often point at it.
+            let scope = ed_name.span.start..ed.span.end;
+            let Json::Object(ed_members) = &mut ed.val else {
+                continue;
+            };
+            ed_members.val.retain(|(name, val)| {
+                if name.val != SUPPRESSION_MEMBER {
+                    return true;
+                }
+                let allowed = allowed_lints(val, path, diag);
+                suppressions.scoped.push((scope.clone(), allowed));
+                false
+            });
+        }
+    }
+
+    suppressions
+}
+
+/// Extracts the lint names from `{ "allow": ["lint-name", ...] }`.
+fn allowed_lints<'d>(
+    val: &Spanned<Json>,
+    path: &'d String,
+    diag: &mut Diagnostics<'d>,
+) -> Vec<Spanned<String>> {
+    let allow = match &val.val {
+        Json::Object(members) => members
+            .val
+            .iter()
+            .find(|(name, _)| name.val == "allow")
+            .map(|(_, allow)| allow),
+        _ => None,
+    };
+
+    let names = match allow.map(|allow| &allow.val) {
+        Some(Json::Array(names)) => names
+            .val
+            .iter()
+            .map(|name| match &name.val {
+                Json::Str(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>(),
+        _ => None,
+    };
+
+    names.unwrap_or_else(|| {
+        diag.push(
+            Report::build(ReportKind::Warning, path, val.span.start)
+                .with_message(format!(
+                    "malformed \"{}\" member",
+                    SUPPRESSION_MEMBER.fg(Color::Blue)
+                ))
+                .with_label(
+                    Label::new((path, val.span.into_range()))
+                        .with_color(Color::Yellow)
+                        .with_message("this is ignored"),
+                )
+                .with_help(format!(
+                    "expected e.g. {{ \"{}\": [\"{}\"] }}",
+                    "allow".fg(Color::Blue),
+                    "unused-custom-enemy-descriptors".fg(Color::Blue)
+                ))
+                .finish(),
+        );
+        Vec::new()
+    })
+}
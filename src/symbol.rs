@@ -0,0 +1,126 @@
+//! Interned names, e.g. of Enemy Descriptors. Lints and the graphs built from `Base` references
+//! compare, hash and clone names a lot more often than they read them, which is cheap for a
+//! [`Symbol`] but means allocating for a `String`.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The strings interned while lowering and linting one Custom Difficulty, see
+/// [`driver::lower`][crate::driver::lower]. Its symbols stay valid after it is dropped.
+#[derive(Debug, Default)]
+pub struct Interner(HashSet<Symbol>);
+
+impl Interner {
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(symbol) = self.0.get(s) {
+            return symbol.clone();
+        }
+        let symbol = Symbol(s.into());
+        self.0.insert(symbol.clone());
+        symbol
+    }
+}
+
+/// A string shared by everything which refers to it. Symbols are as cheap to clone as a reference
+/// count, compare equal symbols of the same [`Interner`] by pointer, and are hashed and ordered
+/// like the string they stand for.
+#[derive(Clone, Default)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Equal strings interned by the same [`Interner`] share a pointer, which is compared first.
+/// Symbols which weren't interned, e.g. the default one, are compared like their strings.
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl Eq for Symbol {}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+/// Hashed like the string, so that a `str` can be looked up in a map of symbols.
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// Ordered like the string, so that anything sorted by symbol comes out the same on every run.
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Symbol(String::deserialize(deserializer)?.into()))
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
@@ -0,0 +1,83 @@
+//! `--time-passes`, which reports how long parsing, lowering and each lint took, so that slow lints
+//! can be found. Times are added up over every file linted, e.g. in an archive, even when the files
+//! are linted in parallel.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// The time taken by each pass, in the order the passes first ran.
+static TIMINGS: Mutex<Vec<(Pass, Duration)>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pass {
+    Parsing,
+    /// Lowering the parsed JSON into a Custom Difficulty, including stripping suppressions.
+    Lowering,
+    Lint(&'static str),
+}
+
+impl fmt::Display for Pass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pass::Parsing => f.write_str("parsing"),
+            Pass::Lowering => f.write_str("lowering"),
+            Pass::Lint(lint) => write!(f, "lint `{lint}`"),
+        }
+    }
+}
+
+/// Starts recording how long passes take.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Runs the pass, recording how long it took if `--time-passes` is given.
+pub fn time<R>(pass: Pass, f: impl FnOnce() -> R) -> R {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    let mut timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    match timings.iter_mut().find(|(timed, _)| *timed == pass) {
+        Some((_, total)) => *total += elapsed,
+        None => timings.push((pass, elapsed)),
+    }
+    result
+}
+
+/// Prints how long each pass took to stderr, so that it doesn't mix with the diagnostics.
+pub fn print_report() {
+    let timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let total = timings
+        .iter()
+        .map(|(_, elapsed)| *elapsed)
+        .sum::<Duration>();
+    let percent = |elapsed: Duration| {
+        if total.is_zero() {
+            0.0
+        } else {
+            elapsed.as_secs_f64() / total.as_secs_f64() * 100.0
+        }
+    };
+
+    eprintln!();
+    eprintln!("time passes:");
+    for (pass, elapsed) in timings.iter() {
+        eprintln!(
+            "{:>12.3}ms {:>5.1}%  {pass}",
+            elapsed.as_secs_f64() * 1000.0,
+            percent(*elapsed)
+        );
+    }
+    eprintln!(
+        "{:>12.3}ms {:>5.1}%  total",
+        total.as_secs_f64() * 1000.0,
+        100.0
+    );
+}
@@ -0,0 +1,444 @@
+//! Property tests, guarding the parser and the handlers:
+//!
+//! - a Custom Difficulty resolves to the same JSON after its resolved JSON (see `cdlint resolve`)
+//!   is lowered again;
+//! - every span of a parsed value or a diagnostic is within the file, starts before it ends and
+//!   falls on character boundaries, whatever the input.
+
+use std::ops::Range;
+
+use cdlint::commands::resolve;
+use cdlint::config::Config;
+use cdlint::diagnostic::ReportKind;
+use cdlint::driver::{self, Input, Lowering};
+use cdlint::game_data::{self, GameData};
+use cdlint::lint::LintSelection;
+use cdlint::parser::{self, Json};
+use cdlint::spanned::{is_synthetic, Spanned};
+use cdlint::Diagnostics;
+use confique::Config as _;
+use proptest::prelude::*;
+use proptest::sample::{select, Index};
+use serde_json::{json, Map, Value};
+
+const PATH: &str = "properties.json";
+
+fn config() -> Config {
+    Config::builder()
+        .load()
+        .expect("the default config should load")
+}
+
+fn vanilla() -> &'static GameData {
+    GameData::for_version(game_data::LATEST).unwrap()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn resolving_round_trips(cd in custom_difficulty()) {
+        let config = config();
+        let resolved = lower_and_resolve(&config, &cd)?;
+        let resolved_again = lower_and_resolve(&config, &resolved)?;
+        prop_assert_eq!(resolved, resolved_again);
+    }
+
+    #[test]
+    fn spans_are_within_the_file(src in json_like()) {
+        check_spans(&src)?;
+    }
+
+    #[test]
+    fn spans_are_within_a_damaged_file(src in damaged_custom_difficulty()) {
+        check_spans(&src)?;
+    }
+}
+
+/// Lowers the Custom Difficulty, which must lower without errors, and resolves it.
+fn lower_and_resolve(config: &Config, cd: &Value) -> Result<Value, TestCaseError> {
+    let input = Input::new(PATH.to_string(), serde_json::to_string_pretty(cd).unwrap());
+    let loaded = match driver::lower(config, &LintSelection::default(), &input) {
+        Lowering::Lowered(loaded) => loaded,
+        Lowering::NothingToLower(diagnostics) | Lowering::Failed(diagnostics, _) => {
+            return Err(TestCaseError::fail(format!(
+                "failed to lower {}: {:?}",
+                input.src,
+                messages(&diagnostics)
+            )));
+        }
+    };
+    let errors = loaded
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.kind == ReportKind::Error)
+        .collect::<Vec<_>>();
+    prop_assert!(errors.is_empty(), "errors in {}: {:?}", input.src, errors);
+    Ok(resolve::resolved_json(&loaded.cd).0)
+}
+
+fn messages(diagnostics: &Diagnostics<'_>) -> Vec<String> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.clone().unwrap_or_default())
+        .collect()
+}
+
+/// Parses, lowers and lints the input, checking the spans of the parsed values and of every
+/// diagnostic.
+fn check_spans(src: &str) -> Result<(), TestCaseError> {
+    let config = config();
+    let input = Input::new(PATH.to_string(), src.to_string());
+
+    let parsed = parser::parse(&config, &input.path, &input.src);
+    if let Some(json) = &parsed.json {
+        check_value_spans(src, json, 0..src.len())?;
+    }
+
+    let selection = LintSelection::default();
+    let diagnostics = match driver::lower(&config, &selection, &input) {
+        Lowering::Lowered(mut loaded) => {
+            // Lints may fail, but the diagnostics they report until then are still checked.
+            let _ = driver::lint(&config, &selection, &input, &mut loaded);
+            loaded.diagnostics
+        }
+        Lowering::NothingToLower(diagnostics) | Lowering::Failed(diagnostics, _) => diagnostics,
+    };
+    for diagnostic in &diagnostics {
+        let message = diagnostic.message.as_deref().unwrap_or_default();
+        if diagnostic.offset != usize::MAX {
+            prop_assert!(
+                diagnostic.offset <= src.len(),
+                "offset of `{message}` is past the end"
+            );
+        }
+        let spans = diagnostic
+            .labels
+            .iter()
+            .map(|label| label.span.1.clone())
+            .chain(diagnostic.notes.iter().filter_map(|note| note.span.clone()))
+            .chain(
+                diagnostic
+                    .suggestions
+                    .iter()
+                    .map(|suggestion| suggestion.span.clone()),
+            );
+        for span in spans {
+            if !is_synthetic(&span) {
+                check_span(src, &span, 0..src.len(), message)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the spans of the value and of the values inside it, which have to be within `outer`.
+fn check_value_spans(
+    src: &str,
+    val: &Spanned<Json>,
+    outer: Range<usize>,
+) -> Result<(), TestCaseError> {
+    let span = val.span.into_range();
+    check_span(src, &span, outer, val.val.kind_desc())?;
+    let inner = match &val.val {
+        Json::Null(v) => v.span,
+        Json::Bool(v) => v.span,
+        Json::Str(v) => v.span,
+        Json::Num(v) => v.span,
+        Json::Array(v) => v.span,
+        Json::Object(v) => v.span,
+        Json::Invalid => return Ok(()),
+    };
+    check_span(src, &inner.into_range(), span.clone(), val.val.kind_desc())?;
+    match &val.val {
+        Json::Array(elements) => {
+            for element in &elements.val {
+                check_value_spans(src, element, span.clone())?;
+            }
+        }
+        Json::Object(members) => {
+            for (name, member_val) in &members.val {
+                check_span(src, &name.span.into_range(), span.clone(), "member name")?;
+                check_value_spans(src, member_val, span.clone())?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn check_span(
+    src: &str,
+    span: &Range<usize>,
+    outer: Range<usize>,
+    what: &str,
+) -> Result<(), TestCaseError> {
+    prop_assert!(
+        span.start <= span.end,
+        "span {span:?} of {what} is reversed"
+    );
+    prop_assert!(
+        outer.start <= span.start && span.end <= outer.end,
+        "span {span:?} of {what} is outside of {outer:?}"
+    );
+    prop_assert!(
+        src.is_char_boundary(span.start) && src.is_char_boundary(span.end),
+        "span {span:?} of {what} splits a character"
+    );
+    Ok(())
+}
+
+/// Text which looks like JSON, with the mistakes the parser recovers from.
+fn json_like() -> impl Strategy<Value = String> {
+    prop::collection::vec(fragment(), 0..48).prop_map(|fragments| fragments.concat())
+}
+
+/// A Custom Difficulty from the UI tests with fragments inserted into it.
+fn damaged_custom_difficulty() -> impl Strategy<Value = String> {
+    let cds = [
+        include_str!("ui/clean.json"),
+        include_str!("ui/enemy_descriptors.json"),
+        include_str!("ui/enemy_pools.json"),
+        include_str!("ui/pawn_stats.json"),
+        include_str!("ui/suppressions.json"),
+        include_str!("ui/values.json"),
+    ];
+    (
+        select(cds.to_vec()),
+        prop::collection::vec((any::<Index>(), fragment()), 1..4),
+    )
+        .prop_map(|(cd, insertions)| {
+            let mut cd = cd.to_string();
+            for (at, fragment) in insertions {
+                let boundaries = cd
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain([cd.len()])
+                    .collect::<Vec<_>>();
+                cd.insert_str(*at.get(&boundaries), &fragment);
+            }
+            cd
+        })
+}
+
+fn fragment() -> impl Strategy<Value = String> {
+    prop_oneof![
+        select(
+            &[
+                "{",
+                "}",
+                "[",
+                "]",
+                ",",
+                ":",
+                " ",
+                "\n",
+                "\"",
+                "'",
+                "null",
+                "None",
+                "true",
+                "True",
+                "-1",
+                "0.5",
+                "1e400",
+                "0x10",
+                "NaN",
+                "Infinity",
+                "// comment\n",
+                "/* comment */",
+                "\"Name\"",
+                "\"Base\"",
+                "\"EnemyDescriptors\"",
+                "\"ED_Spider_Grunt\"",
+                "\"add\"",
+                "\"__cdlint\"",
+                "\"allow\"",
+                "\"\\u00e9\"",
+                "\"\\",
+                "é",
+                "😀",
+                "\u{feff}",
+            ][..]
+        )
+        .prop_map(str::to_string),
+        "\\PC{0,4}",
+    ]
+}
+
+/// A Custom Difficulty which lowers without errors. Custom Enemy Descriptors are based on vanilla
+/// Enemy Descriptors or on custom Enemy Descriptors defined before them, so that they can all be
+/// resolved.
+fn custom_difficulty() -> impl Strategy<Value = Value> {
+    (
+        prop::collection::vec(top_level_member(), 0..12),
+        prop::collection::vec((any::<Index>(), enemy_descriptor_fields()), 0..4),
+        prop::collection::vec(enemy_pool(), 5),
+        prop::option::of(prop::sample::subsequence(vanilla().seasonal_events, 0..3)),
+    )
+        .prop_map(|(members, eds, pools, seasonal_events)| {
+            let mut cd = members.into_iter().collect::<Map<_, _>>();
+
+            let mut names = vanilla()
+                .enemy_descriptors
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>();
+            if !eds.is_empty() {
+                let mut defined = Map::new();
+                for (i, (base, mut fields)) in eds.into_iter().enumerate() {
+                    fields.insert("Base".to_string(), json!(base.get(&names)));
+                    let name = format!("ED_Custom_{i}");
+                    names.push(name.clone());
+                    defined.insert(name, Value::Object(fields));
+                }
+                cd.insert("EnemyDescriptors".to_string(), Value::Object(defined));
+            }
+
+            let pool_names = [
+                "EnemyPool",
+                "CommonEnemies",
+                "DisruptiveEnemies",
+                "SpecialEnemies",
+                "StationaryEnemies",
+            ];
+            for (name, pool) in pool_names.into_iter().zip(pools) {
+                if let Some((clear, add, remove)) = pool {
+                    let pick = |indices: Vec<Index>| {
+                        indices
+                            .iter()
+                            .map(|i| i.get(&names).clone())
+                            .collect::<Vec<_>>()
+                    };
+                    cd.insert(
+                        name.to_string(),
+                        json!({ "clear": clear, "add": pick(add), "remove": pick(remove) }),
+                    );
+                }
+            }
+
+            if let Some(seasonal_events) = seasonal_events {
+                cd.insert("SeasonalEvents".to_string(), json!(seasonal_events));
+            }
+            Value::Object(cd)
+        })
+}
+
+fn top_level_member() -> impl Strategy<Value = (String, Value)> {
+    let per_player_count = |number: BoxedStrategy<Value>| {
+        prop_oneof![
+            number.clone(),
+            prop::collection::vec(number, 1..5).prop_map(Value::from),
+        ]
+    };
+    let members = [
+        ("Name", "[A-Za-z0-9 ]{0,16}".prop_map(Value::from).boxed()),
+        (
+            "Description",
+            "[A-Za-z0-9 ]{0,32}".prop_map(Value::from).boxed(),
+        ),
+        ("MaxActiveCritters", per_player_count(count()).boxed()),
+        ("MaxActiveEnemies", per_player_count(count()).boxed()),
+        ("ResupplyCost", per_player_count(number()).boxed()),
+        ("StartingNitra", per_player_count(count()).boxed()),
+        ("EnemyDamageModifier", per_player_count(number()).boxed()),
+        ("EnemyCountModifier", per_player_count(number()).boxed()),
+        ("EncounterDifficulty", weighted_ranges(count()).boxed()),
+        ("EnemyWaveInterval", weighted_ranges(count()).boxed()),
+        ("VeteranNormal", weighted_ranges(number()).boxed()),
+        (
+            "DisruptiveEnemyPoolCount",
+            (count(), count())
+                .prop_map(|(min, max)| json!({ "min": min, "max": max }))
+                .boxed(),
+        ),
+        ("MinPoolSize", count()),
+        ("SpeedModifier", number()),
+        ("EliteCooldown", count()),
+        (
+            "EscortMule",
+            (number(), number(), number(), number())
+                .prop_map(|(ff, neutral, big_hit, threshold)| {
+                    json!({
+                        "FriendlyFireModifier": ff,
+                        "NeutralDamageModifier": neutral,
+                        "BigHitDamageModifier": big_hit,
+                        "BigHitDamageReductionThreshold": threshold,
+                    })
+                })
+                .boxed(),
+        ),
+    ];
+    select(members.to_vec()).prop_flat_map(|(name, val)| (Just(name.to_string()), val))
+}
+
+fn weighted_ranges(bound: BoxedStrategy<Value>) -> impl Strategy<Value = Value> {
+    prop::collection::vec(
+        (number(), bound.clone(), bound).prop_map(
+            |(weight, min, max)| json!({ "weight": weight, "range": { "min": min, "max": max } }),
+        ),
+        0..4,
+    )
+    .prop_map(Value::from)
+}
+
+/// The fields of an Enemy Descriptor, other than `Base`.
+fn enemy_descriptor_fields() -> impl Strategy<Value = Map<String, Value>> {
+    let fields = [
+        ("SpawnSpread", number()),
+        ("IdealSpawnSize", count()),
+        (
+            "CanBeUsedInEncounters",
+            any::<bool>().prop_map(Value::from).boxed(),
+        ),
+        ("DifficultyRating", number()),
+        ("MinSpawnCount", count()),
+        ("MaxSpawnCount", count()),
+        ("Rarity", number()),
+        ("Elite", any::<bool>().prop_map(Value::from).boxed()),
+        ("Scale", number()),
+        (
+            "PawnStats",
+            prop::collection::vec((select(vanilla().pawn_stats), number()), 1..3)
+                .prop_map(|stats| {
+                    Value::Object(
+                        stats
+                            .into_iter()
+                            .map(|(stat, val)| (stat.to_string(), val))
+                            .collect(),
+                    )
+                })
+                .boxed(),
+        ),
+    ];
+    prop::sample::subsequence(fields.to_vec(), 0..fields.len()).prop_flat_map(|fields| {
+        fields
+            .into_iter()
+            .map(|(name, val)| (Just(name.to_string()), val))
+            .collect::<Vec<_>>()
+            .prop_map(|fields| fields.into_iter().collect::<Map<_, _>>())
+    })
+}
+
+/// Whether the pool is cleared, and which Enemy Descriptors are added and removed, if the pool is
+/// specified.
+#[allow(clippy::type_complexity)]
+fn enemy_pool() -> impl Strategy<Value = Option<(bool, Vec<Index>, Vec<Index>)>> {
+    prop::option::of((
+        any::<bool>(),
+        prop::collection::vec(any::<Index>(), 0..4),
+        prop::collection::vec(any::<Index>(), 0..3),
+    ))
+}
+
+fn number() -> BoxedStrategy<Value> {
+    prop_oneof![
+        (0.0..1000.0f64).prop_map(Value::from),
+        (0u32..1000).prop_map(|n| Value::from(f64::from(n))),
+    ]
+    .boxed()
+}
+
+fn count() -> BoxedStrategy<Value> {
+    (0u32..1000).prop_map(Value::from).boxed()
+}
@@ -0,0 +1,188 @@
+//! UI tests: each `tests/ui/<name>.json` fixture is linted, and what CDLint prints is compared with
+//! the expected output next to it, `<name>.stdout` for the diagnostics and summary and
+//! `<name>.stderr` for errors, which is left out if nothing is printed to stderr.
+//!
+//! Extra arguments for a fixture, e.g. `--error-format short`, can be given in `<name>.args`.
+//!
+//! Each fixture is linted twice, checking that the second run, which reuses the lint results
+//! cached by the first, prints the same.
+//!
+//! Run `cargo test --test ui -- --bless` to update the expected output after changing a diagnostic,
+//! and review the changes like any other change. A fixture name can also be given to only run the
+//! fixtures whose names contain it.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn main() {
+    let mut bless = false;
+    let mut filter = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--bless" => bless = true,
+            // Passed by `cargo test` to every test target.
+            arg if arg.starts_with('-') => {}
+            arg => filter = Some(arg.to_string()),
+        }
+    }
+
+    let ui_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("ui");
+    let mut fixtures = std::fs::read_dir(&ui_dir)
+        .expect("failed to read `tests/ui`")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .filter(|path| {
+            filter
+                .as_ref()
+                .is_none_or(|filter| path.to_string_lossy().contains(filter.as_str()))
+        })
+        .collect::<Vec<_>>();
+    fixtures.sort();
+
+    let cdlint = isolated_cdlint();
+    println!("\nrunning {} ui tests", fixtures.len());
+    let mut failed = Vec::new();
+    for fixture in &fixtures {
+        let name = fixture.file_name().unwrap().to_string_lossy();
+        match run_fixture(&cdlint, &ui_dir, fixture, bless) {
+            Ok(()) => println!("test ui/{name} ... ok"),
+            Err(mismatch) => {
+                println!("test ui/{name} ... FAILED");
+                failed.push((name.to_string(), mismatch));
+            }
+        }
+    }
+    let _ = std::fs::remove_dir_all(cdlint.parent().unwrap());
+
+    for (name, mismatch) in &failed {
+        println!("\n---- ui/{name} ----\n{mismatch}");
+    }
+    if !failed.is_empty() {
+        println!("\nrun `cargo test --test ui -- --bless` to update the expected output");
+        println!(
+            "\ntest result: FAILED. {} passed; {} failed",
+            fixtures.len() - failed.len(),
+            failed.len()
+        );
+        std::process::exit(1);
+    }
+    println!("\ntest result: ok. {} passed; 0 failed", fixtures.len());
+}
+
+/// A copy of the executable in its own directory, so that it runs with the default config and
+/// without the plugins or downloaded game data next to the built executable.
+fn isolated_cdlint() -> PathBuf {
+    let exe = Path::new(env!("CARGO_BIN_EXE_cdlint"));
+    let dir = std::env::temp_dir().join(format!("cdlint-ui-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("failed to create the test directory");
+    let cdlint = dir.join(exe.file_name().unwrap());
+    std::fs::copy(exe, &cdlint).expect("failed to copy the executable");
+    cdlint
+}
+
+/// Lints the fixture and compares the output with the expected output, or updates the expected
+/// output when blessing.
+fn run_fixture(cdlint: &Path, ui_dir: &Path, fixture: &Path, bless: bool) -> Result<(), String> {
+    let args_path = fixture.with_extension("args");
+    let args = std::fs::read_to_string(&args_path).unwrap_or_default();
+
+    let output = run_cdlint(cdlint, ui_dir, &args, fixture);
+    let cached_output = run_cdlint(cdlint, ui_dir, &args, fixture);
+
+    let mut mismatches = Vec::new();
+    for (extension, actual, cached) in [
+        ("stdout", &output.stdout, &cached_output.stdout),
+        ("stderr", &output.stderr, &cached_output.stderr),
+    ] {
+        let expected_path = fixture.with_extension(extension);
+        let actual = String::from_utf8_lossy(actual).replace("\r\n", "\n");
+        let cached = String::from_utf8_lossy(cached).replace("\r\n", "\n");
+        if cached != actual {
+            mismatches.push(format!(
+                "{extension} with cached lint results does not match {extension} without\n{}",
+                diff(&actual, &cached)
+            ));
+        }
+        let expected = std::fs::read_to_string(&expected_path)
+            .unwrap_or_default()
+            .replace("\r\n", "\n");
+        if actual == expected {
+            continue;
+        }
+        if bless {
+            if actual.is_empty() {
+                std::fs::remove_file(&expected_path).unwrap();
+            } else {
+                std::fs::write(&expected_path, &actual).unwrap();
+            }
+            continue;
+        }
+        mismatches.push(format!(
+            "{extension} does not match `{}`\n{}",
+            expected_path.file_name().unwrap().to_string_lossy(),
+            diff(&expected, &actual)
+        ));
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches.join("\n"))
+    }
+}
+
+fn run_cdlint(cdlint: &Path, ui_dir: &Path, args: &str, fixture: &Path) -> Output {
+    // Run from `tests/ui` so that diagnostics show the fixture's file name rather than an absolute
+    // path.
+    Command::new(cdlint)
+        .current_dir(ui_dir)
+        .args(["--color", "never"])
+        .args(args.split_whitespace())
+        .arg(fixture.file_name().unwrap())
+        .env("RUST_LOG", "off")
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run cdlint")
+}
+
+/// A line diff of the expected and actual output: the lines around the first difference, with
+/// removed lines prefixed by `-` and added lines by `+`.
+fn diff(expected: &str, actual: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let common_prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(e, a)| e == a)
+        .count();
+    let common_suffix = expected[common_prefix..]
+        .iter()
+        .rev()
+        .zip(actual[common_prefix..].iter().rev())
+        .take_while(|(e, a)| e == a)
+        .count();
+
+    let mut diff = String::new();
+    let start = common_prefix.saturating_sub(CONTEXT);
+    for line in &expected[start..common_prefix] {
+        diff.push_str(&format!(" {line}\n"));
+    }
+    for line in &expected[common_prefix..expected.len() - common_suffix] {
+        diff.push_str(&format!("-{line}\n"));
+    }
+    for line in &actual[common_prefix..actual.len() - common_suffix] {
+        diff.push_str(&format!("+{line}\n"));
+    }
+    let end = (expected.len() - common_suffix + CONTEXT).min(expected.len());
+    for line in &expected[expected.len() - common_suffix..end] {
+        diff.push_str(&format!(" {line}\n"));
+    }
+    diff
+}